@@ -0,0 +1,454 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    open_ledger,
+    storage_mode,
+    write_blocks_file_block,
+    write_blocks_file_header,
+    CurrentLedger,
+    CurrentNetwork,
+};
+
+use snarkvm::{console::network::Network, ledger::block::Block};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+#[cfg(feature = "parquet")]
+use parquet::{
+    basic::Compression,
+    data_type::{ByteArray, ByteArrayType, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+use serde_json::{json, Value};
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The number of blocks exported between progress reports.
+const EXPORT_PROGRESS_INTERVAL: u32 = 1_000;
+/// The number of rows in each row group of a Parquet dataset.
+#[cfg(feature = "parquet")]
+const PARQUET_ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// The format of an exported dataset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Newline-delimited JSON.
+    Json,
+    /// Comma-separated values, with a header row.
+    Csv,
+    /// The serialized blocks, as a single blocks file for `snarkos ledger import`.
+    Raw,
+    /// Apache Parquet, compressed with Zstandard.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "raw" => Ok(Self::Raw),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Self::Parquet),
+            #[cfg(not(feature = "parquet"))]
+            "parquet" => bail!("The 'parquet' export format requires snarkOS to be built with the 'parquet' feature"),
+            _ => bail!("Unknown export format '{format}' (expected 'json', 'csv', 'raw', or 'parquet')"),
+        }
+    }
+}
+
+impl ExportFormat {
+    /// Returns the file extension of the format.
+    const fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "ndjson",
+            Self::Csv => "csv",
+            Self::Raw => "bin",
+            #[cfg(feature = "parquet")]
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// Exports the blocks, transactions, and transitions of the ledger as datasets.
+#[derive(Debug, Parser)]
+pub struct ExportLedger {
+    /// The directory to write the datasets to, which must not exist.
+    pub path: PathBuf,
+    /// Specify the format of the datasets [options: json, csv, raw, parquet]
+    #[clap(default_value = "json", long = "format")]
+    pub format: ExportFormat,
+    /// Specify the block heights to export, as `start..end` (exclusive), or `start..` for every later block
+    #[clap(default_value = "0..", long = "range")]
+    pub range: String,
+    /// Specify the network of the ledger.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl ExportLedger {
+    /// Exports the blocks, transactions, and transitions of the ledger as datasets.
+    pub fn parse(self) -> Result<String> {
+        ensure!(!self.path.exists(), "The export directory '{}' already exists", self.path.display());
+        let ledger = open_ledger(self.network, storage_mode(&self.storage_path))?;
+        let heights = parse_range(&self.range, ledger.latest_height().saturating_add(1))?;
+        println!("📤 Exporting blocks {} to {} of the ledger\n", heights.start, heights.end.saturating_sub(1));
+
+        fs::create_dir_all(&self.path)?;
+        if self.format == ExportFormat::Raw {
+            return export_blocks_file(&ledger, heights, &self.path.join("blocks.bin"));
+        }
+        let mut blocks = DatasetWriter::new(&self.path, "blocks", self.format)?;
+        let mut transactions = DatasetWriter::new(&self.path, "transactions", self.format)?;
+        let mut transitions = DatasetWriter::new(&self.path, "transitions", self.format)?;
+
+        for height in heights.clone() {
+            let block = ledger.get_block(height)?;
+            blocks.write_row(&block_row(&block))?;
+            for (index, confirmed) in block.transactions().iter().enumerate() {
+                transactions.write_row(&transaction_row(height, index, confirmed)?)?;
+                for (index, transition) in confirmed.transaction().transitions().enumerate() {
+                    transitions.write_row(&[
+                        ("block_height", json!(height)),
+                        ("transaction_id", json!(confirmed.id().to_string())),
+                        ("index", json!(index)),
+                        ("id", json!(transition.id().to_string())),
+                        ("program_id", json!(transition.program_id().to_string())),
+                        ("function_name", json!(transition.function_name().to_string())),
+                        ("inputs", json!(transition.inputs().len())),
+                        ("outputs", json!(transition.outputs().len())),
+                    ])?;
+                }
+            }
+            if (height + 1 - heights.start) % EXPORT_PROGRESS_INTERVAL == 0 {
+                println!("{} {}", format!("[{height}/{}]", heights.end - 1).bold(), "blocks exported".dimmed());
+            }
+        }
+        for dataset in [blocks, transactions, transitions] {
+            dataset.finish()?;
+        }
+
+        Ok(format!("✅ Exported {} blocks of the ledger to '{}'", heights.len(), self.path.display()))
+    }
+}
+
+/// Exports the blocks in the given range as a blocks file.
+fn export_blocks_file(ledger: &CurrentLedger, heights: Range<u32>, path: &Path) -> Result<String> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_blocks_file_header(&mut writer, CurrentNetwork::ID)?;
+    for height in heights.clone() {
+        write_blocks_file_block(&mut writer, &ledger.get_block(height)?)?;
+        if (height + 1 - heights.start) % EXPORT_PROGRESS_INTERVAL == 0 {
+            println!("{} {}", format!("[{height}/{}]", heights.end - 1).bold(), "blocks exported".dimmed());
+        }
+    }
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    Ok(format!("✅ Exported {} blocks of the ledger to '{}'", heights.len(), path.display()))
+}
+
+/// Parses the given `start..end` or `start..` block range, bounded by the given end height.
+fn parse_range(range: &str, max_end: u32) -> Result<Range<u32>> {
+    let (start, end) = range.split_once("..").ok_or_else(|| anyhow!("Invalid block range '{range}'"))?;
+    let start = start.parse::<u32>().map_err(|_| anyhow!("Invalid block range '{range}'"))?;
+    let end = match end {
+        "" => max_end,
+        end => end.parse::<u32>().map_err(|_| anyhow!("Invalid block range '{range}'"))?.min(max_end),
+    };
+    ensure!(start < end, "The block range '{range}' is empty (the ledger ends at block {})", max_end - 1);
+    Ok(start..end)
+}
+
+/// Returns the dataset row of the given block.
+fn block_row(block: &Block<CurrentNetwork>) -> Vec<(&'static str, Value)> {
+    vec![
+        ("height", json!(block.height())),
+        ("hash", json!(block.hash().to_string())),
+        ("previous_hash", json!(block.previous_hash().to_string())),
+        ("round", json!(block.round())),
+        ("timestamp", json!(block.timestamp())),
+        ("transactions", json!(block.transactions().len())),
+        ("cumulative_weight", json!(block.cumulative_weight().to_string())),
+    ]
+}
+
+/// Returns the dataset row of the given transaction.
+fn transaction_row(
+    height: u32,
+    index: usize,
+    confirmed: &snarkvm::ledger::block::ConfirmedTransaction<CurrentNetwork>,
+) -> Result<Vec<(&'static str, Value)>> {
+    let transaction = confirmed.transaction();
+    let kind = match (transaction.is_deploy(), transaction.is_execute()) {
+        (true, _) => "deploy",
+        (_, true) => "execute",
+        _ => "fee",
+    };
+    let status = match confirmed.is_accepted() {
+        true => "accepted",
+        false => "rejected",
+    };
+    Ok(vec![
+        ("block_height", json!(height)),
+        ("index", json!(index)),
+        ("id", json!(confirmed.id().to_string())),
+        ("type", json!(kind)),
+        ("status", json!(status)),
+        ("fee", json!(*transaction.fee_amount()?)),
+        ("transitions", json!(transaction.transitions().count())),
+    ])
+}
+
+/// A dataset file, written one row at a time.
+struct DatasetWriter {
+    /// The format of the dataset.
+    format: ExportFormat,
+    /// The writer of the dataset file.
+    writer: DatasetFile,
+    /// Whether any row was written, as the first row of a CSV file is preceded by the header.
+    has_rows: bool,
+}
+
+/// The writer of a dataset file.
+enum DatasetFile {
+    /// A text file, with one row per line.
+    Text(BufWriter<File>),
+    /// A Parquet file.
+    #[cfg(feature = "parquet")]
+    Parquet(Box<ParquetDataset>),
+}
+
+impl DatasetWriter {
+    /// Creates the file of the given dataset in the given directory.
+    fn new(directory: &Path, name: &str, format: ExportFormat) -> Result<Self> {
+        let file = File::create(directory.join(format!("{name}.{}", format.extension())))?;
+        let writer = match format {
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => DatasetFile::Parquet(Box::new(ParquetDataset::new(name, file))),
+            ExportFormat::Json | ExportFormat::Csv | ExportFormat::Raw => DatasetFile::Text(BufWriter::new(file)),
+        };
+        Ok(Self { format, writer, has_rows: false })
+    }
+
+    /// Writes the given row.
+    fn write_row(&mut self, row: &[(&'static str, Value)]) -> Result<()> {
+        match &mut self.writer {
+            DatasetFile::Text(writer) => write_text_row(writer, self.format, !self.has_rows, row)?,
+            #[cfg(feature = "parquet")]
+            DatasetFile::Parquet(dataset) => dataset.write_row(row)?,
+        }
+        self.has_rows = true;
+        Ok(())
+    }
+
+    /// Flushes the dataset to disk.
+    fn finish(self) -> Result<()> {
+        match self.writer {
+            DatasetFile::Text(mut writer) => {
+                writer.flush()?;
+                Ok(writer.get_ref().sync_all()?)
+            }
+            #[cfg(feature = "parquet")]
+            DatasetFile::Parquet(dataset) => dataset.finish(),
+        }
+    }
+}
+
+/// Writes the given row as a line of the given text format.
+fn write_text_row(
+    writer: &mut impl Write,
+    format: ExportFormat,
+    is_first_row: bool,
+    row: &[(&'static str, Value)],
+) -> Result<()> {
+    match format {
+        ExportFormat::Json => {
+            let object = row.iter().map(|(name, value)| (name.to_string(), value.clone())).collect();
+            serde_json::to_writer(&mut *writer, &Value::Object(object))?;
+        }
+        ExportFormat::Raw => bail!("The 'raw' export format has no datasets"),
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => bail!("The 'parquet' export format is not a text format"),
+        ExportFormat::Csv => {
+            if is_first_row {
+                writeln!(writer, "{}", row.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(","))?;
+            }
+            let values = row.iter().map(|(_, value)| csv_field(value)).collect::<Vec<_>>();
+            write!(writer, "{}", values.join(","))?;
+        }
+    }
+    Ok(writeln!(writer)?)
+}
+
+/// A Parquet dataset, written one row group at a time.
+/// The schema is inferred from the first row: numbers are stored as `INT64`, and every other value as UTF-8 text.
+#[cfg(feature = "parquet")]
+struct ParquetDataset {
+    /// The name of the dataset, which is the name of the Parquet schema.
+    name: String,
+    /// The dataset file, until the first row group is written.
+    file: Option<File>,
+    /// The writer of the dataset file, from the first row group on.
+    writer: Option<SerializedFileWriter<File>>,
+    /// The rows of the next row group.
+    rows: Vec<Vec<(&'static str, Value)>>,
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetDataset {
+    /// Initializes a new Parquet dataset, written to the given file.
+    fn new(name: &str, file: File) -> Self {
+        Self { name: name.to_string(), file: Some(file), writer: None, rows: Vec::new() }
+    }
+
+    /// Writes the given row, and the row group once it is full.
+    fn write_row(&mut self, row: &[(&'static str, Value)]) -> Result<()> {
+        self.rows.push(row.to_vec());
+        if self.rows.len() >= PARQUET_ROW_GROUP_SIZE {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered rows as a row group.
+    fn write_row_group(&mut self) -> Result<()> {
+        // Initialize the writer with the schema of the first row.
+        if self.writer.is_none() {
+            let Some(first_row) = self.rows.first() else { return Ok(()) };
+            let file = self.file.take().ok_or_else(|| anyhow!("The Parquet dataset is already finished"))?;
+            let schema = Arc::new(parse_message_type(&parquet_schema(&self.name, first_row))?);
+            let properties = WriterProperties::builder().set_compression(Compression::ZSTD(Default::default())).build();
+            self.writer = Some(SerializedFileWriter::new(file, schema, Arc::new(properties))?);
+        }
+        let (Some(writer), Some(first_row)) = (self.writer.as_mut(), self.rows.first()) else { return Ok(()) };
+
+        let mut row_group = writer.next_row_group()?;
+        for (index, (name, value)) in first_row.iter().enumerate() {
+            let Some(mut column) = row_group.next_column()? else { bail!("The Parquet schema has no column '{name}'") };
+            let values = self.rows.iter().map(|row| row.get(index).map(|(_, value)| value));
+            match value {
+                Value::Number(_) => {
+                    let values = values
+                        .map(|value| value.and_then(Value::as_i64).ok_or_else(|| anyhow!("Invalid '{name}' value")))
+                        .collect::<Result<Vec<_>>>()?;
+                    column.typed::<Int64Type>().write_batch(&values, None, None)?;
+                }
+                _ => {
+                    let values = values
+                        .map(|value| value.map(|value| ByteArray::from(text_field(value).into_bytes())))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| anyhow!("Invalid '{name}' value"))?;
+                    column.typed::<ByteArrayType>().write_batch(&values, None, None)?;
+                }
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+        self.rows.clear();
+        Ok(())
+    }
+
+    /// Writes the remaining rows and the file footer, and flushes the dataset to disk.
+    /// Note: The file of a dataset without rows is left empty, as the schema is inferred from the rows.
+    fn finish(mut self) -> Result<()> {
+        self.write_row_group()?;
+        match (self.writer, self.file) {
+            (Some(writer), _) => writer.into_inner()?.sync_all()?,
+            (None, Some(file)) => file.sync_all()?,
+            (None, None) => (),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the Parquet schema of the given dataset, with the columns of the given row.
+#[cfg(feature = "parquet")]
+fn parquet_schema(name: &str, row: &[(&'static str, Value)]) -> String {
+    let columns = row
+        .iter()
+        .map(|(name, value)| match value {
+            Value::Number(_) => format!("REQUIRED INT64 {name};"),
+            _ => format!("REQUIRED BINARY {name} (UTF8);"),
+        })
+        .collect::<Vec<_>>();
+    format!("message {name} {{ {} }}", columns.join(" "))
+}
+
+/// Returns the given value as text.
+fn text_field(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Formats the given value as a CSV field, quoting it if needed.
+fn csv_field(value: &Value) -> String {
+    let field = text_field(value);
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("0..10", 100).unwrap(), 0..10);
+        assert_eq!(parse_range("5..", 100).unwrap(), 5..100);
+        assert_eq!(parse_range("5..1000", 100).unwrap(), 5..100);
+        assert!(parse_range("10..10", 100).is_err());
+        assert!(parse_range("100..", 100).is_err());
+        assert!(parse_range("10", 100).is_err());
+        assert!(parse_range("a..b", 100).is_err());
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field(&json!(42)), "42");
+        assert_eq!(csv_field(&json!("credits.aleo")), "credits.aleo");
+        assert_eq!(csv_field(&json!("a,\"b\"")), "\"a,\"\"b\"\"\"");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_schema() {
+        let row = [("height", json!(1)), ("hash", json!("ab1")), ("fee", json!(u64::MAX.to_string()))];
+        let schema = parquet_schema("blocks", &row);
+        assert_eq!(
+            schema,
+            "message blocks { REQUIRED INT64 height; REQUIRED BINARY hash (UTF8); REQUIRED BINARY fee (UTF8); }"
+        );
+        assert!(parse_message_type(&schema).is_ok());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{open_ledger, storage_mode, CurrentLedger, CurrentNetwork};
+
+use snarkvm::{
+    console::network::Network,
+    ledger::block::Block,
+    prelude::{FromBytes, ToBytes},
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use rayon::prelude::*;
+use std::{
+    fs::File,
+    io::{BufReader, ErrorKind, Read, Write},
+    path::PathBuf,
+    time::Instant,
+};
+
+/// The bytes that begin a blocks file.
+const BLOCKS_FILE_MAGIC: &[u8; 8] = b"SNOSBLKS";
+/// The maximum size of a serialized block in a blocks file, matching the maximum size of a network message.
+const MAX_BLOCK_BYTES: usize = 128 * 1024 * 1024;
+/// The number of blocks deserialized at once, in parallel, before they are added to the ledger.
+const IMPORT_BATCH_SIZE: usize = 64;
+/// The number of blocks imported between progress reports.
+const IMPORT_PROGRESS_INTERVAL: u32 = 1_000;
+
+/// Imports the blocks of a blocks file into the ledger, rebuilding its indexes.
+#[derive(Debug, Parser)]
+pub struct ImportLedger {
+    /// The blocks file to import, as written by `snarkos ledger export --format raw`.
+    pub path: PathBuf,
+    /// Add the blocks without checking them first; only use this with a blocks file from a trusted node.
+    #[clap(long = "unchecked")]
+    pub unchecked: bool,
+    /// Specify the network of the ledger.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl ImportLedger {
+    /// Imports the blocks of the blocks file into the ledger.
+    pub fn parse(self) -> Result<String> {
+        let mut reader = BlocksFileReader::open(&self.path)?;
+        let ledger = open_ledger(self.network, storage_mode(&self.storage_path))?;
+        let start_height = ledger.latest_height();
+        println!("📥 Importing blocks from '{}' onto block {start_height} of the ledger\n", self.path.display());
+
+        let timer = Instant::now();
+        let mut num_imported = 0u32;
+        loop {
+            // Read a batch of blocks, and deserialize them in parallel.
+            let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+            while batch.len() < IMPORT_BATCH_SIZE {
+                match reader.next_block_bytes()? {
+                    Some(bytes) => batch.push(bytes),
+                    None => break,
+                }
+            }
+            if batch.is_empty() {
+                break;
+            }
+            let blocks = batch
+                .into_par_iter()
+                .map(|bytes| Block::<CurrentNetwork>::from_bytes_le(&bytes))
+                .collect::<Result<Vec<_>>>()?;
+
+            for block in blocks {
+                if import_block(&ledger, &block, self.unchecked)? {
+                    num_imported += 1;
+                    if num_imported % IMPORT_PROGRESS_INTERVAL == 0 {
+                        let blocks_per_sec = f64::from(num_imported) / timer.elapsed().as_secs_f64().max(1e-3);
+                        println!(
+                            "{} {}",
+                            format!("[{}]", block.height()).bold(),
+                            format!("blocks imported ({blocks_per_sec:.1} blocks/s)").dimmed()
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(format!("✅ Imported {num_imported} blocks, the ledger is at block {}", ledger.latest_height()))
+    }
+}
+
+/// Adds the given block to the ledger, returning `false` if the ledger already contains it.
+fn import_block(ledger: &CurrentLedger, block: &Block<CurrentNetwork>, unchecked: bool) -> Result<bool> {
+    let height = block.height();
+    let latest_height = ledger.latest_height();
+    // Skip the blocks the ledger already contains, as long as they match.
+    if height <= latest_height {
+        ensure!(
+            ledger.get_hash(height)? == block.hash(),
+            "Block {height} of the blocks file does not match the ledger (it belongs to another chain)"
+        );
+        return Ok(false);
+    }
+    ensure!(height == latest_height + 1, "The blocks file skips from block {latest_height} to block {height}");
+
+    if !unchecked {
+        ledger.check_next_block(block, &mut rand::thread_rng())?;
+    }
+    ledger.advance_to_next_block(block)?;
+    Ok(true)
+}
+
+/// Writes the header of a blocks file for the given network.
+pub(crate) fn write_blocks_file_header(writer: &mut impl Write, network: u16) -> Result<()> {
+    writer.write_all(BLOCKS_FILE_MAGIC)?;
+    writer.write_all(&network.to_le_bytes())?;
+    Ok(())
+}
+
+/// Writes the given block to a blocks file, prefixed by its length.
+pub(crate) fn write_blocks_file_block(writer: &mut impl Write, block: &Block<CurrentNetwork>) -> Result<()> {
+    let bytes = block.to_bytes_le()?;
+    writer.write_all(&u32::try_from(bytes.len())?.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// A reader of the serialized blocks of a blocks file.
+struct BlocksFileReader<R: Read> {
+    reader: R,
+}
+
+impl BlocksFileReader<BufReader<File>> {
+    /// Opens the given blocks file, and checks its header.
+    fn open(path: &std::path::Path) -> Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> BlocksFileReader<R> {
+    /// Initializes a reader of the given blocks file, and checks its header.
+    fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        ensure!(&magic == BLOCKS_FILE_MAGIC, "The file is not a blocks file");
+        let mut network = [0u8; 2];
+        reader.read_exact(&mut network)?;
+        let network = u16::from_le_bytes(network);
+        ensure!(network == CurrentNetwork::ID, "The blocks file is for network '{network}'");
+        Ok(Self { reader })
+    }
+
+    /// Returns the bytes of the next block, or `None` at the end of the file.
+    fn next_block_bytes(&mut self) -> Result<Option<Vec<u8>>> {
+        // Read the length prefix, which may only be missing entirely at the end of the file.
+        let mut length = [0u8; 4];
+        let mut num_read = 0;
+        while num_read < length.len() {
+            match self.reader.read(&mut length[num_read..]) {
+                Ok(0) if num_read == 0 => return Ok(None),
+                Ok(0) => bail!("The blocks file is truncated"),
+                Ok(n) => num_read += n,
+                Err(error) if error.kind() == ErrorKind::Interrupted => (),
+                Err(error) => return Err(error.into()),
+            }
+        }
+        let length = u32::from_le_bytes(length) as usize;
+        ensure!(length <= MAX_BLOCK_BYTES, "The blocks file holds an oversized block ({length} bytes)");
+        let mut bytes = vec![0u8; length];
+        if let Err(error) = self.reader.read_exact(&mut bytes) {
+            match error.kind() {
+                ErrorKind::UnexpectedEof => bail!("The blocks file is truncated"),
+                _ => return Err(error.into()),
+            }
+        }
+        Ok(Some(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{ledger::ledger_test_helpers::sample_genesis_block, utilities::TestRng};
+
+    #[test]
+    fn test_blocks_file() {
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut file = Vec::new();
+        write_blocks_file_header(&mut file, CurrentNetwork::ID).unwrap();
+        write_blocks_file_block(&mut file, &genesis).unwrap();
+        write_blocks_file_block(&mut file, &genesis).unwrap();
+
+        let mut reader = BlocksFileReader::new(file.as_slice()).unwrap();
+        for _ in 0..2 {
+            let bytes = reader.next_block_bytes().unwrap().unwrap();
+            assert_eq!(Block::<CurrentNetwork>::from_bytes_le(&bytes).unwrap(), genesis);
+        }
+        assert!(reader.next_block_bytes().unwrap().is_none());
+
+        // A truncated file is rejected.
+        let mut reader = BlocksFileReader::new(&file[..file.len() - 1]).unwrap();
+        assert!(reader.next_block_bytes().is_ok());
+        assert!(reader.next_block_bytes().is_err());
+        // A file truncated within a length prefix is rejected.
+        let block_end = BLOCKS_FILE_MAGIC.len() + 2 + 4 + genesis.to_bytes_le().unwrap().len();
+        let mut reader = BlocksFileReader::new(&file[..block_end + 2]).unwrap();
+        assert!(reader.next_block_bytes().is_ok());
+        assert!(reader.next_block_bytes().is_err());
+        // A file of another network is rejected.
+        let mut other = Vec::new();
+        write_blocks_file_header(&mut other, CurrentNetwork::ID + 1).unwrap();
+        assert!(BlocksFileReader::new(other.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_import_block() {
+        let directory = std::env::temp_dir().join(format!("snarkos-import-test-{}", std::process::id()));
+        let ledger = open_ledger(CurrentNetwork::ID, storage_mode(&Some(directory.clone()))).unwrap();
+        let genesis = ledger.get_block(0).unwrap();
+
+        // A block the ledger already contains is skipped.
+        assert!(!import_block(&ledger, &genesis, false).unwrap());
+        assert!(!import_block(&ledger, &genesis, true).unwrap());
+        // A block of another chain is rejected, whether or not it is checked.
+        let other = sample_genesis_block(&mut TestRng::default());
+        assert_ne!(other.hash(), genesis.hash());
+        assert!(import_block(&ledger, &other, false).is_err());
+        assert!(import_block(&ledger, &other, true).is_err());
+        assert_eq!(ledger.latest_height(), 0);
+
+        drop(ledger);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod export;
+pub use export::*;
+
+mod import;
+pub use import::*;
+
+mod stats;
+pub use stats::*;
+
+mod verify;
+pub use verify::*;
+
+use snarkvm::{
+    console::network::{MainnetV0, Network},
+    ledger::{block::Block, store::helpers::rocksdb::ConsensusDB},
+    prelude::FromBytes,
+};
+
+use aleo_std::StorageMode;
+use anyhow::{ensure, Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+type CurrentNetwork = MainnetV0;
+
+/// The ledger of the current network, stored in RocksDB.
+pub(crate) type CurrentLedger = snarkvm::ledger::Ledger<CurrentNetwork, ConsensusDB<CurrentNetwork>>;
+
+/// Commands to inspect and maintain the ledger storage. The node must be stopped.
+#[derive(Debug, Parser)]
+pub enum Ledger {
+    /// Export the blocks, transactions, and transitions of the ledger as datasets.
+    Export(ExportLedger),
+    /// Import the blocks of a blocks file exported by another node, rebuilding the indexes of the ledger.
+    Import(ImportLedger),
+    /// Report the storage statistics of the ledger, and project its growth.
+    Stats(LedgerStats),
+    /// Verify the blocks and indexes of the ledger.
+    Verify(VerifyLedger),
+}
+
+impl Ledger {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Export(command) => command.parse(),
+            Self::Import(command) => command.parse(),
+            Self::Stats(command) => command.parse(),
+            Self::Verify(command) => command.parse(),
+        }
+    }
+}
+
+/// Returns the storage mode for the given ledger path.
+pub(crate) fn storage_mode(storage_path: &Option<PathBuf>) -> StorageMode {
+    match storage_path {
+        Some(path) => StorageMode::Custom(path.clone()),
+        None => StorageMode::Production,
+    }
+}
+
+/// Opens the ledger of the given network, which fails if a running node holds the database.
+pub(crate) fn open_ledger(network: u16, storage_mode: StorageMode) -> Result<CurrentLedger> {
+    ensure!(network == CurrentNetwork::ID, "Unsupported network ID '{network}'");
+    let genesis = Block::from_bytes_le(CurrentNetwork::genesis_bytes())?;
+    CurrentLedger::load(genesis, storage_mode)
+        .context("Failed to open the ledger (if a node is running, stop it first)")
+}
@@ -0,0 +1,187 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{open_ledger, storage_mode, CurrentLedger};
+
+use snarkos_node::StorageStats;
+use snarkvm::prelude::ToBytes;
+
+use anyhow::{ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+/// The number of latest blocks measured by default.
+const DEFAULT_STATS_RANGE: u32 = 1_000;
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Reports the storage statistics of the ledger, and projects its growth.
+#[derive(Debug, Parser)]
+pub struct LedgerStats {
+    /// Specify the network of the ledger.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+    /// The first block height to measure, which defaults to the latest 1000 blocks.
+    #[clap(long = "start")]
+    pub start: Option<u32>,
+    /// The last block height to measure, which defaults to the latest height.
+    #[clap(long = "end")]
+    pub end: Option<u32>,
+    /// Print the sizes of every measured block.
+    #[clap(long = "per-height")]
+    pub per_height: bool,
+}
+
+/// The sizes of a block.
+#[derive(Clone, Copy, Debug)]
+struct BlockSize {
+    /// The height of the block.
+    height: u32,
+    /// The timestamp of the block.
+    timestamp: i64,
+    /// The size of the serialized block, in bytes.
+    block_bytes: u64,
+    /// The size of the execution and fee proofs in the block, in bytes.
+    proof_bytes: u64,
+}
+
+impl LedgerStats {
+    /// Reports the storage statistics of the ledger, and projects its growth.
+    pub fn parse(self) -> Result<String> {
+        let storage_mode = storage_mode(&self.storage_path);
+        let ledger_path = aleo_std::aleo_ledger_dir(self.network, storage_mode.clone());
+        let ledger = open_ledger(self.network, storage_mode)?;
+        let latest_height = ledger.latest_height();
+        let end = self.end.unwrap_or(latest_height).min(latest_height);
+        let start = self.start.unwrap_or(end.saturating_sub(DEFAULT_STATS_RANGE - 1));
+        ensure!(start <= end, "Invalid block range ({start} to {end})");
+        println!("📊 Measuring blocks {start} to {end} of the ledger\n");
+
+        let sizes =
+            (start..=end).into_par_iter().map(|height| block_size(&ledger, height)).collect::<Result<Vec<_>>>()?;
+        if self.per_height {
+            let mut cumulative_bytes = 0;
+            for size in &sizes {
+                cumulative_bytes += size.block_bytes;
+                println!(
+                    "{} block {} · proofs {} · cumulative {}",
+                    format!("[{}]", size.height).bold(),
+                    format_bytes(size.block_bytes),
+                    format_bytes(size.proof_bytes),
+                    format_bytes(cumulative_bytes).dimmed()
+                );
+            }
+            println!();
+        }
+
+        // Summarize the measured blocks.
+        let num_blocks = sizes.len() as u64;
+        let block_bytes = sizes.iter().map(|size| size.block_bytes).sum::<u64>();
+        let proof_bytes = sizes.iter().map(|size| size.proof_bytes).sum::<u64>();
+        println!("{:<28} {num_blocks}", "Blocks measured".bold());
+        println!(
+            "{:<28} {} ({} per block)",
+            "Block data".bold(),
+            format_bytes(block_bytes),
+            format_bytes(block_bytes / num_blocks)
+        );
+        println!(
+            "{:<28} {} ({} per block)",
+            "Proof data".bold(),
+            format_bytes(proof_bytes),
+            format_bytes(proof_bytes / num_blocks)
+        );
+
+        // Compare the on-disk size with the block data, which is only exact if every block was measured.
+        let disk_bytes = StorageStats::compute(&ledger_path)?.size;
+        println!(
+            "{:<28} {} ({} per block)",
+            "On-disk size".bold(),
+            format_bytes(disk_bytes),
+            format_bytes(disk_bytes / (u64::from(latest_height) + 1))
+        );
+        if start == 0 && end == latest_height {
+            let other_bytes = disk_bytes.saturating_sub(block_bytes);
+            println!("{:<28} {}", "Indexes, state, and overhead".bold(), format_bytes(other_bytes));
+        }
+
+        // Project the growth from the block rate of the measured blocks.
+        let (first, last) = (sizes[0], sizes[sizes.len() - 1]);
+        if last.timestamp > first.timestamp {
+            let blocks_per_day =
+                (last.height - first.height) as f64 * SECONDS_PER_DAY / (last.timestamp - first.timestamp) as f64;
+            let disk_bytes_per_block = disk_bytes as f64 / (f64::from(latest_height) + 1.0);
+            let bytes_per_day = blocks_per_day * disk_bytes_per_block;
+            println!("\n{:<28} {blocks_per_day:.0} blocks per day", "Block rate".bold());
+            for (label, days) in [
+                ("Projected growth (1 day)", 1.0),
+                ("Projected growth (30 days)", 30.0),
+                ("Projected growth (1 year)", 365.0),
+            ] {
+                println!("{:<28} {}", label.bold(), format_bytes((bytes_per_day * days) as u64));
+            }
+        }
+
+        Ok(format!("\n✅ Measured {num_blocks} blocks of the ledger"))
+    }
+}
+
+/// Returns the sizes of the block at the given height.
+fn block_size(ledger: &CurrentLedger, height: u32) -> Result<BlockSize> {
+    let block = ledger.get_block(height)?;
+    let mut proof_bytes = 0;
+    for confirmed in block.transactions().iter() {
+        let transaction = confirmed.transaction();
+        if let Some(proof) = transaction.execution().and_then(|execution| execution.proof()) {
+            proof_bytes += proof.to_bytes_le()?.len() as u64;
+        }
+        if let Some(proof) = transaction.fee_transition().as_ref().and_then(|fee| fee.proof()) {
+            proof_bytes += proof.to_bytes_le()?.len() as u64;
+        }
+    }
+    Ok(BlockSize { height, timestamp: block.timestamp(), block_bytes: block.to_bytes_le()?.len() as u64, proof_bytes })
+}
+
+/// Formats the given number of bytes with a binary unit.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.2} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(5 << 30), "5.00 GiB");
+    }
+}
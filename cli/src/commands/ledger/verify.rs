@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{open_ledger, storage_mode, CurrentLedger, CurrentNetwork};
+
+use snarkvm::{
+    ledger::block::Block,
+    prelude::{FromBytes, ToBytes},
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+/// The number of blocks verified between progress reports.
+const VERIFY_BATCH_SIZE: u32 = 1_000;
+/// The maximum number of discrepancies printed, as a damaged ledger may report one for every block.
+const MAX_PRINTED_DISCREPANCIES: usize = 100;
+
+/// Verifies the blocks and indexes of the ledger.
+#[derive(Debug, Parser)]
+pub struct VerifyLedger {
+    /// Specify the network of the ledger.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+    /// The first block height to verify.
+    #[clap(default_value = "0", long = "start")]
+    pub start: u32,
+    /// The last block height to verify, which defaults to the latest height.
+    #[clap(long = "end")]
+    pub end: Option<u32>,
+    /// Skip the checks of the transaction, transition, serial number, and commitment indexes.
+    #[clap(long = "skip-indexes")]
+    pub skip_indexes: bool,
+}
+
+impl VerifyLedger {
+    /// Verifies the blocks and indexes of the ledger.
+    pub fn parse(self) -> Result<String> {
+        let ledger = open_ledger(self.network, storage_mode(&self.storage_path))?;
+        let end = self.end.unwrap_or(ledger.latest_height()).min(ledger.latest_height());
+        ensure!(self.start <= end, "Invalid block range ({} to {end})", self.start);
+        println!("🔍 Verifying blocks {} to {end} of the ledger\n", self.start);
+
+        // Verify the blocks in batches, checking the blocks of a batch in parallel.
+        let mut discrepancies = Vec::new();
+        let mut batch_start = self.start;
+        loop {
+            let batch_end = batch_start.saturating_add(VERIFY_BATCH_SIZE - 1).min(end);
+            let batch = (batch_start..=batch_end)
+                .into_par_iter()
+                .flat_map_iter(|height| verify_block(&ledger, height, !self.skip_indexes))
+                .collect::<Vec<_>>();
+            for discrepancy in &batch {
+                if discrepancies.len() < MAX_PRINTED_DISCREPANCIES {
+                    println!("{} {discrepancy}", "✗".red().bold());
+                }
+                discrepancies.push(discrepancy.clone());
+            }
+            let progress = format!("[{batch_end}/{end}]");
+            println!("{} {} discrepancies", progress.bold(), discrepancies.len().to_string().dimmed());
+            if batch_end == end {
+                break;
+            }
+            batch_start = batch_end + 1;
+        }
+
+        if !discrepancies.is_empty() {
+            bail!(
+                "Found {} discrepancies in the ledger (the first at block {}); restore the ledger from a snapshot, \
+                 or run `snarkos clean` and sync it again",
+                discrepancies.len(),
+                discrepancies[0].height
+            );
+        }
+        Ok(format!("✅ Verified blocks {} to {end} of the ledger", self.start))
+    }
+}
+
+/// A discrepancy found in the ledger.
+#[derive(Clone, Debug)]
+struct Discrepancy {
+    /// The height of the block.
+    height: u32,
+    /// The description of the discrepancy.
+    message: String,
+}
+
+impl std::fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Block {}: {}", self.height, self.message)
+    }
+}
+
+/// Verifies the block at the given height, returning its discrepancies.
+fn verify_block(ledger: &CurrentLedger, height: u32, check_indexes: bool) -> Vec<Discrepancy> {
+    let mut discrepancies = Vec::new();
+    let mut report = |message: String| discrepancies.push(Discrepancy { height, message });
+
+    // Ensure the block can be loaded, and deserializes with a valid hash.
+    let block = match ledger.get_block(height) {
+        Ok(block) => block,
+        Err(error) => {
+            report(format!("the block cannot be loaded - {error}"));
+            return discrepancies;
+        }
+    };
+    if let Err(error) = block.to_bytes_le().and_then(|bytes| Block::<CurrentNetwork>::from_bytes_le(&bytes)) {
+        report(format!("the block fails to deserialize - {error}"));
+    }
+    if block.height() != height {
+        report(format!("the block is stored at height {height}, but has height {}", block.height()));
+    }
+
+    // Ensure the height and hash indexes agree with the block.
+    match ledger.get_hash(height) {
+        Ok(hash) if hash == block.hash() => (),
+        Ok(hash) => report(format!("the height index points to {hash}, but the block is {}", block.hash())),
+        Err(error) => report(format!("the block hash cannot be loaded - {error}")),
+    }
+    match ledger.get_height(&block.hash()) {
+        Ok(stored_height) if stored_height == height => (),
+        Ok(stored_height) => report(format!("the hash index points to height {stored_height}")),
+        Err(error) => report(format!("the block height cannot be loaded - {error}")),
+    }
+
+    // Ensure the block links to its parent.
+    if height > 0 {
+        match ledger.get_hash(height - 1) {
+            Ok(hash) if hash == block.previous_hash() => (),
+            Ok(hash) => report(format!("the previous hash is {}, but the parent is {hash}", block.previous_hash())),
+            Err(error) => report(format!("the parent hash cannot be loaded - {error}")),
+        }
+    }
+
+    if !check_indexes {
+        return discrepancies;
+    }
+    // Ensure the transactions, transitions, serial numbers, and commitments are indexed.
+    for confirmed in block.transactions().iter() {
+        let transaction_id = confirmed.id();
+        match ledger.find_block_hash(&transaction_id) {
+            Ok(Some(hash)) if hash == block.hash() => (),
+            Ok(Some(hash)) => report(format!("the transaction {transaction_id} is indexed in block {hash}")),
+            Ok(None) => report(format!("the transaction {transaction_id} is not indexed")),
+            Err(error) => report(format!("the transaction {transaction_id} cannot be looked up - {error}")),
+        }
+        for transition in confirmed.transaction().transitions() {
+            let transition_id = transition.id();
+            match ledger.find_transaction_id_from_transition_id(transition_id) {
+                Ok(Some(id)) if id == transaction_id => (),
+                Ok(_) => report(format!("the transition {transition_id} is not indexed in {transaction_id}")),
+                Err(error) => report(format!("the transition {transition_id} cannot be looked up - {error}")),
+            }
+            for serial_number in transition.serial_numbers() {
+                if !ledger.contains_serial_number(serial_number).unwrap_or(false) {
+                    report(format!("the serial number {serial_number} is not indexed"));
+                }
+            }
+            for commitment in transition.commitments() {
+                if !ledger.contains_commitment(commitment).unwrap_or(false) {
+                    report(format!("the commitment {commitment} is not indexed"));
+                }
+            }
+        }
+    }
+    discrepancies
+}
@@ -0,0 +1,520 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{open_ledger, storage_mode};
+
+use aleo_std::StorageMode;
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use clap::Parser;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The name of the snapshot manifest.
+const MANIFEST_FILE: &str = "manifest.json";
+/// The name of the snapshot directory that holds the ledger files.
+const LEDGER_DIR: &str = "ledger";
+/// The ledger file holding the unique ID of the database.
+const IDENTITY_FILE: &str = "IDENTITY";
+/// The ledger files that are specific to a running database, and are not part of a snapshot.
+const SKIPPED_FILES: &[&str] = &["LOCK", "LOG"];
+/// The files and directories the node keeps in the ledger directory, which are local to the node
+/// and are not part of a snapshot.
+const SKIPPED_NODE_FILES: &[&str] = &["MEMPOOL", "MEMPOOL.tmp", "CHECKPOINT", "TXINDEX"];
+/// The maximum size of a downloaded snapshot manifest, in bytes.
+const MAX_MANIFEST_SIZE: u64 = 16 << 20;
+/// The size of the buffer used to copy and hash the ledger files.
+const BUFFER_SIZE: usize = 1 << 20;
+/// The time allowed to connect to the snapshot host, and to wait on a stalled download.
+const DOWNLOAD_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+/// The slowest download rate tolerated for a snapshot file, in bytes per second.
+const MIN_DOWNLOAD_RATE: u64 = 1 << 20;
+
+/// The manifest of a ledger snapshot.
+#[derive(Debug, Deserialize, Serialize)]
+struct SnapshotManifest {
+    /// The network ID of the ledger.
+    network: u16,
+    /// The height of the latest block in the snapshot.
+    height: u32,
+    /// The hash of the latest block in the snapshot.
+    block_hash: String,
+    /// The UNIX timestamp at which the snapshot was created.
+    created_at: u64,
+    /// The snapshot this snapshot is incremental to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<BaseSnapshot>,
+    /// The ledger files, with their checksums.
+    files: Vec<SnapshotFile>,
+}
+
+/// The snapshot an incremental snapshot is taken against.
+#[derive(Debug, Deserialize, Serialize)]
+struct BaseSnapshot {
+    /// The height of the latest block in the base snapshot.
+    height: u32,
+    /// The SHA-256 checksum of the manifest of the base snapshot, in hex.
+    manifest_sha256: String,
+}
+
+/// A ledger file in a snapshot.
+#[derive(Debug, Deserialize, Serialize)]
+struct SnapshotFile {
+    /// The path of the file, relative to the ledger directory.
+    path: PathBuf,
+    /// The size of the file, in bytes.
+    size: u64,
+    /// The SHA-256 checksum of the file, in hex.
+    sha256: String,
+    /// Whether the file is unchanged since the base snapshot, and is stored there instead.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    in_base: bool,
+}
+
+/// Commands to create and restore snapshots of the ledger.
+#[derive(Debug, Parser)]
+pub enum Snapshot {
+    /// Create a snapshot of the ledger. The node must be stopped.
+    Create(CreateSnapshot),
+    /// Restore the ledger from a snapshot.
+    Restore(RestoreSnapshot),
+}
+
+impl Snapshot {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Create(command) => command.parse(),
+            Self::Restore(command) => command.parse(),
+        }
+    }
+}
+
+/// Creates a snapshot of the ledger.
+#[derive(Debug, Parser)]
+pub struct CreateSnapshot {
+    /// The directory to write the snapshot to, which must not exist.
+    pub path: PathBuf,
+    /// Creates an incremental snapshot, which only holds the files that changed since the given snapshot
+    #[clap(long = "base")]
+    pub base: Option<PathBuf>,
+    /// Specify the network of the ledger.
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl CreateSnapshot {
+    /// Creates a snapshot of the ledger.
+    pub fn parse(self) -> Result<String> {
+        ensure!(!self.path.exists(), "The snapshot directory '{}' already exists", self.path.display());
+        let storage_mode = storage_mode(&self.storage_path);
+        let ledger_path = aleo_std::aleo_ledger_dir(self.network, storage_mode.clone());
+        ensure!(ledger_path.exists(), "No ledger was found in '{}'", ledger_path.display());
+
+        // Open the ledger, which fails if a running node holds the database, so the files are consistent.
+        let (height, block_hash) = latest_block(self.network, storage_mode)?;
+        let files = list_files(&ledger_path)?;
+
+        // Load the base snapshot, if the snapshot is incremental.
+        let (base, base_files) = match &self.base {
+            Some(base_path) => {
+                let (base_manifest, manifest_sha256) = read_manifest(base_path)?;
+                ensure!(base_manifest.network == self.network, "The base snapshot is for another network");
+                ensure!(base_manifest.height <= height, "The base snapshot is ahead of the ledger");
+                ensure_same_database(&base_manifest, &ledger_path)?;
+                let base = BaseSnapshot { height: base_manifest.height, manifest_sha256 };
+                let base_files =
+                    base_manifest.files.into_iter().map(|file| (file.path.clone(), file)).collect::<HashMap<_, _>>();
+                (Some(base), base_files)
+            }
+            None => (None, HashMap::new()),
+        };
+        match &base {
+            Some(base) => println!(
+                "📸 Creating an incremental snapshot of the ledger at block {height} ({block_hash}), since block {}\n",
+                base.height
+            ),
+            None => println!("📸 Creating a snapshot of the ledger at block {height} ({block_hash})\n"),
+        }
+
+        // Copy the ledger files, computing their checksums along the way.
+        let (num_files, total_size) = (files.len(), files.iter().map(|(_, size)| size).sum::<u64>());
+        let mut copied_size = 0;
+        let mut manifest_files = Vec::with_capacity(num_files);
+        for (index, (path, size)) in files.into_iter().enumerate() {
+            // RocksDB never rewrites a table file, so a table file of the same name and size is in the base snapshot.
+            let is_table = path.extension().map_or(false, |extension| extension == "sst");
+            let base_file = base_files.get(&path).filter(|file| is_table && file.size == size);
+            let sha256 = match base_file {
+                Some(file) => file.sha256.clone(),
+                None => copy_and_hash(&ledger_path.join(&path), &self.path.join(LEDGER_DIR).join(&path))?,
+            };
+            copied_size += size;
+            print_progress(index + 1, num_files, copied_size, total_size, &path);
+            manifest_files.push(SnapshotFile { path, size, sha256, in_base: base_file.is_some() });
+        }
+
+        // Write the manifest last, so an interrupted snapshot is never mistaken for a complete one.
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let manifest =
+            SnapshotManifest { network: self.network, height, block_hash, created_at, base, files: manifest_files };
+        fs::write(self.path.join(MANIFEST_FILE), serde_json::to_vec_pretty(&manifest)?)?;
+
+        Ok(format!("✅ Created a snapshot of the ledger at block {height} in '{}'", self.path.display()))
+    }
+}
+
+/// Restores the ledger from a snapshot.
+#[derive(Debug, Parser)]
+pub struct RestoreSnapshot {
+    /// The directory of the snapshot.
+    pub path: PathBuf,
+    /// Specify the earlier snapshots an incremental snapshot builds on, back to its full snapshot
+    #[clap(long = "base")]
+    pub bases: Vec<PathBuf>,
+    /// Specify the path to a directory to restore the ledger to
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl RestoreSnapshot {
+    /// Restores the ledger from a snapshot.
+    pub fn parse(self) -> Result<String> {
+        let (manifest, _) = read_manifest(&self.path)?;
+        let sources = resolve_sources(&self.path, &manifest, &self.bases)?;
+
+        let storage_mode = storage_mode(&self.storage_path);
+        let ledger_path = aleo_std::aleo_ledger_dir(manifest.network, storage_mode.clone());
+        ensure!(
+            !ledger_path.exists(),
+            "A ledger already exists in '{}', run `snarkos clean` to remove it first",
+            ledger_path.display()
+        );
+        println!("📦 Restoring the snapshot of the ledger at block {} ({})\n", manifest.height, manifest.block_hash);
+
+        install_snapshot(&manifest, storage_mode, |file| Ok(Box::new(File::open(&sources[&file.path])?)))?;
+
+        Ok(format!("✅ Restored the ledger at block {} to '{}'", manifest.height, ledger_path.display()))
+    }
+}
+
+/// Bootstraps the ledger from the snapshot at the given HTTPS URL, unless a ledger already exists.
+/// The snapshot is only accepted if its manifest matches the given checksum.
+pub(crate) fn bootstrap_ledger(
+    url: &str,
+    manifest_checksum: &str,
+    network: u16,
+    storage_mode: StorageMode,
+) -> Result<()> {
+    let ledger_path = aleo_std::aleo_ledger_dir(network, storage_mode.clone());
+    if ledger_path.exists() {
+        println!("📦 Skipping the bootstrap, as a ledger already exists in '{}'\n", ledger_path.display());
+        return Ok(());
+    }
+    ensure!(url.starts_with("https://"), "Invalid snapshot URL '{url}' (expected 'https://')");
+    let url = url.trim_end_matches('/');
+
+    // Download the manifest, and ensure it matches the pinned checksum.
+    let agent =
+        ureq::AgentBuilder::new().timeout_connect(DOWNLOAD_STALL_TIMEOUT).timeout_read(DOWNLOAD_STALL_TIMEOUT).build();
+    let mut manifest = Vec::new();
+    agent
+        .get(&format!("{url}/{MANIFEST_FILE}"))
+        .timeout(download_timeout(MAX_MANIFEST_SIZE))
+        .call()?
+        .into_reader()
+        .take(MAX_MANIFEST_SIZE)
+        .read_to_end(&mut manifest)?;
+    let checksum = format!("{:x}", Sha256::digest(&manifest));
+    ensure!(checksum == manifest_checksum.to_lowercase(), "The checksum of the snapshot manifest does not match");
+    let manifest: SnapshotManifest = serde_json::from_slice(&manifest).context("Invalid snapshot manifest")?;
+    ensure!(manifest.network == network, "The snapshot is for network {}, expected {network}", manifest.network);
+    ensure!(manifest.base.is_none(), "Cannot bootstrap the ledger from an incremental snapshot");
+    println!("📦 Bootstrapping the ledger from the snapshot at block {} ({})\n", manifest.height, manifest.block_hash);
+
+    // Download the ledger files, whose checksums are verified against the manifest.
+    install_snapshot(&manifest, storage_mode, |file| {
+        let path = file.path.iter().map(|component| component.to_string_lossy()).collect::<Vec<_>>().join("/");
+        let request = agent.get(&format!("{url}/{LEDGER_DIR}/{path}")).timeout(download_timeout(file.size));
+        Ok(Box::new(request.call()?.into_reader()))
+    })?;
+
+    println!("✅ Bootstrapped the ledger at block {}, the remaining blocks sync from peers\n", manifest.height);
+    Ok(())
+}
+
+/// Returns the time allowed to download a file of the given size, in bytes.
+fn download_timeout(size: u64) -> Duration {
+    DOWNLOAD_STALL_TIMEOUT + Duration::from_secs(size / MIN_DOWNLOAD_RATE)
+}
+
+/// Installs the files of the given snapshot as the ledger, reading each file with the given function.
+fn install_snapshot(
+    manifest: &SnapshotManifest,
+    storage_mode: StorageMode,
+    open: impl Fn(&SnapshotFile) -> Result<Box<dyn Read>>,
+) -> Result<()> {
+    let ledger_path = aleo_std::aleo_ledger_dir(manifest.network, storage_mode.clone());
+
+    // Copy the files into a staging directory, so a failed restore never leaves a partial ledger behind.
+    let mut staging_path = ledger_path.clone().into_os_string();
+    staging_path.push(".restore");
+    let staging_path = PathBuf::from(staging_path);
+    if staging_path.exists() {
+        fs::remove_dir_all(&staging_path)?;
+    }
+    let result =
+        restore_files(manifest, &open, &staging_path).and_then(|_| Ok(fs::rename(&staging_path, &ledger_path)?));
+    if let Err(error) = result {
+        let _ = fs::remove_dir_all(&staging_path);
+        return Err(error);
+    }
+
+    // Ensure the restored ledger opens at the expected block, and remove it otherwise.
+    let (height, block_hash) = match latest_block(manifest.network, storage_mode) {
+        Ok(latest_block) => latest_block,
+        Err(error) => {
+            fs::remove_dir_all(&ledger_path)?;
+            return Err(error.context("The restored ledger failed to open, and was removed"));
+        }
+    };
+    if (height, &block_hash) != (manifest.height, &manifest.block_hash) {
+        fs::remove_dir_all(&ledger_path)?;
+        bail!(
+            "The restored ledger is at block {height} ({block_hash}), expected block {} ({}), and was removed",
+            manifest.height,
+            manifest.block_hash
+        );
+    }
+    Ok(())
+}
+
+/// Reads the manifest of the snapshot in the given directory, and returns it with its SHA-256 checksum in hex.
+fn read_manifest(path: &Path) -> Result<(SnapshotManifest, String)> {
+    let manifest_path = path.join(MANIFEST_FILE);
+    let manifest = fs::read(&manifest_path)
+        .with_context(|| format!("Failed to read the snapshot manifest '{}'", manifest_path.display()))?;
+    let checksum = format!("{:x}", Sha256::digest(&manifest));
+    Ok((serde_json::from_slice(&manifest)?, checksum))
+}
+
+/// Ensures the given snapshot was taken from the database in the given ledger directory,
+/// as the names of the table files are only unique within a database.
+fn ensure_same_database(manifest: &SnapshotManifest, ledger_path: &Path) -> Result<()> {
+    let identity = manifest.files.iter().find(|file| file.path == Path::new(IDENTITY_FILE));
+    let is_same_database = match (identity, fs::read(ledger_path.join(IDENTITY_FILE))) {
+        (Some(identity), Ok(ledger_identity)) => identity.sha256 == format!("{:x}", Sha256::digest(ledger_identity)),
+        _ => false,
+    };
+    ensure!(is_same_database, "The base snapshot was not taken from this ledger");
+    Ok(())
+}
+
+/// Returns the location of each file of the given snapshot. The files an incremental snapshot shares
+/// with its base are located by following the chain of bases through the given earlier snapshots.
+fn resolve_sources(path: &Path, manifest: &SnapshotManifest, bases: &[PathBuf]) -> Result<HashMap<PathBuf, PathBuf>> {
+    // Index the earlier snapshots by the checksums of their manifests.
+    let mut snapshots = HashMap::new();
+    for base_path in bases {
+        let (base_manifest, checksum) = read_manifest(base_path)?;
+        let files = base_manifest.files.into_iter().map(|file| (file.path.clone(), file)).collect::<HashMap<_, _>>();
+        snapshots.insert(checksum, (base_path.as_path(), base_manifest.base, files));
+    }
+
+    let mut sources = HashMap::with_capacity(manifest.files.len());
+    for file in &manifest.files {
+        let (mut directory, mut base, mut in_base) = (path, &manifest.base, file.in_base);
+        while in_base {
+            let base_snapshot = base.as_ref().ok_or_else(|| anyhow!("The snapshot has no base snapshot"))?;
+            let (base_path, base_base, base_files) =
+                snapshots.get(&base_snapshot.manifest_sha256).ok_or_else(|| {
+                    anyhow!("The base snapshot at block {} is missing (pass it with '--base')", base_snapshot.height)
+                })?;
+            let base_file = base_files
+                .get(&file.path)
+                .filter(|base_file| base_file.sha256 == file.sha256)
+                .ok_or_else(|| anyhow!("The base snapshot does not hold the file '{}'", file.path.display()))?;
+            (directory, base, in_base) = (*base_path, base_base, base_file.in_base);
+        }
+        sources.insert(file.path.clone(), directory.join(LEDGER_DIR).join(&file.path));
+    }
+    Ok(sources)
+}
+
+/// Opens the ledger, and returns the height and hash of its latest block.
+fn latest_block(network: u16, storage_mode: StorageMode) -> Result<(u32, String)> {
+    let ledger = open_ledger(network, storage_mode)?;
+    Ok((ledger.latest_height(), ledger.latest_hash().to_string()))
+}
+
+/// Returns the relative paths and sizes of the snapshot files in the given directory, in order.
+fn list_files(root: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if directory == root && SKIPPED_NODE_FILES.contains(&name.as_str()) {
+                continue;
+            }
+            if metadata.is_dir() {
+                directories.push(entry.path());
+                continue;
+            }
+            if SKIPPED_FILES.iter().any(|skipped| name == *skipped || name.starts_with(&format!("{skipped}.old"))) {
+                continue;
+            }
+            files.push((entry.path().strip_prefix(root)?.to_path_buf(), metadata.len()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Copies the given file, and returns its SHA-256 checksum in hex.
+fn copy_and_hash(source: &Path, target: &Path) -> Result<String> {
+    write_and_hash(File::open(source)?, target)
+}
+
+/// Writes the contents of the given reader to the target file, and returns their SHA-256 checksum in hex.
+fn write_and_hash(source: impl Read, target: &Path) -> Result<String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut reader = BufReader::new(source);
+    let mut writer = BufWriter::new(File::create(target)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        let num_bytes = reader.read(&mut buffer)?;
+        if num_bytes == 0 {
+            break;
+        }
+        hasher.update(&buffer[..num_bytes]);
+        writer.write_all(&buffer[..num_bytes])?;
+    }
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copies the files of the given snapshot into the target directory, verifying their checksums.
+fn restore_files(
+    manifest: &SnapshotManifest,
+    open: &impl Fn(&SnapshotFile) -> Result<Box<dyn Read>>,
+    target: &Path,
+) -> Result<()> {
+    let total_size = manifest.files.iter().map(|file| file.size).sum::<u64>();
+    let mut copied_size = 0;
+    for (index, file) in manifest.files.iter().enumerate() {
+        // Ensure the path stays within the target directory, as the manifest may come from a remote host.
+        let is_valid_path = file.path.components().all(|component| matches!(component, Component::Normal(_)));
+        ensure!(is_valid_path, "Invalid snapshot file path '{}'", file.path.display());
+        let sha256 = write_and_hash(open(file)?, &target.join(&file.path))?;
+        ensure!(sha256 == file.sha256, "The checksum of the snapshot file '{}' does not match", file.path.display());
+        copied_size += file.size;
+        print_progress(index + 1, manifest.files.len(), copied_size, total_size, &file.path);
+    }
+    Ok(())
+}
+
+/// Prints the progress of a snapshot copy.
+fn print_progress(num_files: usize, total_files: usize, copied_size: u64, total_size: u64, path: &Path) {
+    let percentage = copied_size as f64 * 100.0 / total_size.max(1) as f64;
+    let progress = format!("[{num_files}/{total_files}] {percentage:.1}%");
+    println!("{} {}", progress.bold(), path.display().to_string().dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_files_and_copy() {
+        let directory = std::env::temp_dir().join(format!("snarkos-snapshot-test-{}", std::process::id()));
+        let (source, target) = (directory.join("source"), directory.join("target"));
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("000001.sst"), b"abc").unwrap();
+        fs::write(source.join("nested").join("CURRENT"), b"MANIFEST-000001").unwrap();
+        fs::write(source.join("LOCK"), b"").unwrap();
+        fs::write(source.join("LOG.old.1"), b"").unwrap();
+        fs::write(source.join("MEMPOOL"), b"abc").unwrap();
+        fs::write(source.join("MEMPOOL.tmp"), b"abc").unwrap();
+        fs::write(source.join("CHECKPOINT"), b"abc").unwrap();
+        fs::create_dir_all(source.join("TXINDEX")).unwrap();
+        fs::write(source.join("TXINDEX").join("000002.sst"), b"abc").unwrap();
+
+        // The database-specific files and the node-local files are skipped.
+        let files = list_files(&source).unwrap();
+        assert_eq!(files, vec![(PathBuf::from("000001.sst"), 3), (PathBuf::from("nested").join("CURRENT"), 15)]);
+
+        // The copy preserves the contents, and returns the SHA-256 checksum.
+        let sha256 = copy_and_hash(&source.join("000001.sst"), &target.join("000001.sst")).unwrap();
+        assert_eq!(sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(fs::read(target.join("000001.sst")).unwrap(), b"abc");
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_sources() {
+        let directory = std::env::temp_dir().join(format!("snarkos-snapshot-chain-test-{}", std::process::id()));
+        let file = |path: &str, sha256: &str, in_base: bool| SnapshotFile {
+            path: PathBuf::from(path),
+            size: 1,
+            sha256: sha256.to_string(),
+            in_base,
+        };
+        let write_manifest = |name: &str, base: Option<BaseSnapshot>, files: Vec<SnapshotFile>| {
+            let manifest =
+                SnapshotManifest { network: 0, height: 0, block_hash: String::new(), created_at: 0, base, files };
+            fs::create_dir_all(directory.join(name)).unwrap();
+            fs::write(directory.join(name).join(MANIFEST_FILE), serde_json::to_vec(&manifest).unwrap()).unwrap();
+            read_manifest(&directory.join(name)).unwrap()
+        };
+
+        // A full snapshot, followed by two incremental snapshots.
+        let (_, full_checksum) =
+            write_manifest("full", None, vec![file("1.sst", "a", false), file("CURRENT", "b", false)]);
+        let base = BaseSnapshot { height: 0, manifest_sha256: full_checksum };
+        let files = vec![file("1.sst", "a", true), file("2.sst", "c", false), file("CURRENT", "d", false)];
+        let (_, delta_checksum) = write_manifest("delta", Some(base), files);
+        let base = BaseSnapshot { height: 0, manifest_sha256: delta_checksum };
+        let files = vec![file("1.sst", "a", true), file("2.sst", "c", true), file("CURRENT", "e", false)];
+        let (manifest, _) = write_manifest("latest", Some(base), files);
+
+        // The shared files are located in the snapshot that holds them.
+        let bases = [directory.join("delta"), directory.join("full")];
+        let sources = resolve_sources(&directory.join("latest"), &manifest, &bases).unwrap();
+        assert_eq!(sources[Path::new("1.sst")], directory.join("full").join(LEDGER_DIR).join("1.sst"));
+        assert_eq!(sources[Path::new("2.sst")], directory.join("delta").join(LEDGER_DIR).join("2.sst"));
+        assert_eq!(sources[Path::new("CURRENT")], directory.join("latest").join(LEDGER_DIR).join("CURRENT"));
+
+        // The restore fails if a base of the chain is missing.
+        assert!(resolve_sources(&directory.join("latest"), &manifest, &bases[..1]).is_err());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
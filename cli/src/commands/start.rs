@@ -14,7 +14,24 @@
 
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::{ledger_service::DEFAULT_LEDGER_CACHE_SIZE, MEMORY_POOL_PORT},
+    consensus::{
+        EvictionOrder,
+        MempoolPolicy,
+        TransactionOrder,
+        DEFAULT_MAX_QUEUED_AGE_SECS,
+        DEFAULT_MAX_QUEUED_BYTES,
+    },
+    parse_core_list,
+    rest::{ApiKeys, CorsConfig, RestConfig, RpcConcurrencyLimits, RpcMethodPolicy, RpcReplicas, TlsConfig},
+    router::messages::NodeType,
+    Node,
+    ProverThreads,
+    StorageBackend,
+    StorageProfile,
+    DEFAULT_MIN_FREE_DISK_MB,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -32,7 +49,7 @@ use snarkvm::{
 };
 
 use aleo_std::StorageMode;
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 use core::str::FromStr;
@@ -40,7 +57,7 @@ use indexmap::IndexMap;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tokio::runtime::{self, Runtime};
 
 /// The recommended minimum number of 'open files' limit for a validator.
@@ -94,7 +111,7 @@ pub struct Start {
     /// Specify the IP address and port for the BFT
     #[clap(long = "bft")]
     pub bft: Option<SocketAddr>,
-    /// Specify the IP address and port of the peer(s) to connect to
+    /// Specify the IP address and port of the peer(s) to connect to; a prover requests its puzzle from them in order
     #[clap(default_value = "", long = "peers")]
     pub peers: String,
     /// Specify the IP address and port of the validator(s) to connect to
@@ -107,6 +124,78 @@ pub struct Start {
     /// Specify the requests per second (RPS) rate limit per IP for the REST server
     #[clap(default_value = "10", long = "rest-rps")]
     pub rest_rps: u32,
+    /// Specify the path to a file of static bearer tokens (one per line) accepted by the private REST routes
+    #[clap(long = "rest-tokens-file")]
+    pub rest_tokens_file: Option<PathBuf>,
+    /// Specify the requests per second (RPS) rate limit per static bearer token for the REST server (0 to disable)
+    #[clap(default_value = "0", long = "rest-token-rps")]
+    pub rest_token_rps: u32,
+    /// Specify the burst size per static bearer token for the REST server (defaults to the token RPS)
+    #[clap(default_value = "0", long = "rest-token-burst")]
+    pub rest_token_burst: u32,
+    /// Specify the path to a file of named API keys for the JSON-RPC endpoint (one `name:key:rps:methods` per line)
+    #[clap(long = "rest-api-keys-file")]
+    pub rest_api_keys_file: Option<PathBuf>,
+    /// Specify the path to a PEM certificate chain, to serve the REST API over TLS
+    #[clap(long = "rest-tls-cert", requires = "rest_tls_key")]
+    pub rest_tls_cert: Option<PathBuf>,
+    /// Specify the path to the PEM private key for the REST TLS certificate
+    #[clap(long = "rest-tls-key", requires = "rest_tls_cert")]
+    pub rest_tls_key: Option<PathBuf>,
+    /// Specify the path to PEM CA certificates, to require and verify REST client certificates
+    #[clap(long = "rest-tls-client-ca", requires = "rest_tls_cert")]
+    pub rest_tls_client_ca: Option<PathBuf>,
+    /// Specify the number of seconds until the one-time JWT for the private REST routes expires
+    #[clap(default_value_t = snarkos_node_rest::EXPIRATION, long = "rest-jwt-expiry")]
+    pub rest_jwt_expiry: i64,
+    /// If the flag is set, the REST server will index the transactions and public records of each address
+    #[clap(long = "rest-address-index")]
+    pub rest_address_index: bool,
+    /// If the flag is set, the REST server will index the location of each transaction and transition
+    #[clap(long = "txindex")]
+    pub txindex: bool,
+    /// Specify the IP address and port for the gRPC server (requires the `grpc` feature)
+    #[clap(long = "rest-grpc")]
+    pub rest_grpc: Option<SocketAddr>,
+    /// Specify the path of a Unix socket to serve JSON-RPC on, with access controlled by the file permissions
+    #[clap(long = "rest-unix-socket")]
+    pub rest_unix_socket: Option<PathBuf>,
+    /// Specify the comma-separated RPC methods or method groups to enable (`read`, `wallet`, `control`, `debug`)
+    #[clap(default_value = "", long = "rest-rpc-allow")]
+    pub rest_rpc_allow: String,
+    /// Specify the comma-separated RPC methods or method groups to disable, overriding `--rest-rpc-allow`
+    #[clap(default_value = "", long = "rest-rpc-deny")]
+    pub rest_rpc_deny: String,
+    /// Specify the comma-separated concurrency limits of the RPC methods, as `method=limit` (`0` removes a limit)
+    #[clap(default_value = "", long = "rest-rpc-limits")]
+    pub rest_rpc_limits: String,
+    /// Specify the number of seconds an RPC call waits for a free slot, when its method is at its concurrency limit
+    #[clap(default_value = "5", long = "rest-rpc-queue-timeout")]
+    pub rest_rpc_queue_timeout: u64,
+    /// Specify the comma-separated URLs of replica nodes, which serve the expensive read RPC calls at their limit
+    #[clap(default_value = "", long = "rest-replicas")]
+    pub rest_replicas: String,
+    /// Specify the number of blocks the node may be behind its peers, and still report as ready on `/readyz`
+    #[clap(default_value = "10", long = "rest-ready-blocks-behind")]
+    pub rest_ready_blocks_behind: u32,
+    /// Specify the comma-separated origins allowed to call the REST server from a browser (defaults to any origin)
+    #[clap(default_value = "", long = "rest-cors-origins")]
+    pub rest_cors_origins: String,
+    /// Specify the comma-separated HTTP methods allowed for cross-origin requests (defaults to GET, POST, OPTIONS)
+    #[clap(default_value = "", long = "rest-cors-methods")]
+    pub rest_cors_methods: String,
+    /// Specify the comma-separated request headers allowed for cross-origin requests, in addition to `Content-Type`
+    #[clap(default_value = "", long = "rest-cors-headers")]
+    pub rest_cors_headers: String,
+    /// Specify the number of seconds the REST server reports as not ready before it stops serving, on shutdown
+    #[clap(default_value = "0", long = "rest-shutdown-delay")]
+    pub rest_shutdown_delay: u64,
+    /// Specify the number of seconds the REST server waits for in-flight requests to complete, on shutdown
+    #[clap(default_value = "10", long = "rest-shutdown-timeout")]
+    pub rest_shutdown_timeout: u64,
+    /// If the flag is set, the REST server will log each JSON-RPC call, with the sensitive parameters redacted
+    #[clap(long = "rest-access-log")]
+    pub rest_access_log: bool,
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
@@ -117,8 +206,8 @@ pub struct Start {
     /// Specify the verbosity of the node [options: 0, 1, 2, 3, 4]
     #[clap(default_value = "1", long = "verbosity")]
     pub verbosity: u8,
-    /// Specify the path to the file where logs will be stored
-    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
+    /// Specify the path to the file where logs will be stored, also read from `SNARKOS_LOGFILE`
+    #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile", env = "SNARKOS_LOGFILE")]
     pub logfile: PathBuf,
     /// Enables the metrics exporter
     #[clap(default_value = "false", long = "metrics")]
@@ -137,9 +226,49 @@ pub struct Start {
     /// If development mode is enabled, specify the number of genesis validators (default: 4)
     #[clap(long)]
     pub dev_num_validators: Option<u16>,
-    /// Specify the path to a directory containing the ledger
-    #[clap(long = "storage_path")]
+    /// Specify the path to a directory containing the ledger, also read from `SNARKOS_STORAGE_PATH`
+    #[clap(long = "storage_path", env = "SNARKOS_STORAGE_PATH")]
     pub storage_path: Option<PathBuf>,
+    /// Specify the storage backend of the ledger, also read from `SNARKOS_STORAGE_BACKEND` [options: rocksdb, memory]
+    #[clap(default_value = "rocksdb", long = "storage-backend", env = "SNARKOS_STORAGE_BACKEND")]
+    pub storage_backend: StorageBackend,
+    /// Specify the data the node keeps beyond the ledger; 'archive' enables every index [options: default, archive]
+    #[clap(default_value = "default", long = "storage-profile")]
+    pub storage_profile: StorageProfile,
+    /// Specify the number of recent blocks, headers, and solutions to cache in memory (`0` disables the cache)
+    #[clap(default_value_t = DEFAULT_LEDGER_CACHE_SIZE, long = "ledger-cache-size")]
+    pub ledger_cache_size: usize,
+    /// Specify the minimum free disk space in MiB, below which block sync and the memory pool pause (`0` disables it)
+    #[clap(default_value_t = DEFAULT_MIN_FREE_DISK_MB, long = "min-free-disk")]
+    pub min_free_disk: u64,
+    /// Specify the maximum number of transactions waiting to enter the memory pool (default: one batch)
+    #[clap(long = "mempool-max-transactions")]
+    pub mempool_max_transactions: Option<usize>,
+    /// Specify the maximum total size in MiB of the transactions waiting to enter the memory pool
+    #[clap(default_value_t = DEFAULT_MAX_QUEUED_BYTES >> 20, long = "mempool-max-size")]
+    pub mempool_max_size: usize,
+    /// Specify the number of seconds a transaction may wait to enter the memory pool, before it expires
+    #[clap(default_value_t = DEFAULT_MAX_QUEUED_AGE_SECS, long = "mempool-max-age")]
+    pub mempool_max_age: u64,
+    /// Specify the order in which waiting transactions are evicted, once the queue is full [options: age, fee]
+    #[clap(default_value = "age", long = "mempool-eviction")]
+    pub mempool_eviction: EvictionOrder,
+    /// Specify the order in which waiting transactions enter the memory pool [options: arrival, fee-density]
+    #[clap(default_value = "arrival", long = "mempool-ordering")]
+    pub mempool_ordering: TransactionOrder,
+    /// Specify the number of threads the prover uses for proving (default: every core)
+    #[clap(long = "prover-threads")]
+    pub prover_threads: Option<usize>,
+    /// Specify the cores to pin the proving threads to, such as `0-3,6` (Linux only)
+    #[clap(long = "prover-cores")]
+    pub prover_cores: Option<String>,
+    /// Bootstraps a new ledger from the snapshot at the given HTTPS URL, then syncs the remaining blocks from peers.
+    /// If the snapshot fails verification, the node falls back to a full sync (requires `--bootstrap-checksum`)
+    #[clap(long = "bootstrap-from", requires = "bootstrap_checksum")]
+    pub bootstrap_from: Option<String>,
+    /// Specify the SHA-256 checksum of the snapshot manifest to accept when bootstrapping the ledger
+    #[clap(long = "bootstrap-checksum", requires = "bootstrap_from")]
+    pub bootstrap_checksum: Option<String>,
 
     #[clap(long)]
     /// If development mode is enabled, specify the custom bonded balances as a json object. (default: None)
@@ -214,6 +343,88 @@ impl Start {
         }
     }
 
+    /// Returns the REST server configuration, from the given configurations.
+    fn parse_rest_config(&self) -> Result<RestConfig> {
+        // Ensure the JWT for the private REST routes is not expired when it is issued.
+        ensure!(self.rest_jwt_expiry > 0, "The '--rest-jwt-expiry' flag must be greater than 0");
+        let mut config = RestConfig::new(self.rest_rps);
+        config.token_rps = self.rest_token_rps;
+        config.token_burst = self.rest_token_burst;
+        // An archive node keeps every index.
+        config.address_index = self.rest_address_index || self.storage_profile.is_archive();
+        config.transaction_index = self.txindex || self.storage_profile.is_archive();
+        config.grpc = self.rest_grpc;
+        config.unix_socket = self.rest_unix_socket.clone();
+        config.ready_max_blocks_behind = self.rest_ready_blocks_behind;
+        config.shutdown_delay_secs = self.rest_shutdown_delay;
+        config.shutdown_timeout_secs = self.rest_shutdown_timeout;
+        config.access_log = self.rest_access_log;
+        let parse_list = |list: &str| -> Vec<String> {
+            list.split(',').map(str::trim).filter(|entry| !entry.is_empty()).map(str::to_string).collect()
+        };
+        // Set the enabled RPC methods.
+        config.rpc_methods = RpcMethodPolicy::new(parse_list(&self.rest_rpc_allow), parse_list(&self.rest_rpc_deny))?;
+        config.rpc_limits = RpcConcurrencyLimits::new(&parse_list(&self.rest_rpc_limits), self.rest_rpc_queue_timeout)?;
+        config.replicas = RpcReplicas::new(&parse_list(&self.rest_replicas))?;
+        // Set the CORS policy.
+        config.cors = CorsConfig::new(
+            &parse_list(&self.rest_cors_origins),
+            &parse_list(&self.rest_cors_methods),
+            &parse_list(&self.rest_cors_headers),
+        )?;
+        // Load the static bearer tokens, if a file was specified.
+        if let Some(path) = &self.rest_tokens_file {
+            let tokens = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("Failed to read the REST tokens file '{}': {e}", path.display()))?;
+            config.bearer_tokens =
+                tokens.lines().map(str::trim).filter(|token| !token.is_empty()).map(str::to_string).collect();
+            ensure!(!config.bearer_tokens.is_empty(), "The REST tokens file '{}' is empty", path.display());
+        }
+        // Load the API keys, if a file was specified.
+        if let Some(path) = &self.rest_api_keys_file {
+            let entries = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("Failed to read the REST API keys file '{}': {e}", path.display()))?;
+            let entries = entries.lines().map(str::trim).filter(|entry| !entry.is_empty()).map(str::to_string);
+            config.api_keys = ApiKeys::new(&entries.collect::<Vec<_>>())?;
+        }
+        // Set the TLS configuration, if a certificate was specified.
+        if let (Some(cert_path), Some(key_path)) = (&self.rest_tls_cert, &self.rest_tls_key) {
+            config.tls = Some(TlsConfig {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                client_ca_path: self.rest_tls_client_ca.clone(),
+            });
+        }
+        Ok(config)
+    }
+
+    /// Returns the retention and expiry policy of the memory pool, from the given configurations.
+    fn parse_mempool_policy<N: Network>(&self) -> Result<MempoolPolicy> {
+        let mut policy = MempoolPolicy::new::<N>();
+        if let Some(max_transactions) = self.mempool_max_transactions {
+            ensure!(max_transactions > 0, "The '--mempool-max-transactions' flag must be greater than 0");
+            policy.max_transactions = max_transactions;
+        }
+        ensure!(self.mempool_max_size > 0, "The '--mempool-max-size' flag must be greater than 0");
+        policy.max_bytes = self.mempool_max_size << 20;
+        policy.max_age = Duration::from_secs(self.mempool_max_age);
+        policy.eviction = self.mempool_eviction;
+        policy.ordering = self.mempool_ordering;
+        Ok(policy)
+    }
+
+    /// Returns the CPU budget of the prover, from the given configurations.
+    fn parse_prover_threads(&self) -> Result<ProverThreads> {
+        if let Some(num_threads) = self.prover_threads {
+            ensure!(num_threads > 0, "The '--prover-threads' flag must be greater than 0");
+        }
+        let cores = match &self.prover_cores {
+            Some(list) => parse_core_list(list)?,
+            None => Vec::new(),
+        };
+        Ok(ProverThreads { num_threads: self.prover_threads, cores })
+    }
+
     /// Returns the CDN to prefetch initial blocks from, from the given configurations.
     fn parse_cdn(&self) -> Option<String> {
         // Determine if the node type is not declared.
@@ -480,6 +691,12 @@ impl Start {
             true => None,
             false => Some(self.rest),
         };
+        // Parse the REST configuration.
+        let mut rest_config = self.parse_rest_config()?;
+        // Parse the memory pool policy.
+        let mempool_policy = self.parse_mempool_policy::<N>()?;
+        // Parse the CPU budget of the prover.
+        let prover_threads = self.parse_prover_threads()?;
 
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
@@ -498,7 +715,8 @@ impl Start {
                 if let Some(rest_ip) = rest_ip {
                     println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
 
-                    if let Ok(jwt_token) = snarkos_node_rest::Claims::new(account.address()).to_jwt_string() {
+                    let claims = snarkos_node_rest::Claims::with_expiration(account.address(), self.rest_jwt_expiry);
+                    if let Ok(jwt_token) = claims.to_jwt_string() {
                         println!("🔑 Your one-time JWT token is {}\n", jwt_token.dimmed());
                     }
                 }
@@ -523,13 +741,40 @@ impl Start {
             Some(path) => StorageMode::Custom(path.clone()),
             None => StorageMode::from(self.dev),
         };
+        // Keep the transaction index on disk next to the ledger, so it is not rebuilt on every start.
+        if rest_config.transaction_index && self.storage_backend == StorageBackend::RocksDb {
+            let ledger_path = aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone());
+            rest_config.transaction_index_path = Some(snarkos_node_rest::transaction_index_path(&ledger_path));
+        }
+        // Bootstrap the ledger from a remote snapshot, if requested.
+        if let Some(url) = self.bootstrap_from.clone() {
+            ensure!(!node_type.is_prover(), "The '--bootstrap-from' flag is not supported for provers");
+            ensure!(self.dev.is_none(), "The '--bootstrap-from' flag is not supported in development mode");
+            ensure!(
+                self.storage_backend == StorageBackend::RocksDb,
+                "The '--bootstrap-from' flag requires the 'rocksdb' storage backend"
+            );
+            // A snapshot is only trusted if its manifest matches the checksum pinned by the operator.
+            let Some(checksum) = self.bootstrap_checksum.clone() else {
+                bail!("The '--bootstrap-from' flag requires the '--bootstrap-checksum' flag");
+            };
+            let storage_mode = storage_mode.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                crate::commands::bootstrap_ledger(&url, &checksum, N::ID, storage_mode)
+            })
+            .await?;
+            // A snapshot that cannot be downloaded or verified is discarded, and the ledger syncs every block instead.
+            if let Err(error) = result {
+                eprintln!("Failed to bootstrap the ledger, falling back to a full sync from peers - {error}\n");
+            }
+        }
 
         // Initialize the node.
         let bft_ip = if self.dev.is_some() { self.bft } else { None };
         match node_type {
-            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, storage_mode).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode).await,
+            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, rest_config, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, self.storage_backend, self.ledger_cache_size, self.min_free_disk, mempool_policy).await,
+            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, storage_mode, prover_threads).await,
+            NodeType::Client => Node::new_client(self.node, rest_ip, rest_config, account, &trusted_peers, genesis, cdn, storage_mode, self.storage_backend, self.ledger_cache_size, self.min_free_disk).await,
         }
     }
 
@@ -692,6 +937,28 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_rest_jwt_expiry() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.parse_rest_config().is_ok());
+
+        let config = Start::try_parse_from(["snarkos", "--rest-jwt-expiry", "0"].iter()).unwrap();
+        assert!(config.parse_rest_config().is_err());
+        let config = Start::try_parse_from(["snarkos", "--rest-jwt-expiry=-60"].iter()).unwrap();
+        assert!(config.parse_rest_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_bootstrap() {
+        // A snapshot is only accepted with a pinned manifest checksum.
+        assert!(Start::try_parse_from(["snarkos", "--bootstrap-from", "https://a.b"].iter()).is_err());
+        assert!(Start::try_parse_from(["snarkos", "--bootstrap-checksum", "ab"].iter()).is_err());
+        let config =
+            Start::try_parse_from(["snarkos", "--bootstrap-from", "https://a.b", "--bootstrap-checksum", "ab"].iter())
+                .unwrap();
+        assert_eq!(config.bootstrap_checksum.as_deref(), Some("ab"));
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
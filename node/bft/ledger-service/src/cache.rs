@@ -0,0 +1,179 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::{
+        block::{Block, Header},
+        coinbase::{ProverSolution, PuzzleCommitment},
+        store::ConsensusStorage,
+        Ledger,
+    },
+    prelude::{Network, Result},
+};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::{hash::Hash, num::NonZeroUsize, ops::Range, sync::Arc};
+
+/// The default number of recent blocks, headers, and solutions held by the ledger cache.
+pub const DEFAULT_LEDGER_CACHE_SIZE: usize = 256;
+
+/// An in-memory cache of the blocks, headers, and solutions read from the ledger.
+/// It spares the gossip and API read paths from loading and deserializing the same items repeatedly.
+#[derive(Clone)]
+pub struct LedgerCache<N: Network> {
+    /// The recently read blocks, keyed by height.
+    blocks: Arc<CacheMap<u32, Block<N>>>,
+    /// The recently read block headers, keyed by height.
+    headers: Arc<CacheMap<u32, Header<N>>>,
+    /// The recently read solutions, keyed by solution ID.
+    solutions: Arc<CacheMap<PuzzleCommitment<N>, ProverSolution<N>>>,
+}
+
+impl<N: Network> LedgerCache<N> {
+    /// Initializes a new ledger cache, holding up to `capacity` items of each kind (`0` disables the cache).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            blocks: Arc::new(CacheMap::new(
+                capacity,
+                #[cfg(feature = "metrics")]
+                (metrics::storage::BLOCK_CACHE_HITS, metrics::storage::BLOCK_CACHE_MISSES),
+            )),
+            headers: Arc::new(CacheMap::new(
+                capacity,
+                #[cfg(feature = "metrics")]
+                (metrics::storage::HEADER_CACHE_HITS, metrics::storage::HEADER_CACHE_MISSES),
+            )),
+            solutions: Arc::new(CacheMap::new(
+                capacity,
+                #[cfg(feature = "metrics")]
+                (metrics::storage::SOLUTION_CACHE_HITS, metrics::storage::SOLUTION_CACHE_MISSES),
+            )),
+        }
+    }
+
+    /// Returns the block for the given block height.
+    pub fn get_block<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, height: u32) -> Result<Block<N>> {
+        self.blocks.get_or_load(height, || ledger.get_block(height))
+    }
+
+    /// Returns the blocks in the given block range.
+    /// The range is inclusive of the start and exclusive of the end.
+    pub fn get_blocks<C: ConsensusStorage<N>>(
+        &self,
+        ledger: &Ledger<N, C>,
+        heights: Range<u32>,
+    ) -> Result<Vec<Block<N>>> {
+        heights.map(|height| self.get_block(ledger, height)).collect()
+    }
+
+    /// Returns the block header for the given block height.
+    pub fn get_header<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>, height: u32) -> Result<Header<N>> {
+        // If the block is cached, take the header from it, rather than loading it.
+        self.headers.get_or_load(height, || match self.blocks.peek(&height) {
+            Some(block) => Ok(*block.header()),
+            None => ledger.get_header(height),
+        })
+    }
+
+    /// Returns the solution for the given solution ID.
+    pub fn get_solution<C: ConsensusStorage<N>>(
+        &self,
+        ledger: &Ledger<N, C>,
+        solution_id: &PuzzleCommitment<N>,
+    ) -> Result<ProverSolution<N>> {
+        self.solutions.get_or_load(*solution_id, || ledger.get_solution(solution_id))
+    }
+}
+
+/// A least-recently-used map of the items of one kind.
+struct CacheMap<K: Hash + Eq, V: Clone> {
+    /// The cached items, or `None` if the cache is disabled.
+    entries: Option<Mutex<LruCache<K, V>>>,
+    /// The names of the hit and miss counters.
+    #[cfg(feature = "metrics")]
+    counters: (&'static str, &'static str),
+}
+
+impl<K: Hash + Eq, V: Clone> CacheMap<K, V> {
+    /// Initializes a new map with the given capacity (`0` disables the map).
+    fn new(capacity: usize, #[cfg(feature = "metrics")] counters: (&'static str, &'static str)) -> Self {
+        Self {
+            entries: NonZeroUsize::new(capacity).map(|capacity| Mutex::new(LruCache::new(capacity))),
+            #[cfg(feature = "metrics")]
+            counters,
+        }
+    }
+
+    /// Returns the item for the given key, loading and caching it if it is not cached.
+    fn get_or_load(&self, key: K, load: impl FnOnce() -> Result<V>) -> Result<V> {
+        let Some(entries) = &self.entries else {
+            return load();
+        };
+        if let Some(value) = entries.lock().get(&key) {
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter(self.counters.0);
+            return Ok(value.clone());
+        }
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(self.counters.1);
+        // Load the item without holding the lock, as loading it may be slow.
+        let value = load()?;
+        entries.lock().put(key, value.clone());
+        Ok(value)
+    }
+
+    /// Returns the item for the given key, if it is cached, without updating its recency.
+    fn peek(&self, key: &K) -> Option<V> {
+        self.entries.as_ref()?.lock().peek(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::bail;
+
+    fn new_map(capacity: usize) -> CacheMap<u32, u32> {
+        CacheMap::new(
+            capacity,
+            #[cfg(feature = "metrics")]
+            ("hits", "misses"),
+        )
+    }
+
+    #[test]
+    fn test_cache_map() {
+        let map = new_map(2);
+        assert_eq!(map.get_or_load(1, || Ok(10)).unwrap(), 10);
+        assert_eq!(map.get_or_load(2, || Ok(20)).unwrap(), 20);
+        // Cached items are not loaded again.
+        assert_eq!(map.get_or_load(1, || bail!("Item 1 is cached")).unwrap(), 10);
+        // The least recently used item is evicted.
+        assert_eq!(map.get_or_load(3, || Ok(30)).unwrap(), 30);
+        assert_eq!(map.peek(&2), None);
+        assert_eq!(map.peek(&1), Some(10));
+        // Failed loads are not cached.
+        assert!(map.get_or_load(4, || bail!("Item 4 is missing")).is_err());
+        assert_eq!(map.peek(&4), None);
+    }
+
+    #[test]
+    fn test_cache_map_disabled() {
+        let map = new_map(0);
+        assert_eq!(map.get_or_load(1, || Ok(10)).unwrap(), 10);
+        assert_eq!(map.get_or_load(1, || Ok(11)).unwrap(), 11);
+        assert_eq!(map.peek(&1), None);
+    }
+}
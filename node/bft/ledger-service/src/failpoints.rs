@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Failpoints inject errors and crashes at precise points of the ledger write path, to test that the
+//! ledger recovers consistently. They are only compiled with the `failpoints` feature.
+//! The block writes inside snarkVM are atomic, so the failpoints surround them.
+//!
+//! A failpoint is configured in code with [`configure`], or for a spawned process with the
+//! `SNARKOS_FAILPOINTS` environment variable, as a `;`-separated list of `name=action` pairs.
+
+use snarkvm::prelude::{anyhow, bail, Result};
+
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::OnceLock};
+
+/// The environment variable that configures the failpoints of a process.
+pub const FAILPOINTS_ENV: &str = "SNARKOS_FAILPOINTS";
+
+/// The failpoint before a block is written to the ledger.
+pub const BEFORE_ADVANCE: &str = "ledger::before_advance";
+/// The failpoint after a block is written to the ledger, before the write is reported.
+pub const AFTER_ADVANCE: &str = "ledger::after_advance";
+
+/// The action a failpoint takes when it is reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailpointAction {
+    /// Return an error.
+    Error,
+    /// Panic.
+    Panic,
+    /// Abort the process, as a crash would.
+    Abort,
+}
+
+impl FailpointAction {
+    /// Parses the given failpoint action.
+    fn parse(action: &str) -> Result<Self> {
+        match action {
+            "error" => Ok(Self::Error),
+            "panic" => Ok(Self::Panic),
+            "abort" => Ok(Self::Abort),
+            _ => bail!("Unknown failpoint action '{action}' (expected 'error', 'panic', or 'abort')"),
+        }
+    }
+}
+
+/// Returns the configured failpoints, which are initialized from the environment.
+fn failpoints() -> &'static Mutex<HashMap<String, FailpointAction>> {
+    static FAILPOINTS: OnceLock<Mutex<HashMap<String, FailpointAction>>> = OnceLock::new();
+    FAILPOINTS.get_or_init(|| {
+        let failpoints = match std::env::var(FAILPOINTS_ENV) {
+            Ok(config) => {
+                parse_failpoints(&config).unwrap_or_else(|error| panic!("Invalid {FAILPOINTS_ENV} - {error}"))
+            }
+            Err(_) => HashMap::new(),
+        };
+        Mutex::new(failpoints)
+    })
+}
+
+/// Parses the given `name=action` pairs.
+fn parse_failpoints(config: &str) -> Result<HashMap<String, FailpointAction>> {
+    config
+        .split(';')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (name, action) = entry.split_once('=').ok_or_else(|| anyhow!("Invalid failpoint '{entry}'"))?;
+            Ok((name.trim().to_string(), FailpointAction::parse(action.trim())?))
+        })
+        .collect()
+}
+
+/// Sets the action of the given failpoint.
+pub fn configure(name: &str, action: FailpointAction) {
+    failpoints().lock().insert(name.to_string(), action);
+}
+
+/// Removes the given failpoint.
+pub fn remove(name: &str) {
+    failpoints().lock().remove(name);
+}
+
+/// Removes every failpoint.
+pub fn clear() {
+    failpoints().lock().clear();
+}
+
+/// Takes the action of the given failpoint, if it is configured.
+pub fn trigger(name: &str) -> Result<()> {
+    let action = failpoints().lock().get(name).copied();
+    match action {
+        Some(FailpointAction::Error) => bail!("Failpoint '{name}' was reached"),
+        Some(FailpointAction::Panic) => panic!("Failpoint '{name}' was reached"),
+        Some(FailpointAction::Abort) => {
+            tracing::error!("Failpoint '{name}' was reached, aborting the process");
+            std::process::abort()
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_failpoints() {
+        let failpoints = parse_failpoints("ledger::before_advance=error; ledger::after_advance = abort;").unwrap();
+        assert_eq!(failpoints.len(), 2);
+        assert_eq!(failpoints[BEFORE_ADVANCE], FailpointAction::Error);
+        assert_eq!(failpoints[AFTER_ADVANCE], FailpointAction::Abort);
+        assert!(parse_failpoints("").unwrap().is_empty());
+        assert!(parse_failpoints("ledger::before_advance").is_err());
+        assert!(parse_failpoints("ledger::before_advance=crash").is_err());
+    }
+
+    #[test]
+    fn test_trigger() {
+        let name = "test::trigger";
+        assert!(trigger(name).is_ok());
+        configure(name, FailpointAction::Error);
+        assert!(trigger(name).is_err());
+        remove(name);
+        assert!(trigger(name).is_ok());
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{fmt_id, spawn_blocking, LedgerService};
+use crate::{fmt_id, spawn_blocking, LedgerCache, LedgerService};
 use snarkvm::{
     ledger::{
         block::{Block, Transaction},
@@ -43,6 +43,7 @@ const COMMITTEE_CACHE_SIZE: usize = 16;
 /// A core ledger service.
 pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
+    cache: LedgerCache<N>,
     coinbase_verifying_key: Arc<CoinbaseVerifyingKey<N>>,
     committee_cache: Arc<Mutex<LruCache<u64, Committee<N>>>>,
     shutdown: Arc<AtomicBool>,
@@ -50,10 +51,10 @@ pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
 
 impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
     /// Initializes a new core ledger service.
-    pub fn new(ledger: Ledger<N, C>, shutdown: Arc<AtomicBool>) -> Self {
+    pub fn new(ledger: Ledger<N, C>, cache: LedgerCache<N>, shutdown: Arc<AtomicBool>) -> Self {
         let coinbase_verifying_key = Arc::new(ledger.coinbase_puzzle().coinbase_verifying_key().clone());
         let committee_cache = Arc::new(Mutex::new(LruCache::new(COMMITTEE_CACHE_SIZE.try_into().unwrap())));
-        Self { ledger, coinbase_verifying_key, committee_cache, shutdown }
+        Self { ledger, cache, coinbase_verifying_key, committee_cache, shutdown }
     }
 }
 
@@ -98,18 +99,18 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
 
     /// Returns the block for the given block height.
     fn get_block(&self, height: u32) -> Result<Block<N>> {
-        self.ledger.get_block(height)
+        self.cache.get_block(&self.ledger, height)
     }
 
     /// Returns the blocks in the given block range.
     /// The range is inclusive of the start and exclusive of the end.
     fn get_blocks(&self, heights: Range<u32>) -> Result<Vec<Block<N>>> {
-        self.ledger.get_blocks(heights)
+        self.cache.get_blocks(&self.ledger, heights)
     }
 
     /// Returns the solution for the given solution ID.
     fn get_solution(&self, solution_id: &PuzzleCommitment<N>) -> Result<ProverSolution<N>> {
-        self.ledger.get_solution(solution_id)
+        self.cache.get_solution(&self.ledger, solution_id)
     }
 
     /// Returns the unconfirmed transaction for the given transaction ID.
@@ -315,8 +316,12 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         if self.shutdown.load(Ordering::Relaxed) {
             bail!("Skipping advancing to block {} - The node is shutting down", block.height());
         }
+        #[cfg(feature = "failpoints")]
+        crate::failpoints::trigger(crate::failpoints::BEFORE_ADVANCE)?;
         // Advance to the next block.
         self.ledger.advance_to_next_block(block)?;
+        #[cfg(feature = "failpoints")]
+        crate::failpoints::trigger(crate::failpoints::AFTER_ADVANCE)?;
         tracing::info!("\n\nAdvanced to block {} at round {} - {}\n", block.height(), block.round(), block.hash());
         Ok(())
     }
@@ -17,6 +17,14 @@
 #[macro_use]
 extern crate async_trait;
 
+#[cfg(feature = "ledger")]
+pub mod cache;
+#[cfg(feature = "ledger")]
+pub use cache::*;
+
+#[cfg(feature = "failpoints")]
+pub mod failpoints;
+
 #[cfg(feature = "ledger")]
 pub mod ledger;
 #[cfg(feature = "ledger")]
@@ -191,6 +191,11 @@ impl<N: Network> BFT<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.primary.unconfirmed_transactions()
     }
+
+    /// Returns the unconfirmed transaction with the given ID, if it is in the memory pool.
+    pub fn get_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> Option<Data<Transaction<N>>> {
+        self.primary.get_unconfirmed_transaction(transaction_id)
+    }
 }
 
 impl<N: Network> BFT<N> {
@@ -145,7 +145,13 @@ impl<N: Network> Gateway<N> {
             (Some(ip), _) => ip,
         };
         // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE));
+        // Note: The inbound admission limits are disabled, as the handshake only admits committee members,
+        // and validators that share a subnet, or reconnect after a restart, must not be throttled.
+        let tcp = Tcp::new(Config {
+            max_inbound_connections_per_sec: 0,
+            max_inbound_connections_per_subnet_per_sec: 0,
+            ..Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE)
+        });
         // Return the gateway.
         Ok(Self {
             account,
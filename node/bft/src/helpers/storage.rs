@@ -20,7 +20,7 @@ use snarkvm::{
         block::{Block, Transaction},
         narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
     },
-    prelude::{anyhow, bail, ensure, Address, Field, Network, Result},
+    prelude::{anyhow, bail, ensure, Address, Field, Network, Result, ToBytes},
 };
 
 use indexmap::{map::Entry, IndexMap, IndexSet};
@@ -33,6 +33,9 @@ use std::{
     },
 };
 
+/// The number of orphaned transmissions removed at once, while the certificates being inserted are held off.
+const ORPHANED_TRANSMISSIONS_BATCH_SIZE: usize = 1024;
+
 #[derive(Clone, Debug)]
 pub struct Storage<N: Network>(Arc<StorageInner<N>>);
 
@@ -86,6 +89,8 @@ pub struct StorageInner<N: Network> {
     batch_ids: RwLock<IndexMap<Field<N>, u64>>,
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: Arc<dyn StorageService<N>>,
+    /// The lock that keeps the orphaned transmissions from being removed while a certificate is inserted.
+    orphans_lock: RwLock<()>,
 }
 
 impl<N: Network> Storage<N> {
@@ -111,6 +116,7 @@ impl<N: Network> Storage<N> {
             certificates: Default::default(),
             batch_ids: Default::default(),
             transmissions,
+            orphans_lock: Default::default(),
         }));
         // Update the storage to the current round.
         storage.update_current_round(current_round);
@@ -501,6 +507,8 @@ impl<N: Network> Storage<N> {
     ) -> Result<()> {
         // Ensure the certificate round is above the GC round.
         ensure!(certificate.round() > self.gc_round(), "Certificate round is at or below the GC round");
+        // Hold off the removal of orphaned transmissions, as the missing transmissions are only those not in storage.
+        let _orphans_lock = self.orphans_lock.read();
         // Ensure the certificate and its transmissions are valid.
         let missing_transmissions = self.check_certificate(&certificate, transmissions)?;
         // Insert the certificate into storage.
@@ -583,6 +591,37 @@ impl<N: Network> Storage<N> {
         // Return successfully.
         true
     }
+
+    /// Removes the transmissions that are not referenced by any certificate in storage,
+    /// such as those left behind by the certificates of a previous run of the node.
+    /// Returns the number of removed transmissions, and their total size in bytes.
+    pub fn remove_orphaned_transmissions(&self) -> (usize, usize) {
+        // Find the orphaned transmissions, while the certificates being inserted are held off.
+        let orphaned = {
+            let _orphans_lock = self.orphans_lock.write();
+            let certificates = self.certificates.read();
+            self.transmissions.find_orphaned_transmissions(&|certificate_id| certificates.contains_key(certificate_id))
+        };
+        // Remove them in batches, so a certificate being inserted waits for one batch at most.
+        // Each batch only removes the transmissions that no certificate has referenced since.
+        let (mut num_removed, mut num_bytes) = (0, 0);
+        for transmission_ids in orphaned.chunks(ORPHANED_TRANSMISSIONS_BATCH_SIZE) {
+            let removed = {
+                let _orphans_lock = self.orphans_lock.write();
+                let certificates = self.certificates.read();
+                self.transmissions.remove_orphaned_transmissions(transmission_ids, &|certificate_id| {
+                    certificates.contains_key(certificate_id)
+                })
+            };
+            // Size the removed transmissions once the locks are released.
+            num_removed += removed.len();
+            num_bytes += removed
+                .iter()
+                .map(|transmission| transmission.to_bytes_le().map_or(0, |bytes| bytes.len()))
+                .sum::<usize>();
+        }
+        (num_removed, num_bytes)
+    }
 }
 
 impl<N: Network> Storage<N> {
@@ -945,6 +984,38 @@ mod tests {
         // Check that the underlying storage representation remains unchanged.
         assert_storage(&storage, &rounds, &certificates, &batch_ids, &transmissions);
     }
+
+    #[test]
+    fn test_remove_orphaned_transmissions() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage, keeping a handle on its transmissions.
+        let service = Arc::new(BFTMemoryService::new());
+        let storage = Storage::<CurrentNetwork>::new(ledger, service.clone(), 1);
+
+        // Insert the transmissions of a certificate that is not in storage, as if from a previous run.
+        let orphaned = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate(rng);
+        let (orphaned_transmissions, _) = sample_transmissions(&orphaned, rng);
+        service.insert_transmissions(orphaned.id(), orphaned.transmission_ids().clone(), orphaned_transmissions);
+
+        // Insert a certificate.
+        let certificate = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate(rng);
+        let (missing_transmissions, transmissions) = sample_transmissions(&certificate, rng);
+        storage.insert_certificate_atomic(certificate.clone(), missing_transmissions);
+        assert_eq!(storage.transmissions_iter().count(), transmissions.len() + orphaned.transmission_ids().len());
+
+        // Ensure only the orphaned transmissions are removed.
+        let (num_removed, num_bytes) = storage.remove_orphaned_transmissions();
+        assert_eq!(num_removed, orphaned.transmission_ids().len());
+        assert!(num_bytes > 0);
+        assert_eq!(storage.transmissions_iter().collect::<HashMap<_, _>>(), transmissions);
+        // Ensure there is nothing left to remove.
+        assert_eq!(storage.remove_orphaned_transmissions(), (0, 0));
+    }
 }
 
 #[cfg(test)]
@@ -195,6 +195,11 @@ impl<N: Network> Primary<N> {
         &self.gateway
     }
 
+    /// Returns the sync module.
+    pub const fn sync(&self) -> &Sync<N> {
+        &self.sync
+    }
+
     /// Returns the storage.
     pub const fn storage(&self) -> &Storage<N> {
         &self.storage
@@ -263,6 +268,12 @@ impl<N: Network> Primary<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.workers.iter().flat_map(|worker| worker.transactions())
     }
+
+    /// Returns the unconfirmed transaction with the given ID, if it is in the ready queue of its worker.
+    pub fn get_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> Option<Data<Transaction<N>>> {
+        let worker_id = assign_to_worker::<N>(transaction_id, self.num_workers()).ok()?;
+        self.workers.get(worker_id as usize)?.get_transaction(transaction_id)
+    }
 }
 
 impl<N: Network> Primary<N> {
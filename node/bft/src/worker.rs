@@ -142,6 +142,14 @@ impl<N: Network> Worker<N> {
     pub fn transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.ready.transactions()
     }
+
+    /// Returns the transaction with the given ID, if it is in the ready queue.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Option<Data<Transaction<N>>> {
+        match self.ready.get(transaction_id)? {
+            Transmission::Transaction(transaction) => Some(transaction),
+            _ => None,
+        }
+    }
 }
 
 impl<N: Network> Worker<N> {
@@ -143,6 +143,36 @@ impl<N: Network> StorageService<N> for BFTMemoryService<N> {
         }
     }
 
+    /// Returns the IDs of the transmissions that none of the known certificates reference.
+    fn find_orphaned_transmissions(&self, is_known_certificate: &dyn Fn(&Field<N>) -> bool) -> Vec<TransmissionID<N>> {
+        self.transmissions
+            .read()
+            .iter()
+            .filter(|(_, (_, certificate_ids))| !certificate_ids.iter().any(is_known_certificate))
+            .map(|(transmission_id, _)| *transmission_id)
+            .collect()
+    }
+
+    /// Removes the given transmissions that none of the known certificates reference, and returns them.
+    fn remove_orphaned_transmissions(
+        &self,
+        transmission_ids: &[TransmissionID<N>],
+        is_known_certificate: &dyn Fn(&Field<N>) -> bool,
+    ) -> Vec<Transmission<N>> {
+        // Acquire the transmissions write lock.
+        let mut transmissions = self.transmissions.write();
+        let mut removed = Vec::with_capacity(transmission_ids.len());
+        for transmission_id in transmission_ids {
+            // Skip the transmissions that a certificate references since they were found.
+            if let Entry::Occupied(occupied_entry) = transmissions.entry(*transmission_id) {
+                if !occupied_entry.get().1.iter().any(is_known_certificate) {
+                    removed.push(occupied_entry.shift_remove().0);
+                }
+            }
+        }
+        removed
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
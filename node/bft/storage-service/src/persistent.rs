@@ -18,6 +18,7 @@ use snarkvm::{
         narwhal::{BatchHeader, Transmission, TransmissionID},
         store::{
             cow_to_cloned,
+            cow_to_copied,
             helpers::{
                 rocksdb::{
                     internal::{self, BFTMap, Database, MapID},
@@ -33,6 +34,7 @@ use snarkvm::{
 
 use aleo_std::StorageMode;
 use indexmap::{indexset, IndexSet};
+use parking_lot::Mutex;
 use std::{borrow::Cow, collections::HashMap};
 use tracing::error;
 
@@ -41,12 +43,17 @@ use tracing::error;
 pub struct BFTPersistentStorage<N: Network> {
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: DataMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>,
+    /// The lock that serializes the updates to the entries, as each update reads an entry and then writes it.
+    lock: Mutex<()>,
 }
 
 impl<N: Network> BFTPersistentStorage<N> {
     /// Initializes a new BFT persistent storage service.
     pub fn open(storage_mode: StorageMode) -> Result<Self> {
-        Ok(Self { transmissions: internal::RocksDB::open_map(N::ID, storage_mode, MapID::BFT(BFTMap::Transmissions))? })
+        Ok(Self {
+            transmissions: internal::RocksDB::open_map(N::ID, storage_mode, MapID::BFT(BFTMap::Transmissions))?,
+            lock: Default::default(),
+        })
     }
 
     /// Initializes a new BFT persistent storage service.
@@ -54,6 +61,7 @@ impl<N: Network> BFTPersistentStorage<N> {
     pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
         Ok(Self {
             transmissions: internal::RocksDB::open_map_testing(temp_dir, dev, MapID::BFT(BFTMap::Transmissions))?,
+            lock: Default::default(),
         })
     }
 }
@@ -116,6 +124,8 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         transmission_ids: IndexSet<TransmissionID<N>>,
         mut missing_transmissions: HashMap<TransmissionID<N>, Transmission<N>>,
     ) {
+        // Acquire the update lock.
+        let _lock = self.lock.lock();
         // Inserts the following:
         //   - Inserts **only the missing** transmissions from storage.
         //   - Inserts the certificate ID into the corresponding set for **all** transmissions.
@@ -158,6 +168,8 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
     ///
     /// If the transmission no longer references any certificate IDs, the entry is removed from storage.
     fn remove_transmissions(&self, certificate_id: &Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>) {
+        // Acquire the update lock.
+        let _lock = self.lock.lock();
         // If this is the last certificate ID for the transmission ID, remove the transmission.
         'outer: for transmission_id in transmission_ids {
             // Retrieve the transmission entry.
@@ -194,10 +206,45 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         }
     }
 
+    /// Returns the IDs of the transmissions that none of the known certificates reference.
+    fn find_orphaned_transmissions(&self, is_known_certificate: &dyn Fn(&Field<N>) -> bool) -> Vec<TransmissionID<N>> {
+        self.transmissions
+            .iter_confirmed()
+            .filter(|(_, entry)| !entry.1.iter().any(is_known_certificate))
+            .map(|(transmission_id, _)| cow_to_copied!(transmission_id))
+            .collect()
+    }
+
+    /// Removes the given transmissions that none of the known certificates reference, and returns them.
+    fn remove_orphaned_transmissions(
+        &self,
+        transmission_ids: &[TransmissionID<N>],
+        is_known_certificate: &dyn Fn(&Field<N>) -> bool,
+    ) -> Vec<Transmission<N>> {
+        // Acquire the update lock.
+        let _lock = self.lock.lock();
+        let mut removed = Vec::with_capacity(transmission_ids.len());
+        for transmission_id in transmission_ids {
+            // Skip the transmissions that a certificate references since they were found.
+            let transmission = match self.transmissions.get_confirmed(transmission_id) {
+                Ok(Some(entry)) if !entry.1.iter().any(is_known_certificate) => cow_to_cloned!(entry).0,
+                Ok(_) => continue,
+                Err(e) => {
+                    error!("Failed to read the orphaned transmission {transmission_id} from storage - {e}");
+                    continue;
+                }
+            };
+            match self.transmissions.remove(transmission_id) {
+                Ok(()) => removed.push(transmission),
+                Err(e) => error!("Failed to remove the orphaned transmission {transmission_id} from storage - {e}"),
+            }
+        }
+        removed
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
-        use snarkvm::ledger::store::cow_to_copied;
         self.transmissions.iter_confirmed().map(|(k, v)| (cow_to_copied!(k), cow_to_cloned!(v))).collect()
     }
 }
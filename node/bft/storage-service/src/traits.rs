@@ -48,6 +48,16 @@ pub trait StorageService<N: Network>: Debug + Send + Sync {
     /// If the transmission no longer references any certificate IDs, the entry is removed from storage.
     fn remove_transmissions(&self, certificate_id: &Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>);
 
+    /// Returns the IDs of the transmissions that none of the known certificates reference.
+    fn find_orphaned_transmissions(&self, is_known_certificate: &dyn Fn(&Field<N>) -> bool) -> Vec<TransmissionID<N>>;
+
+    /// Removes the given transmissions that none of the known certificates reference, and returns them.
+    fn remove_orphaned_transmissions(
+        &self,
+        transmission_ids: &[TransmissionID<N>],
+        is_known_certificate: &dyn Fn(&Field<N>) -> bool,
+    ) -> Vec<Transmission<N>>;
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>;
@@ -17,6 +17,9 @@
 #[macro_use]
 extern crate tracing;
 
+mod queue;
+pub use queue::*;
+
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{
@@ -31,7 +34,7 @@ use snarkos_node_bft::{
     BFT,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
-use snarkos_node_bft_storage_service::BFTPersistentStorage;
+use snarkos_node_bft_storage_service::{BFTMemoryService, BFTPersistentStorage, StorageService};
 use snarkvm::{
     ledger::{
         block::Transaction,
@@ -47,37 +50,23 @@ use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::{
     sync::{oneshot, OnceCell},
     task::JoinHandle,
 };
 
-/// Percentage of mempool transactions capacity reserved for deployments.
-const CAPACITY_FOR_DEPLOYMENTS: usize = 20;
-/// Percentage of mempool transactions capacity reserved for executions.
-const CAPACITY_FOR_EXECUTIONS: usize = 80;
-
-/// Helper struct to track incoming transactions.
-struct TransactionsQueue<N: Network> {
-    pub deployments: LruCache<N::TransactionID, Transaction<N>>,
-    pub executions: LruCache<N::TransactionID, Transaction<N>>,
-}
-
-impl<N: Network> Default for TransactionsQueue<N> {
-    fn default() -> Self {
-        Self {
-            deployments: LruCache::new(
-                NonZeroUsize::new(BatchHeader::<N>::MAX_TRANSMISSIONS_PER_BATCH * CAPACITY_FOR_DEPLOYMENTS / 100)
-                    .unwrap(),
-            ),
-            executions: LruCache::new(
-                NonZeroUsize::new(BatchHeader::<N>::MAX_TRANSMISSIONS_PER_BATCH * CAPACITY_FOR_EXECUTIONS / 100)
-                    .unwrap(),
-            ),
-        }
-    }
-}
+/// The interval at which the transmissions orphaned in storage are removed.
+const ORPHANED_TRANSMISSIONS_GC_INTERVAL_IN_SECS: u64 = 600;
 
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
@@ -90,11 +79,13 @@ pub struct Consensus<N: Network> {
     /// The unconfirmed solutions queue.
     solutions_queue: Arc<Mutex<LruCache<PuzzleCommitment<N>, ProverSolution<N>>>>,
     /// The unconfirmed transactions queue.
-    transactions_queue: Arc<Mutex<TransactionsQueue<N>>>,
+    transactions_queue: Arc<Mutex<TransactionsQueue<N::TransactionID, Transaction<N>>>>,
     /// The recently-seen unconfirmed solutions.
     seen_solutions: Arc<Mutex<LruCache<PuzzleCommitment<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The boolean indicator of whether the memory pool is paused, such as while the node is low on disk space.
+    is_mempool_paused: Arc<AtomicBool>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -107,14 +98,19 @@ impl<N: Network> Consensus<N> {
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         storage_mode: StorageMode,
+        in_memory: bool,
+        mempool_policy: MempoolPolicy,
     ) -> Result<Self> {
         // Recover the development ID, if it is present.
         let dev = match storage_mode {
             StorageMode::Development(id) => Some(id),
             StorageMode::Production | StorageMode::Custom(..) => None,
         };
-        // Initialize the Narwhal transmissions.
-        let transmissions = Arc::new(BFTPersistentStorage::open(storage_mode)?);
+        // Initialize the Narwhal transmissions, which are kept in memory alongside an in-memory ledger.
+        let transmissions: Arc<dyn StorageService<N>> = match in_memory {
+            true => Arc::new(BFTMemoryService::new()),
+            false => Arc::new(BFTPersistentStorage::open(storage_mode)?),
+        };
         // Initialize the Narwhal storage.
         let storage = NarwhalStorage::new(ledger.clone(), transmissions, BatchHeader::<N>::MAX_GC_ROUNDS as u64);
         // Initialize the BFT.
@@ -127,9 +123,10 @@ impl<N: Network> Consensus<N> {
             solutions_queue: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(BatchHeader::<N>::MAX_TRANSMISSIONS_PER_BATCH).unwrap(),
             ))),
-            transactions_queue: Default::default(),
+            transactions_queue: Arc::new(Mutex::new(TransactionsQueue::new(mempool_policy))),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            is_mempool_paused: Default::default(),
             handles: Default::default(),
         })
     }
@@ -163,6 +160,16 @@ impl<N: Network> Consensus<N> {
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set")
     }
+
+    /// Returns `true` if the memory pool is paused.
+    pub fn is_mempool_paused(&self) -> bool {
+        self.is_mempool_paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses or resumes the memory pool. While paused, unconfirmed solutions and transactions are rejected.
+    pub fn set_mempool_paused(&self, is_paused: bool) {
+        self.is_mempool_paused.store(is_paused, Ordering::SeqCst);
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -207,6 +214,75 @@ impl<N: Network> Consensus<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.bft.unconfirmed_transactions()
     }
+
+    /// Returns the unconfirmed transaction with the given ID, if it is in the memory pool.
+    pub fn get_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> Option<Data<Transaction<N>>> {
+        self.bft.get_unconfirmed_transaction(transaction_id)
+    }
+
+    /// Returns the IDs of the unconfirmed transactions in the memory pool.
+    pub fn unconfirmed_transaction_ids(&self) -> impl '_ + Iterator<Item = N::TransactionID> {
+        self.unconfirmed_transmission_ids().filter_map(|id| match id {
+            TransmissionID::Transaction(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Returns the number of transactions waiting in the queue to enter the memory pool.
+    pub fn num_queued_transactions(&self) -> usize {
+        self.transactions_queue.lock().len()
+    }
+
+    /// Returns the total size of the transactions waiting in the queue to enter the memory pool, in bytes.
+    pub fn num_queued_transaction_bytes(&self) -> usize {
+        self.transactions_queue.lock().num_bytes()
+    }
+
+    /// Returns the number of transactions that expired, and that were evicted, from the queue.
+    pub fn num_dropped_transactions(&self) -> (u64, u64) {
+        let queue = self.transactions_queue.lock();
+        (queue.num_expired(), queue.num_evicted())
+    }
+
+    /// Returns the retention and expiry policy of the queue.
+    pub fn mempool_policy(&self) -> MempoolPolicy {
+        *self.transactions_queue.lock().policy()
+    }
+
+    /// Returns the number of solutions waiting in the queue to enter the memory pool.
+    pub fn num_queued_solutions(&self) -> usize {
+        self.solutions_queue.lock().len()
+    }
+
+    /// Returns the transactions waiting in the queue to enter the memory pool.
+    pub fn queued_transactions(&self) -> Vec<Transaction<N>> {
+        self.transactions_queue.lock().values().cloned().collect()
+    }
+
+    /// Returns the IDs of the transactions waiting in the queue to enter the memory pool, deployments first.
+    pub fn queued_transaction_ids(&self) -> Vec<N::TransactionID> {
+        self.transactions_queue.lock().keys().copied().collect()
+    }
+
+    /// Returns the number of deployments waiting in the queue to enter the memory pool.
+    pub fn num_queued_deployments(&self) -> usize {
+        self.transactions_queue.lock().num_deployments()
+    }
+
+    /// Returns the fees of the transactions waiting in the queue to enter the memory pool, in microcredits.
+    pub fn queued_transaction_fees(&self) -> Vec<u64> {
+        self.transactions_queue.lock().fees().collect()
+    }
+
+    /// Returns the fee densities of the transactions waiting in the queue to enter the memory pool.
+    pub fn queued_fee_densities(&self) -> Vec<FeeDensity> {
+        self.transactions_queue.lock().fee_densities().collect()
+    }
+
+    /// Returns the queued transaction with the given ID, if it is waiting to enter the memory pool.
+    pub fn get_queued_transaction(&self, transaction_id: &N::TransactionID) -> Option<Transaction<N>> {
+        self.transactions_queue.lock().peek(transaction_id).cloned()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -216,6 +292,11 @@ impl<N: Network> Consensus<N> {
         {
             let solution_id = solution.commitment();
 
+            // Check that the memory pool is not paused.
+            if self.is_mempool_paused() {
+                bail!("Solution '{}' was rejected, as the memory pool is paused", fmt_id(solution_id));
+            }
+
             // Check if the transaction was recently seen.
             if self.seen_solutions.lock().put(solution_id, ()).is_some() {
                 // If the transaction was recently seen, return early.
@@ -263,12 +344,17 @@ impl<N: Network> Consensus<N> {
         Ok(())
     }
 
-    /// Adds the given unconfirmed transaction to the memory pool.
-    pub async fn add_unconfirmed_transaction(&self, transaction: Transaction<N>) -> Result<()> {
+    /// Adds the given unconfirmed transaction to the memory pool, with the size of its serialized bytes, if known.
+    /// The transaction is only serialized to measure it if the size is unknown.
+    pub async fn add_unconfirmed_transaction(&self, transaction: Transaction<N>, size: Option<usize>) -> Result<()> {
         // Process the unconfirmed transaction.
         {
             let transaction_id = transaction.id();
 
+            // Check that the memory pool is not paused.
+            if self.is_mempool_paused() {
+                bail!("Transaction '{}' was rejected, as the memory pool is paused", fmt_id(transaction_id));
+            }
             // Check that the transaction is not a fee transaction.
             if transaction.is_fee() {
                 bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
@@ -284,11 +370,13 @@ impl<N: Network> Consensus<N> {
             }
             // Add the transaction to the memory pool.
             trace!("Received unconfirmed transaction '{}' in the queue", fmt_id(transaction_id));
-            if transaction.is_deploy() {
-                if self.transactions_queue.lock().deployments.put(transaction_id, transaction).is_some() {
-                    bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
-                }
-            } else if self.transactions_queue.lock().executions.put(transaction_id, transaction).is_some() {
+            let is_deployment = transaction.is_deploy();
+            let size = match size {
+                Some(size) => size,
+                None => transaction.to_bytes_le()?.len(),
+            };
+            let fee = transaction.fee_amount().map(|fee| *fee).unwrap_or(0);
+            if !self.transactions_queue.lock().insert(transaction_id, transaction, is_deployment, size, fee) {
                 bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
             }
         }
@@ -305,22 +393,14 @@ impl<N: Network> Consensus<N> {
             // Acquire the lock on the transactions queue.
             let mut tx_queue = self.transactions_queue.lock();
             // Determine the number of deployments to send.
-            let num_deployments = tx_queue.deployments.len().min(capacity * CAPACITY_FOR_DEPLOYMENTS / 100);
+            let num_deployments = tx_queue.num_deployments().min(capacity * CAPACITY_FOR_DEPLOYMENTS / 100);
             // Determine the number of executions to send.
-            let num_executions = tx_queue.executions.len().min(capacity.saturating_sub(num_deployments));
+            let num_executions = tx_queue.num_executions().min(capacity.saturating_sub(num_deployments));
             // Create an iterator which will select interleaved deployments and executions within the capacity.
             // Note: interleaving ensures we will never have consecutive invalid deployments blocking the queue.
             let selector_iter = (0..num_deployments).map(|_| true).interleave((0..num_executions).map(|_| false));
             // Drain the transactions from the queue, interleaving deployments and executions.
-            selector_iter
-                .filter_map(|select_deployment| {
-                    if select_deployment {
-                        tx_queue.deployments.pop_lru().map(|(_, tx)| tx)
-                    } else {
-                        tx_queue.executions.pop_lru().map(|(_, tx)| tx)
-                    }
-                })
-                .collect_vec()
+            selector_iter.filter_map(|select_deployment| tx_queue.pop(select_deployment)).collect_vec()
         };
         // Iterate over the transactions.
         for transaction in transactions.into_iter() {
@@ -355,6 +435,33 @@ impl<N: Network> Consensus<N> {
                 self_.process_bft_subdag(committed_subdag, transmissions, callback).await;
             }
         });
+
+        // Periodically remove the transmissions orphaned in storage, starting with those left by the previous run.
+        let self_ = self.clone();
+        self.spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(ORPHANED_TRANSMISSIONS_GC_INTERVAL_IN_SECS));
+            loop {
+                interval.tick().await;
+                let storage = self_.bft.storage().clone();
+                match spawn_blocking!(Ok(storage.remove_orphaned_transmissions())) {
+                    Ok((0, _)) => (),
+                    Ok((num_removed, num_bytes)) => {
+                        info!(
+                            "Removed {num_removed} orphaned transmissions from storage, reclaiming {num_bytes} bytes"
+                        );
+                        #[cfg(feature = "metrics")]
+                        {
+                            metrics::increment_counter_by(
+                                metrics::storage::ORPHANED_TRANSMISSIONS_REMOVED,
+                                num_removed as u64,
+                            );
+                            metrics::increment_counter_by(metrics::storage::ORPHANED_BYTES_RECLAIMED, num_bytes as u64);
+                        }
+                    }
+                    Err(e) => error!("Failed to remove the orphaned transmissions from storage - {e}"),
+                }
+            }
+        });
     }
 
     /// Processes the committed subdag and transmissions from the BFT.
@@ -398,10 +505,13 @@ impl<N: Network> Consensus<N> {
         // Check that the block is well-formed.
         self.ledger.check_next_block(&next_block)?;
         // Advance to the next block.
+        #[cfg(feature = "metrics")]
+        let write_timer = std::time::Instant::now();
         self.ledger.advance_to_next_block(&next_block)?;
 
         #[cfg(feature = "metrics")]
         {
+            metrics::histogram(metrics::storage::BLOCK_WRITE_LATENCY, write_timer.elapsed().as_secs_f64());
             let elapsed = std::time::Duration::from_secs((snarkos_node_bft::helpers::now() - start) as u64);
             let next_block_timestamp = next_block.header().metadata().timestamp();
             let block_latency = next_block_timestamp - current_block_timestamp;
@@ -0,0 +1,451 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{ledger::narwhal::BatchHeader, prelude::Network};
+
+use anyhow::{bail, Result};
+use lru::LruCache;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::Hash,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Percentage of mempool transactions capacity reserved for deployments.
+pub(crate) const CAPACITY_FOR_DEPLOYMENTS: usize = 20;
+
+/// The default maximum total size of the queued transactions, in bytes.
+pub const DEFAULT_MAX_QUEUED_BYTES: usize = 64 * 1024 * 1024;
+/// The default maximum time a transaction waits in the queue, in seconds.
+pub const DEFAULT_MAX_QUEUED_AGE_SECS: u64 = 600;
+
+/// The order in which queued transactions are evicted, once the queue is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvictionOrder {
+    /// Evict the oldest transaction first.
+    Age,
+    /// Evict the transaction with the lowest fee first, and the oldest among equal fees.
+    Fee,
+}
+
+impl FromStr for EvictionOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(order: &str) -> Result<Self> {
+        match order {
+            "age" => Ok(Self::Age),
+            "fee" => Ok(Self::Fee),
+            _ => bail!("Unknown eviction order '{order}' (expected 'age' or 'fee')"),
+        }
+    }
+}
+
+impl fmt::Display for EvictionOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Age => write!(f, "age"),
+            Self::Fee => write!(f, "fee"),
+        }
+    }
+}
+
+/// The order in which queued transactions enter the memory pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionOrder {
+    /// The oldest transaction enters first.
+    Arrival,
+    /// The transaction with the highest fee per byte enters first, and the oldest among equal fee densities.
+    FeeDensity,
+}
+
+impl FromStr for TransactionOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(order: &str) -> Result<Self> {
+        match order {
+            "arrival" => Ok(Self::Arrival),
+            "fee-density" => Ok(Self::FeeDensity),
+            _ => bail!("Unknown transaction order '{order}' (expected 'arrival' or 'fee-density')"),
+        }
+    }
+}
+
+impl fmt::Display for TransactionOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Arrival => write!(f, "arrival"),
+            Self::FeeDensity => write!(f, "fee-density"),
+        }
+    }
+}
+
+/// The fee of a transaction per byte of its size, compared exactly, without rounding.
+#[derive(Clone, Copy, Debug)]
+pub struct FeeDensity {
+    /// The fee, in microcredits.
+    fee: u64,
+    /// The size, in bytes.
+    size: usize,
+}
+
+impl FeeDensity {
+    /// Initializes the fee density of a transaction with the given fee and size.
+    pub const fn new(fee: u64, size: usize) -> Self {
+        Self { fee, size }
+    }
+
+    /// Returns the fee per byte, rounded down, treating an empty transaction as one byte.
+    pub const fn fee_per_byte(&self) -> u64 {
+        self.fee / if self.size == 0 { 1 } else { self.size as u64 }
+    }
+}
+
+impl Ord for FeeDensity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare `fee / size` by cross-multiplying, treating an empty transaction as one byte.
+        let lhs = u128::from(self.fee) * other.size.max(1) as u128;
+        let rhs = u128::from(other.fee) * self.size.max(1) as u128;
+        lhs.cmp(&rhs)
+    }
+}
+
+impl PartialOrd for FeeDensity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for FeeDensity {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FeeDensity {}
+
+/// The retention and expiry policy of the transactions waiting to enter the memory pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MempoolPolicy {
+    /// The maximum number of queued transactions.
+    pub max_transactions: usize,
+    /// The maximum total size of the queued transactions, in bytes.
+    pub max_bytes: usize,
+    /// The maximum time a transaction waits in the queue, before it expires.
+    pub max_age: Duration,
+    /// The order in which transactions are evicted, once the queue is full.
+    pub eviction: EvictionOrder,
+    /// The order in which transactions enter the memory pool.
+    pub ordering: TransactionOrder,
+}
+
+impl MempoolPolicy {
+    /// Returns the default policy, which queues up to one batch of transactions.
+    pub fn new<N: Network>() -> Self {
+        Self {
+            max_transactions: BatchHeader::<N>::MAX_TRANSMISSIONS_PER_BATCH,
+            max_bytes: DEFAULT_MAX_QUEUED_BYTES,
+            max_age: Duration::from_secs(DEFAULT_MAX_QUEUED_AGE_SECS),
+            eviction: EvictionOrder::Age,
+            ordering: TransactionOrder::Arrival,
+        }
+    }
+
+    /// Returns the maximum number of queued deployments.
+    fn max_deployments(&self) -> usize {
+        (self.max_transactions * CAPACITY_FOR_DEPLOYMENTS / 100).max(1)
+    }
+
+    /// Returns the maximum number of queued executions.
+    fn max_executions(&self) -> usize {
+        self.max_transactions.saturating_sub(self.max_deployments()).max(1)
+    }
+}
+
+/// A queued transaction, with the metadata its policy is enforced with.
+struct QueuedEntry<V> {
+    value: V,
+    /// The size of the transaction, in bytes.
+    size: usize,
+    /// The fee of the transaction, in microcredits.
+    fee: u64,
+    /// The time the transaction was queued.
+    queued_at: Instant,
+}
+
+/// The transactions waiting to enter the memory pool, in the order they were queued,
+/// with their number, size, and age bounded by the policy.
+pub(crate) struct TransactionsQueue<K: Hash + Eq, V> {
+    /// The retention and expiry policy.
+    policy: MempoolPolicy,
+    /// The queued deployments.
+    deployments: LruCache<K, QueuedEntry<V>>,
+    /// The queued executions.
+    executions: LruCache<K, QueuedEntry<V>>,
+    /// The total size of the queued transactions, in bytes.
+    num_bytes: usize,
+    /// The number of transactions that expired.
+    num_expired: u64,
+    /// The number of transactions evicted to bound the queue.
+    num_evicted: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> TransactionsQueue<K, V> {
+    /// Initializes a new queue with the given policy.
+    pub(crate) fn new(policy: MempoolPolicy) -> Self {
+        Self {
+            policy,
+            deployments: LruCache::unbounded(),
+            executions: LruCache::unbounded(),
+            num_bytes: 0,
+            num_expired: 0,
+            num_evicted: 0,
+        }
+    }
+
+    /// Returns the policy of the queue.
+    pub(crate) const fn policy(&self) -> &MempoolPolicy {
+        &self.policy
+    }
+
+    /// Returns the number of queued transactions.
+    pub(crate) fn len(&self) -> usize {
+        self.deployments.len() + self.executions.len()
+    }
+
+    /// Returns the number of queued deployments.
+    pub(crate) fn num_deployments(&self) -> usize {
+        self.deployments.len()
+    }
+
+    /// Returns the number of queued executions.
+    pub(crate) fn num_executions(&self) -> usize {
+        self.executions.len()
+    }
+
+    /// Returns the total size of the queued transactions, in bytes.
+    pub(crate) const fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+
+    /// Returns the number of transactions that expired.
+    pub(crate) const fn num_expired(&self) -> u64 {
+        self.num_expired
+    }
+
+    /// Returns the number of transactions evicted to bound the queue.
+    pub(crate) const fn num_evicted(&self) -> u64 {
+        self.num_evicted
+    }
+
+    /// Returns the queued transactions, deployments first.
+    pub(crate) fn values(&self) -> impl '_ + Iterator<Item = &V> {
+        self.deployments.iter().chain(self.executions.iter()).map(|(_, entry)| &entry.value)
+    }
+
+    /// Returns the keys of the queued transactions, deployments first.
+    pub(crate) fn keys(&self) -> impl '_ + Iterator<Item = &K> {
+        self.deployments.iter().chain(self.executions.iter()).map(|(key, _)| key)
+    }
+
+    /// Returns the fees of the queued transactions, deployments first.
+    pub(crate) fn fees(&self) -> impl '_ + Iterator<Item = u64> {
+        self.deployments.iter().chain(self.executions.iter()).map(|(_, entry)| entry.fee)
+    }
+
+    /// Returns the fee densities of the queued transactions, deployments first.
+    pub(crate) fn fee_densities(&self) -> impl '_ + Iterator<Item = FeeDensity> {
+        self.deployments.iter().chain(self.executions.iter()).map(|(_, entry)| FeeDensity::new(entry.fee, entry.size))
+    }
+
+    /// Returns the queued transaction with the given key.
+    pub(crate) fn peek(&self, key: &K) -> Option<&V> {
+        self.deployments.peek(key).or_else(|| self.executions.peek(key)).map(|entry| &entry.value)
+    }
+
+    /// Queues the given transaction, and then evicts transactions until the queue is within its policy.
+    /// Returns `false` if the transaction is already queued.
+    pub(crate) fn insert(&mut self, key: K, value: V, is_deployment: bool, size: usize, fee: u64) -> bool {
+        if self.deployments.contains(&key) || self.executions.contains(&key) {
+            return false;
+        }
+        let entry = QueuedEntry { value, size, fee, queued_at: Instant::now() };
+        match is_deployment {
+            true => self.deployments.put(key, entry),
+            false => self.executions.put(key, entry),
+        };
+        self.num_bytes += size;
+
+        self.expire(Instant::now());
+        // Bound the number of transactions of each kind.
+        while self.deployments.len() > self.policy.max_deployments() {
+            self.evict(Some(true));
+        }
+        while self.executions.len() > self.policy.max_executions() {
+            self.evict(Some(false));
+        }
+        // Bound the total size of the transactions.
+        while self.num_bytes > self.policy.max_bytes && self.len() > 0 {
+            self.evict(None);
+        }
+        true
+    }
+
+    /// Removes and returns the next queued deployment or execution, in the order of the policy,
+    /// skipping the expired transactions.
+    pub(crate) fn pop(&mut self, is_deployment: bool) -> Option<V> {
+        self.expire(Instant::now());
+        let queue = match is_deployment {
+            true => &mut self.deployments,
+            false => &mut self.executions,
+        };
+        let key = match self.policy.ordering {
+            TransactionOrder::Arrival => queue.peek_lru().map(|(key, _)| key.clone())?,
+            TransactionOrder::FeeDensity => queue
+                .iter()
+                .max_by(|(_, a), (_, b)| {
+                    // Prefer the highest fee density, and then the oldest transaction.
+                    FeeDensity::new(a.fee, a.size)
+                        .cmp(&FeeDensity::new(b.fee, b.size))
+                        .then_with(|| b.queued_at.cmp(&a.queued_at))
+                })
+                .map(|(key, _)| key.clone())?,
+        };
+        let entry = queue.pop(&key)?;
+        self.num_bytes -= entry.size;
+        Some(entry.value)
+    }
+
+    /// Removes the transactions that have been queued for longer than the policy allows.
+    fn expire(&mut self, now: Instant) {
+        let max_age = self.policy.max_age;
+        for queue in [&mut self.deployments, &mut self.executions] {
+            // The queue is in insertion order, so the expired transactions are the oldest.
+            while let Some((_, entry)) = queue.peek_lru() {
+                if now.saturating_duration_since(entry.queued_at) <= max_age {
+                    break;
+                }
+                if let Some((_, entry)) = queue.pop_lru() {
+                    self.num_bytes -= entry.size;
+                    self.num_expired += 1;
+                }
+            }
+        }
+    }
+
+    /// Evicts one transaction, in the order of the policy, among the deployments, the executions, or both.
+    fn evict(&mut self, is_deployment: Option<bool>) {
+        let eviction = self.policy.eviction;
+        // Returns the key of the next transaction to evict from the given queue, and its ordering.
+        let candidate = |queue: &LruCache<K, QueuedEntry<V>>| match eviction {
+            EvictionOrder::Age => queue.peek_lru().map(|(key, entry)| (key.clone(), (0, entry.queued_at))),
+            EvictionOrder::Fee => queue
+                .iter()
+                .map(|(key, entry)| (key.clone(), (entry.fee, entry.queued_at)))
+                .min_by_key(|(_, order)| *order),
+        };
+        let deployment = is_deployment.unwrap_or(true).then(|| candidate(&self.deployments)).flatten();
+        let execution = (!is_deployment.unwrap_or(false)).then(|| candidate(&self.executions)).flatten();
+
+        let (queue, key) = match (deployment, execution) {
+            (Some((key, order)), Some((_, other))) if order <= other => (&mut self.deployments, key),
+            (_, Some((key, _))) => (&mut self.executions, key),
+            (Some((key, _)), None) => (&mut self.deployments, key),
+            (None, None) => return,
+        };
+        if let Some(entry) = queue.pop(&key) {
+            self.num_bytes -= entry.size;
+            self.num_evicted += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_transactions: usize, max_bytes: usize, eviction: EvictionOrder) -> MempoolPolicy {
+        let ordering = TransactionOrder::Arrival;
+        MempoolPolicy { max_transactions, max_bytes, max_age: Duration::from_secs(60), eviction, ordering }
+    }
+
+    #[test]
+    fn test_eviction_by_age() {
+        let mut queue = TransactionsQueue::new(policy(10, 1000, EvictionOrder::Age));
+        // The executions are bounded by 80% of the transactions.
+        for id in 0..9u32 {
+            assert!(queue.insert(id, id, false, 10, 100 - u64::from(id)));
+        }
+        assert_eq!(queue.num_executions(), 8);
+        assert_eq!(queue.num_evicted(), 1);
+        assert!(queue.peek(&0).is_none());
+        assert_eq!(queue.num_bytes(), 80);
+        // Duplicates are not queued.
+        assert!(!queue.insert(1, 1, false, 10, 100));
+        assert_eq!(queue.pop(false), Some(1));
+    }
+
+    #[test]
+    fn test_eviction_by_fee() {
+        let mut queue = TransactionsQueue::new(policy(10, 1000, EvictionOrder::Fee));
+        for (id, fee) in [(0u32, 50), (1, 10), (2, 30), (3, 10)] {
+            assert!(queue.insert(id, id, id == 0, 300, fee));
+        }
+        // The total size is bounded, so the lowest fee (and oldest) transaction is evicted.
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.num_bytes(), 900);
+        assert!(queue.peek(&1).is_none());
+        assert!(queue.peek(&3).is_some());
+        // The deployments compete with the executions for the size.
+        assert!(queue.insert(4, 4, false, 300, 40));
+        assert!(queue.peek(&3).is_none());
+        assert_eq!(queue.num_deployments(), 1);
+        assert_eq!(queue.num_evicted(), 2);
+    }
+
+    #[test]
+    fn test_fee_density_ordering() {
+        let mut policy = policy(10, 1000, EvictionOrder::Age);
+        policy.ordering = TransactionOrder::FeeDensity;
+        let mut queue = TransactionsQueue::new(policy);
+        // The densities are 1, 3, 2, and 3 microcredits per byte.
+        for (id, fee, size) in [(0u32, 100, 100), (1, 300, 100), (2, 100, 50), (3, 600, 200)] {
+            assert!(queue.insert(id, id, false, size, fee));
+        }
+        let mut fees_per_byte = queue.fee_densities().map(|density| density.fee_per_byte()).collect::<Vec<_>>();
+        fees_per_byte.sort_unstable();
+        assert_eq!(fees_per_byte, vec![1, 2, 3, 3]);
+        // Equal densities are popped in the order they arrived.
+        assert_eq!((0..4).filter_map(|_| queue.pop(false)).collect::<Vec<_>>(), vec![1, 3, 2, 0]);
+        assert_eq!(queue.num_bytes(), 0);
+
+        assert!(FeeDensity::new(1, 0) == FeeDensity::new(1, 1));
+        assert_eq!(FeeDensity::new(7, 0).fee_per_byte(), 7);
+        assert_eq!(FeeDensity::new(7, 2).fee_per_byte(), 3);
+        assert!(FeeDensity::new(u64::MAX, 1) > FeeDensity::new(u64::MAX - 1, 1));
+    }
+
+    #[test]
+    fn test_expiry() {
+        let mut queue = TransactionsQueue::new(policy(10, 1000, EvictionOrder::Age));
+        assert!(queue.insert(0u32, 0u32, false, 10, 1));
+        assert!(queue.insert(1, 1, true, 10, 1));
+        queue.expire(Instant::now() + Duration::from_secs(61));
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.num_bytes(), 0);
+        assert_eq!(queue.num_expired(), 2);
+        assert_eq!(queue.pop(false), None);
+    }
+}
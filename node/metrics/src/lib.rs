@@ -19,10 +19,20 @@ pub use names::*;
 // Re-export the snarkVM metrics.
 pub use snarkvm::metrics::*;
 
-/// Initializes the metrics and returns a handle to the task running the metrics exporter.
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use std::sync::OnceLock;
+
+/// The handle to the Prometheus recorder, used to render the metrics on demand.
+static PROMETHEUS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Initializes the metrics and spawns the task running the metrics exporter.
 pub fn initialize_metrics() {
     // Build the Prometheus exporter.
-    metrics_exporter_prometheus::PrometheusBuilder::new().install().expect("can't build the prometheus exporter");
+    let (recorder, exporter) = PrometheusBuilder::new().build().expect("can't build the prometheus exporter");
+    // Keep a handle to the recorder, so other servers can render the metrics.
+    let _ = PROMETHEUS_HANDLE.set(recorder.handle());
+    metrics::set_global_recorder(recorder).expect("can't install the prometheus recorder");
+    tokio::spawn(exporter);
 
     // Register the snarkVM metrics.
     snarkvm::metrics::register_metrics();
@@ -38,3 +48,13 @@ pub fn initialize_metrics() {
         register_histogram(name);
     }
 }
+
+/// Increments the counter with the given name by the given value.
+pub fn increment_counter_by(name: &'static str, value: u64) {
+    metrics::counter!(name).increment(value);
+}
+
+/// Returns the metrics in the Prometheus text format, or `None` if the metrics are not initialized.
+pub fn render_metrics() -> Option<String> {
+    PROMETHEUS_HANDLE.get().map(PrometheusHandle::render)
+}
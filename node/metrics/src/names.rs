@@ -12,9 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) const COUNTER_NAMES: [&str; 1] = [bft::LEADERS_ELECTED];
+pub(super) const COUNTER_NAMES: [&str; 15] = [
+    bft::LEADERS_ELECTED,
+    prover::PUZZLE_ITERATIONS,
+    prover::SOLUTIONS_FOUND,
+    rest::RPC_FORWARDED,
+    rest::RPC_REQUESTS,
+    router::MESSAGES_RECEIVED,
+    router::MESSAGES_SENT,
+    storage::BLOCK_CACHE_HITS,
+    storage::BLOCK_CACHE_MISSES,
+    storage::HEADER_CACHE_HITS,
+    storage::HEADER_CACHE_MISSES,
+    storage::ORPHANED_BYTES_RECLAIMED,
+    storage::ORPHANED_TRANSMISSIONS_REMOVED,
+    storage::SOLUTION_CACHE_HITS,
+    storage::SOLUTION_CACHE_MISSES,
+];
 
-pub(super) const GAUGE_NAMES: [&str; 13] = [
+pub(super) const GAUGE_NAMES: [&str; 21] = [
     bft::CONNECTED,
     bft::CONNECTING,
     bft::LAST_STORED_ROUND,
@@ -24,16 +40,26 @@ pub(super) const GAUGE_NAMES: [&str; 13] = [
     blocks::TRANSACTIONS,
     consensus::COMMITTED_CERTIFICATES,
     consensus::LAST_COMMITTED_ROUND,
+    consensus::UNCONFIRMED_TRANSACTIONS,
+    prover::PROOF_RATE,
     router::CONNECTED,
     router::CANDIDATE,
     router::RESTRICTED,
+    storage::SIZE,
+    storage::FILES,
+    storage::TABLE_SIZE,
+    storage::TABLE_FILES,
+    storage::WAL_SIZE,
+    sync::IS_SYNCED,
     tcp::TCP_TASKS,
 ];
 
-pub(super) const HISTOGRAM_NAMES: [&str; 7] = [
+pub(super) const HISTOGRAM_NAMES: [&str; 9] = [
     bft::COMMIT_ROUNDS_LATENCY,
     consensus::CERTIFICATE_COMMIT_LATENCY,
     consensus::BLOCK_LATENCY,
+    rest::RPC_LATENCY,
+    storage::BLOCK_WRITE_LATENCY,
     tcp::NOISE_CODEC_ENCRYPTION_TIME,
     tcp::NOISE_CODEC_DECRYPTION_TIME,
     tcp::NOISE_CODEC_ENCRYPTION_SIZE,
@@ -60,12 +86,48 @@ pub mod consensus {
     pub const COMMITTED_CERTIFICATES: &str = "snarkos_consensus_committed_certificates_total";
     pub const LAST_COMMITTED_ROUND: &str = "snarkos_consensus_last_committed_round";
     pub const BLOCK_LATENCY: &str = "snarkos_consensus_block_latency_secs";
+    pub const UNCONFIRMED_TRANSACTIONS: &str = "snarkos_consensus_unconfirmed_transactions_total";
+}
+
+pub mod prover {
+    pub const PROOF_RATE: &str = "snarkos_prover_proofs_per_second";
+    pub const PUZZLE_ITERATIONS: &str = "snarkos_prover_puzzle_iterations_total";
+    pub const SOLUTIONS_FOUND: &str = "snarkos_prover_solutions_found_total";
+}
+
+pub mod rest {
+    pub const RPC_FORWARDED: &str = "snarkos_rest_rpc_forwarded_total";
+    pub const RPC_LATENCY: &str = "snarkos_rest_rpc_latency_secs";
+    pub const RPC_REQUESTS: &str = "snarkos_rest_rpc_requests_total";
 }
 
 pub mod router {
     pub const CONNECTED: &str = "snarkos_router_connected_total";
     pub const CANDIDATE: &str = "snarkos_router_candidate_total";
     pub const RESTRICTED: &str = "snarkos_router_restricted_total";
+    pub const MESSAGES_RECEIVED: &str = "snarkos_router_messages_received_total";
+    pub const MESSAGES_SENT: &str = "snarkos_router_messages_sent_total";
+}
+
+pub mod storage {
+    pub const BLOCK_CACHE_HITS: &str = "snarkos_storage_block_cache_hits_total";
+    pub const BLOCK_CACHE_MISSES: &str = "snarkos_storage_block_cache_misses_total";
+    pub const BLOCK_WRITE_LATENCY: &str = "snarkos_storage_block_write_latency_secs";
+    pub const FILES: &str = "snarkos_storage_files_total";
+    pub const HEADER_CACHE_HITS: &str = "snarkos_storage_header_cache_hits_total";
+    pub const HEADER_CACHE_MISSES: &str = "snarkos_storage_header_cache_misses_total";
+    pub const ORPHANED_BYTES_RECLAIMED: &str = "snarkos_storage_orphaned_bytes_reclaimed_total";
+    pub const ORPHANED_TRANSMISSIONS_REMOVED: &str = "snarkos_storage_orphaned_transmissions_removed_total";
+    pub const SIZE: &str = "snarkos_storage_size_bytes";
+    pub const SOLUTION_CACHE_HITS: &str = "snarkos_storage_solution_cache_hits_total";
+    pub const SOLUTION_CACHE_MISSES: &str = "snarkos_storage_solution_cache_misses_total";
+    pub const TABLE_FILES: &str = "snarkos_storage_table_files_total";
+    pub const TABLE_SIZE: &str = "snarkos_storage_table_size_bytes";
+    pub const WAL_SIZE: &str = "snarkos_storage_wal_size_bytes";
+}
+
+pub mod sync {
+    pub const IS_SYNCED: &str = "snarkos_sync_is_synced";
 }
 
 pub mod tcp {
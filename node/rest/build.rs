@@ -0,0 +1,27 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+fn main() {
+    // Generate the gRPC service from the protobuf schema, if the feature is enabled.
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/snarkos.proto");
+        // Use the vendored `protoc`, so that building does not require a system installation.
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().expect("Failed to locate protoc"));
+        tonic_build::configure()
+            .build_client(false)
+            .compile(&["proto/snarkos.proto"], &["proto"])
+            .expect("Failed to compile the gRPC protobuf schema");
+    }
+}
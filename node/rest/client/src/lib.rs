@@ -0,0 +1,228 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+
+mod types;
+pub use types::*;
+
+use anyhow::{anyhow, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use snarkvm::prelude::{
+    block::{Block, ConfirmedTransaction, Transaction},
+    Address,
+    Network,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A JSON-RPC 2.0 error object, as returned by the node.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RpcError {
+    /// The error code.
+    pub code: i64,
+    /// The error message.
+    pub message: String,
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPC error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// A JSON-RPC 2.0 response object.
+#[derive(Deserialize)]
+struct RpcResponse {
+    /// The result, on success.
+    result: Option<Value>,
+    /// The error, on failure.
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    /// Returns the result of the call, deserialized into the given type.
+    fn into_result<T: DeserializeOwned>(self) -> Result<T> {
+        match (self.result, self.error) {
+            (_, Some(error)) => Err(error.into()),
+            (Some(result), None) => Ok(serde_json::from_value(result)?),
+            (None, None) => Ok(serde_json::from_value(Value::Null)?),
+        }
+    }
+}
+
+/// A typed client for the JSON-RPC interface of a snarkOS node.
+///
+/// The control and debug methods are only served to authenticated clients; see [`RpcClient::with_bearer_token`].
+pub struct RpcClient<N: Network> {
+    /// The URL of the JSON-RPC endpoint.
+    url: String,
+    /// The bearer token, if the client is authenticated.
+    token: Option<String>,
+    /// The HTTP client.
+    client: reqwest::Client,
+    /// The ID of the next request.
+    next_id: AtomicU64,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> RpcClient<N> {
+    /// Initializes a new client for the node at the given base URL, such as `http://127.0.0.1:3030`.
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            url: format!("{}/mainnet/rpc", base_url.trim_end_matches('/')),
+            token: None,
+            client: reqwest::Client::new(),
+            next_id: AtomicU64::new(0),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Initializes a new client for the authenticated endpoint of the node at the given base URL.
+    pub fn with_bearer_token(base_url: &str, token: impl Into<String>) -> Self {
+        Self {
+            url: format!("{}/mainnet/node/rpc", base_url.trim_end_matches('/')),
+            token: Some(token.into()),
+            ..Self::new(base_url)
+        }
+    }
+
+    /// Calls the given method with the given parameters, and deserializes the result into the given type.
+    pub async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut request = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": id }));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        // The node returns a JSON-RPC response body for failed calls as well, so the status code is not checked.
+        let response = request.send().await.map_err(|e| anyhow!("Failed to call '{method}' - {e}"))?;
+        response.json::<RpcResponse>().await.map_err(|e| anyhow!("Invalid response to '{method}' - {e}"))?.into_result()
+    }
+
+    /// Returns the OpenRPC document describing the methods enabled on the node.
+    pub async fn discover(&self) -> Result<Value> {
+        self.call("rpc.discover", json!([])).await
+    }
+
+    /// Returns the status of the node.
+    pub async fn get_status(&self) -> Result<NodeStatus<N>> {
+        self.call("getstatus", json!([])).await
+    }
+
+    /// Returns the latest block height.
+    pub async fn latest_height(&self) -> Result<u32> {
+        self.call("latestheight", json!([])).await
+    }
+
+    /// Returns the latest block hash.
+    pub async fn latest_hash(&self) -> Result<N::BlockHash> {
+        self.call("latesthash", json!([])).await
+    }
+
+    /// Returns the latest block.
+    pub async fn latest_block(&self) -> Result<Block<N>> {
+        self.call("latestblock", json!([])).await
+    }
+
+    /// Returns the block at the given height.
+    pub async fn get_block(&self, height: u32) -> Result<Block<N>> {
+        self.call("getblock", json!([height])).await
+    }
+
+    /// Returns the block with the given hash.
+    pub async fn get_block_by_hash(&self, hash: &N::BlockHash) -> Result<Block<N>> {
+        self.call("getblock", json!([hash])).await
+    }
+
+    /// Returns the blocks in the given range, where `end` is exclusive.
+    pub async fn get_blocks(&self, start: u32, end: u32) -> Result<Vec<Block<N>>> {
+        self.call("getblocks", json!([start, end])).await
+    }
+
+    /// Returns the confirmed transaction with the given ID.
+    pub async fn get_confirmed_transaction(&self, id: &N::TransactionID) -> Result<ConfirmedTransaction<N>> {
+        self.call("getconfirmedtransaction", json!([id])).await
+    }
+
+    /// Returns a summary of the memory pool.
+    pub async fn get_memory_pool_info(&self) -> Result<MemoryPoolInfo> {
+        self.call("getmemorypoolinfo", json!([])).await
+    }
+
+    /// Returns a page of the IDs of the transactions that publicly reference the given address.
+    pub async fn get_transactions_by_address(
+        &self,
+        address: Address<N>,
+        cursor: usize,
+        limit: Option<usize>,
+    ) -> Result<AddressTransactionsPage<N>> {
+        let params = AddressTransactionsParams { address, cursor, limit };
+        self.call("gettransactionsbyaddress", serde_json::to_value(params)?).await
+    }
+
+    /// Runs the memory pool acceptance checks on the given transaction, without broadcasting it.
+    pub async fn validate_raw_transaction(&self, transaction: &Transaction<N>) -> Result<TransactionValidation<N>> {
+        self.call("validaterawtransaction", json!([transaction])).await
+    }
+
+    /// Estimates the fee for a transaction of the given size to be included within the given number of blocks.
+    pub async fn estimate_fee(&self, blocks: Option<u32>, size: Option<u64>) -> Result<FeeEstimate> {
+        self.call("estimatefee", json!([blocks, size])).await
+    }
+
+    /// Waits until a new block is added to the ledger, or the timeout (in seconds) elapses.
+    pub async fn wait_for_new_block(&self, timeout: Option<u64>) -> Result<BlockTip<N>> {
+        self.call("waitfornewblock", json!([timeout])).await
+    }
+
+    /// Waits until the ledger reaches the given height, or the timeout (in seconds) elapses.
+    pub async fn wait_for_height(&self, height: u32, timeout: Option<u64>) -> Result<BlockTip<N>> {
+        self.call("waitforheight", json!([height, timeout])).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_response() {
+        // A successful response.
+        let response: RpcResponse = serde_json::from_value(json!({ "jsonrpc": "2.0", "result": 7, "id": 0 })).unwrap();
+        assert_eq!(response.into_result::<u32>().unwrap(), 7);
+
+        // A null result.
+        let response: RpcResponse =
+            serde_json::from_value(json!({ "jsonrpc": "2.0", "result": null, "id": 0 })).unwrap();
+        assert!(response.into_result::<()>().is_ok());
+
+        // An error response.
+        let response: RpcResponse = serde_json::from_value(
+            json!({ "jsonrpc": "2.0", "error": { "code": -32601, "message": "Method 'x' not found" }, "id": 0 }),
+        )
+        .unwrap();
+        let error = response.into_result::<u32>().unwrap_err().downcast::<RpcError>().unwrap();
+        assert_eq!(error.code, -32601);
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Address, Network};
+use std::net::SocketAddr;
+
+/// The sync progress of a node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SyncProgress {
+    /// The block height at which the current sync session started.
+    pub start_height: u32,
+    /// The current block height of the ledger.
+    pub current_height: u32,
+    /// The greatest block height advertised by the sync peers.
+    pub target_height: u32,
+    /// The average number of blocks advanced per second, since the sync session started.
+    pub blocks_per_sec: f64,
+    /// The estimated number of seconds remaining, if it can be computed.
+    pub eta_secs: Option<u64>,
+    /// The peers currently being synced from.
+    pub sync_peers: Vec<SocketAddr>,
+    /// Whether the node is synced up to the target height.
+    pub is_synced: bool,
+}
+
+/// The result of `getstatus`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound = "")]
+pub struct NodeStatus<N: Network> {
+    /// The type of the node.
+    pub node_type: String,
+    /// The latest block height.
+    pub height: u32,
+    /// The latest block hash.
+    pub hash: N::BlockHash,
+    /// Whether the node is synced.
+    pub is_synced: bool,
+    /// The number of connected peers.
+    pub num_peers: usize,
+    /// The sync progress, if a sync session has started.
+    pub sync_progress: Option<SyncProgress>,
+}
+
+/// The result of `waitfornewblock` and `waitforheight`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound = "")]
+pub struct BlockTip<N: Network> {
+    /// The latest block height.
+    pub height: u32,
+    /// The latest block hash.
+    pub hash: N::BlockHash,
+    /// Whether the timeout expired before the condition was met.
+    pub timed_out: bool,
+}
+
+/// The result of `estimatefee`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FeeEstimate {
+    /// The number of blocks within which the transaction should be included.
+    pub target_blocks: u32,
+    /// The suggested fee per byte, in microcredits.
+    pub fee_per_byte: u64,
+    /// The suggested total fee for a transaction of the requested size, in microcredits.
+    pub fee: Option<u64>,
+    /// The number of confirmed transactions sampled from recent blocks.
+    pub sampled_transactions: usize,
+    /// The number of transactions in the memory pool ahead of the suggested fee.
+    pub memory_pool_ahead: usize,
+}
+
+/// The result of `getmemorypoolinfo`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryPoolInfo {
+    /// The number of transactions, including the queued transactions.
+    pub num_transactions: usize,
+    /// The number of deployment transactions.
+    pub num_deployments: usize,
+    /// The number of execution transactions.
+    pub num_executions: usize,
+    /// The number of transactions waiting in the queue to enter the memory pool.
+    pub num_queued_transactions: usize,
+    /// The total size of the transactions waiting in the queue, in bytes.
+    #[serde(default)]
+    pub queued_transaction_bytes: usize,
+    /// The number of transactions that expired from the queue.
+    #[serde(default)]
+    pub num_expired_transactions: u64,
+    /// The number of transactions evicted from the queue, once it was full.
+    #[serde(default)]
+    pub num_evicted_transactions: u64,
+    /// The number of solutions in the memory pool.
+    pub num_solutions: usize,
+    /// The number of solutions waiting in the queue to enter the memory pool.
+    pub num_queued_solutions: usize,
+    /// The sum of the transaction fees, in microcredits.
+    pub total_fees: u64,
+    /// The lowest transaction fee, in microcredits.
+    pub min_fee: Option<u64>,
+    /// The highest transaction fee, in microcredits.
+    pub max_fee: Option<u64>,
+    /// The retention and expiry policy of the queue, if the node reports it.
+    #[serde(default)]
+    pub policy: Option<MemoryPoolPolicy>,
+}
+
+/// The retention and expiry policy of the memory pool queue.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MemoryPoolPolicy {
+    /// The maximum number of queued transactions.
+    pub max_transactions: usize,
+    /// The maximum total size of the queued transactions, in bytes.
+    pub max_bytes: usize,
+    /// The number of seconds a transaction may be queued for, before it expires.
+    pub max_age_secs: u64,
+    /// The order in which queued transactions are evicted [options: age, fee].
+    pub eviction: String,
+    /// The order in which queued transactions enter the memory pool [options: arrival, fee-density].
+    #[serde(default)]
+    pub ordering: String,
+}
+
+/// The outcome of a single acceptance check.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidationCheck {
+    /// The name of the check.
+    pub check: String,
+    /// Whether the transaction passed the check.
+    pub passed: bool,
+    /// The reason the transaction failed the check, if it failed.
+    pub reason: Option<String>,
+}
+
+/// The result of `validaterawtransaction`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound = "")]
+pub struct TransactionValidation<N: Network> {
+    /// The transaction ID.
+    pub transaction_id: N::TransactionID,
+    /// Whether the transaction passed every check, and would be accepted into the memory pool.
+    pub is_valid: bool,
+    /// The outcome of each check, in the order they were run.
+    pub checks: Vec<ValidationCheck>,
+}
+
+/// The result of `gettransactionsbyaddress`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound = "")]
+pub struct AddressTransactionsPage<N: Network> {
+    /// The IDs of the transactions, in ledger order.
+    pub transaction_ids: Vec<N::TransactionID>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub next_cursor: Option<usize>,
+    /// The latest block height covered by the index.
+    pub indexed_height: Option<u32>,
+}
+
+/// The query of `gettransactionsbyaddress`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct AddressTransactionsParams<N: Network> {
+    /// The address.
+    pub(crate) address: Address<N>,
+    /// The index of the first transaction in the page.
+    pub(crate) cursor: usize,
+    /// The maximum number of transactions in the page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) limit: Option<usize>,
+}
@@ -0,0 +1,239 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use indexmap::IndexSet;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{
+    block::{Input, Output, Transaction},
+    Address,
+    Field,
+    Literal,
+    Owner,
+    Plaintext,
+};
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// The maximum number of transaction IDs (or record commitments) returned per page.
+pub const MAX_ADDRESS_TRANSACTIONS_PER_PAGE: usize = 100;
+
+/// An in-memory index of the transactions that reference each address publicly,
+/// either as a public input or output of a transition, or as the owner of a public record,
+/// and of the commitments of the records that each address owns publicly.
+pub(crate) struct AddressIndex<N: Network> {
+    /// The map of addresses to the IDs of the transactions that reference them, in ledger order.
+    transactions: RwLock<HashMap<Address<N>, Vec<N::TransactionID>>>,
+    /// The map of addresses to the commitments of their public records, in ledger order.
+    commitments: RwLock<HashMap<Address<N>, Vec<Field<N>>>>,
+    /// The next block height to index.
+    next_height: AtomicU32,
+}
+
+impl<N: Network> AddressIndex<N> {
+    /// Initializes a new, empty address index.
+    pub(crate) fn new() -> Self {
+        Self { transactions: Default::default(), commitments: Default::default(), next_height: AtomicU32::new(0) }
+    }
+
+    /// Returns the transaction IDs for the given address starting at the given cursor,
+    /// along with the cursor for the next page, if there is one.
+    fn get_transactions(
+        &self,
+        address: &Address<N>,
+        cursor: usize,
+        limit: usize,
+    ) -> (Vec<N::TransactionID>, Option<usize>) {
+        page(self.transactions.read().get(address), cursor, limit)
+    }
+
+    /// Returns the record commitments for the given address starting at the given cursor,
+    /// along with the cursor for the next page, if there is one.
+    fn get_commitments(&self, address: &Address<N>, cursor: usize, limit: usize) -> (Vec<Field<N>>, Option<usize>) {
+        page(self.commitments.read().get(address), cursor, limit)
+    }
+}
+
+/// Returns the page of the given items starting at the given cursor,
+/// along with the cursor for the next page, if there is one.
+fn page<T: Clone>(items: Option<&Vec<T>>, cursor: usize, limit: usize) -> (Vec<T>, Option<usize>) {
+    let Some(items) = items else {
+        return (Vec::new(), None);
+    };
+    let page_end = cursor.saturating_add(limit).min(items.len());
+    let page = items.get(cursor.min(page_end)..page_end).unwrap_or_default().to_vec();
+    (page, (page_end < items.len()).then_some(page_end))
+}
+
+impl<N: Network> BlockIndex<N> for AddressIndex<N> {
+    const NAME: &'static str = "address index";
+
+    fn next_height(&self) -> u32 {
+        self.next_height.load(Ordering::SeqCst)
+    }
+
+    fn index_block(&self, block: &Block<N>) -> Result<()> {
+        let mut transactions = self.transactions.write();
+        let mut commitments = self.commitments.write();
+        for confirmed in block.transactions().iter() {
+            // Record the transaction once for each address.
+            for address in transaction_addresses(confirmed.transaction()) {
+                transactions.entry(address).or_default().push(confirmed.id());
+            }
+            // Record the commitment of each public record under its owner.
+            for transition in confirmed.transaction().transitions() {
+                for output in transition.outputs() {
+                    if let Output::Record(commitment, _, Some(record)) = output {
+                        if let Owner::Public(address) = record.owner() {
+                            commitments.entry(*address).or_default().push(*commitment);
+                        }
+                    }
+                }
+            }
+        }
+        self.next_height.store(block.height().saturating_add(1), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Returns the addresses that the given transaction references publicly.
+pub(crate) fn transaction_addresses<N: Network>(transaction: &Transaction<N>) -> IndexSet<Address<N>> {
+    let mut addresses = IndexSet::new();
+    for transition in transaction.transitions() {
+        for input in transition.inputs() {
+            if let Input::Public(_, Some(plaintext)) = input {
+                collect_addresses(plaintext, &mut addresses);
+            }
+        }
+        for output in transition.outputs() {
+            match output {
+                Output::Public(_, Some(plaintext)) => collect_addresses(plaintext, &mut addresses),
+                Output::Record(_, _, Some(record)) => {
+                    if let Owner::Public(address) = record.owner() {
+                        addresses.insert(*address);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    addresses
+}
+
+/// Appends the addresses contained in the given plaintext to `addresses`.
+fn collect_addresses<N: Network>(plaintext: &Plaintext<N>, addresses: &mut IndexSet<Address<N>>) {
+    match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => {
+            addresses.insert(*address);
+        }
+        Plaintext::Literal(..) => (),
+        Plaintext::Struct(members, _) => members.values().for_each(|member| collect_addresses(member, addresses)),
+        Plaintext::Array(elements, _) => elements.iter().for_each(|element| collect_addresses(element, addresses)),
+    }
+}
+
+/// The `get_transactions_by_address` and `get_commitments_by_address` query object.
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct AddressTransactionsQuery {
+    /// The index of the first transaction in the page, as returned by the previous page.
+    #[serde(default)]
+    cursor: usize,
+    /// The maximum number of transactions in the page.
+    limit: Option<usize>,
+}
+
+/// A page of transaction IDs for an address.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct AddressTransactionsPage<N: Network> {
+    /// The IDs of the transactions, in ledger order.
+    transaction_ids: Vec<N::TransactionID>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    next_cursor: Option<usize>,
+    /// The latest block height covered by the index.
+    indexed_height: Option<u32>,
+}
+
+/// A page of record commitments for an address.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct AddressCommitmentsPage<N: Network> {
+    /// The commitments of the public records owned by the address, in ledger order.
+    commitments: Vec<Field<N>>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    next_cursor: Option<usize>,
+    /// The latest block height covered by the index.
+    indexed_height: Option<u32>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/address/{address}/transactions?cursor={index}&limit={limit}
+    pub(crate) async fn get_transactions_by_address(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<AddressTransactionsQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.transactions_by_address(&address, &query)?))
+    }
+
+    // GET /mainnet/address/{address}/commitments?cursor={index}&limit={limit}
+    pub(crate) async fn get_commitments_by_address(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<AddressTransactionsQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.commitments_by_address(&address, &query)?))
+    }
+
+    /// Returns the page of transaction IDs for the given address.
+    pub(crate) fn transactions_by_address(
+        &self,
+        address: &Address<N>,
+        query: &AddressTransactionsQuery,
+    ) -> Result<AddressTransactionsPage<N>, RestError> {
+        let (index, limit) = self.address_index_query(query)?;
+        let (transaction_ids, next_cursor) = index.get_transactions(address, query.cursor, limit);
+        Ok(AddressTransactionsPage { transaction_ids, next_cursor, indexed_height: index.next_height().checked_sub(1) })
+    }
+
+    /// Returns the page of record commitments for the given address.
+    pub(crate) fn commitments_by_address(
+        &self,
+        address: &Address<N>,
+        query: &AddressTransactionsQuery,
+    ) -> Result<AddressCommitmentsPage<N>, RestError> {
+        let (index, limit) = self.address_index_query(query)?;
+        let (commitments, next_cursor) = index.get_commitments(address, query.cursor, limit);
+        Ok(AddressCommitmentsPage { commitments, next_cursor, indexed_height: index.next_height().checked_sub(1) })
+    }
+
+    /// Returns the address index and the page size of the given query, if the index is enabled and the size is valid.
+    fn address_index_query(&self, query: &AddressTransactionsQuery) -> Result<(&AddressIndex<N>, usize), RestError> {
+        let Some(index) = &self.address_index else {
+            return Err(RestError("The address index is not enabled on this node".to_string()));
+        };
+        // Ensure the page size is bounded.
+        let limit = query.limit.unwrap_or(MAX_ADDRESS_TRANSACTIONS_PER_PAGE);
+        if limit == 0 || limit > MAX_ADDRESS_TRANSACTIONS_PER_PAGE {
+            return Err(RestError(format!(
+                "The page size must be between 1 and {MAX_ADDRESS_TRANSACTIONS_PER_PAGE} (requested {limit})"
+            )));
+        }
+        Ok((index, limit))
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{block::Ratify, ToBytes};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks aggregated per call.
+pub const MAX_BLOCK_STATS_RANGE: u32 = 1_000;
+
+/// The `get_block_stats_range` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BlockStatsRange {
+    /// The starting block height (inclusive).
+    start: u32,
+    /// The ending block height (exclusive).
+    end: u32,
+}
+
+/// The statistics of a single block.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct BlockStats<N: Network> {
+    /// The block height.
+    height: u32,
+    /// The block hash.
+    hash: N::BlockHash,
+    /// The block timestamp.
+    timestamp: i64,
+    /// The size of the block, in bytes.
+    size_in_bytes: usize,
+    /// The number of transactions.
+    num_transactions: usize,
+    /// The number of accepted transactions.
+    num_accepted: usize,
+    /// The number of rejected transactions.
+    num_rejected: usize,
+    /// The sum of the transaction fees, in microcredits.
+    total_fees: u64,
+    /// The proof target.
+    proof_target: u64,
+    /// The coinbase target.
+    coinbase_target: u64,
+    /// The block reward, in microcredits.
+    block_reward: u64,
+    /// The puzzle reward, in microcredits.
+    puzzle_reward: u64,
+    /// The time it took this node to check and add the block, if it was recently added through block sync.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation_time_ms: Option<u64>,
+}
+
+/// The statistics of a range of blocks.
+#[derive(Default, Serialize)]
+pub(crate) struct BlockStatsSummary {
+    /// The starting block height (inclusive).
+    start: u32,
+    /// The ending block height (exclusive).
+    end: u32,
+    /// The number of blocks.
+    num_blocks: usize,
+    /// The total size of the blocks, in bytes.
+    total_size_in_bytes: usize,
+    /// The total number of transactions.
+    total_transactions: usize,
+    /// The total number of rejected transactions.
+    total_rejected: usize,
+    /// The sum of the transaction fees, in microcredits.
+    total_fees: u64,
+    /// The sum of the block rewards, in microcredits.
+    total_block_reward: u64,
+    /// The sum of the puzzle rewards, in microcredits.
+    total_puzzle_reward: u64,
+    /// The average size of a block, in bytes.
+    avg_size_in_bytes: f64,
+    /// The average number of transactions per block.
+    avg_transactions: f64,
+    /// The lowest proof target.
+    min_proof_target: u64,
+    /// The highest proof target.
+    max_proof_target: u64,
+    /// The average validation time, over the blocks with a known validation time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_validation_time_ms: Option<f64>,
+}
+
+impl<N: Network> BlockStats<N> {
+    /// Computes the statistics of the given block.
+    fn new(block: &Block<N>, validation_time_ms: Option<u64>) -> Result<Self> {
+        let (mut block_reward, mut puzzle_reward) = (0, 0);
+        for ratification in block.ratifications().iter() {
+            match ratification {
+                Ratify::BlockReward(amount) => block_reward += amount,
+                Ratify::PuzzleReward(amount) => puzzle_reward += amount,
+                _ => (),
+            }
+        }
+        let total_fees = block
+            .transactions()
+            .iter()
+            .map(|confirmed| confirmed.transaction().fee_amount().map(|fee| *fee))
+            .sum::<Result<u64>>()?;
+        Ok(Self {
+            height: block.height(),
+            hash: block.hash(),
+            timestamp: block.timestamp(),
+            size_in_bytes: block.to_bytes_le()?.len(),
+            num_transactions: block.transactions().len(),
+            num_accepted: block.transactions().num_accepted(),
+            num_rejected: block.transactions().num_rejected(),
+            total_fees,
+            proof_target: block.proof_target(),
+            coinbase_target: block.coinbase_target(),
+            block_reward,
+            puzzle_reward,
+            validation_time_ms,
+        })
+    }
+}
+
+impl BlockStatsSummary {
+    /// Aggregates the statistics of the given blocks.
+    fn new<N: Network>(start: u32, end: u32, stats: &[BlockStats<N>]) -> Self {
+        let mut summary = Self { start, end, num_blocks: stats.len(), ..Default::default() };
+        let mut validation_times = Vec::new();
+        for block in stats {
+            summary.total_size_in_bytes += block.size_in_bytes;
+            summary.total_transactions += block.num_transactions;
+            summary.total_rejected += block.num_rejected;
+            summary.total_fees += block.total_fees;
+            summary.total_block_reward += block.block_reward;
+            summary.total_puzzle_reward += block.puzzle_reward;
+            validation_times.extend(block.validation_time_ms);
+        }
+        if !stats.is_empty() {
+            summary.avg_size_in_bytes = summary.total_size_in_bytes as f64 / stats.len() as f64;
+            summary.avg_transactions = summary.total_transactions as f64 / stats.len() as f64;
+        }
+        summary.min_proof_target = stats.iter().map(|block| block.proof_target).min().unwrap_or_default();
+        summary.max_proof_target = stats.iter().map(|block| block.proof_target).max().unwrap_or_default();
+        if !validation_times.is_empty() {
+            let total = validation_times.iter().sum::<u64>();
+            summary.avg_validation_time_ms = Some(total as f64 / validation_times.len() as f64);
+        }
+        summary
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/block/{height}/stats
+    pub(crate) async fn get_block_stats(
+        State(rest): State<Self>,
+        Path(height): Path<u32>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.block_stats(height)?))
+    }
+
+    // GET /mainnet/blocks/stats?start={start_height}&end={end_height}
+    pub(crate) async fn get_block_stats_range(
+        State(rest): State<Self>,
+        Query(range): Query<BlockStatsRange>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.block_stats_summary(range.start, range.end).await?))
+    }
+
+    /// Returns the statistics of the block at the given height.
+    pub(crate) fn block_stats(&self, height: u32) -> Result<BlockStats<N>, RestError> {
+        let validation_time = self.sync.validation_time(height).map(|time| time.as_millis() as u64);
+        Ok(BlockStats::new(&self.ledger.get_block(height)?, validation_time)?)
+    }
+
+    /// Returns the aggregated statistics of the blocks in the given range.
+    pub(crate) async fn block_stats_summary(&self, start: u32, end: u32) -> Result<BlockStatsSummary, RestError> {
+        // Ensure the block range is valid and bounded.
+        if start > end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+        if end - start > MAX_BLOCK_STATS_RANGE {
+            return Err(RestError(format!(
+                "Cannot aggregate more than {MAX_BLOCK_STATS_RANGE} blocks per call (requested {})",
+                end - start
+            )));
+        }
+        // Bound the range by the latest height.
+        let end = end.min(self.ledger.latest_height().saturating_add(1));
+
+        // Load the blocks off the async runtime.
+        let rest = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let stats = cfg_into_iter!((start.min(end)..end))
+                .map(|height| rest.block_stats(height))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, RestError>(BlockStatsSummary::new(start, end, &stats))
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to aggregate the block statistics - {e}")))?
+    }
+}
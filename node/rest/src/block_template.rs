@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::block::Transaction;
+
+use serde::Serialize;
+
+/// The maximum number of candidate transactions included in a block template.
+pub const MAX_TEMPLATE_TRANSACTIONS: usize = 100;
+
+/// The coinbase puzzle state that solutions for the next block must be produced against.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct CoinbaseChallenge<N: Network> {
+    /// The epoch number.
+    epoch_number: u32,
+    /// The hash of the block that starts the epoch.
+    epoch_block_hash: N::BlockHash,
+    /// The degree of the epoch polynomial.
+    degree: u32,
+}
+
+/// The work needed to construct the next block.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct BlockTemplate<N: Network> {
+    /// The height of the next block.
+    height: u32,
+    /// The hash of the latest block.
+    previous_hash: N::BlockHash,
+    /// The coinbase challenge of the current epoch.
+    challenge: CoinbaseChallenge<N>,
+    /// The target that the combined proof of the coinbase must meet.
+    coinbase_target: u64,
+    /// The target that each individual solution must meet.
+    proof_target: u64,
+    /// The candidate transactions from the memory pool, ordered by fee per byte (highest first).
+    /// This is empty on nodes that do not run consensus.
+    transactions: Vec<Transaction<N>>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/block/template
+    pub(crate) async fn get_block_template(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.block_template().await?))
+    }
+
+    /// Returns the coinbase challenge, targets, and candidate transactions for the next block.
+    pub(crate) async fn block_template(&self) -> Result<BlockTemplate<N>, RestError> {
+        let latest_block = self.ledger.latest_block();
+        let epoch_challenge = self.ledger.latest_epoch_challenge()?;
+
+        // Select the candidate transactions, updating the cached ones with the memory pool, off the async runtime.
+        let transactions = match self.consensus.clone() {
+            Some(consensus) => {
+                let template_cache = self.template_cache.clone();
+                tokio::task::spawn_blocking(move || template_cache.candidates(consensus.unconfirmed_transactions()))
+                    .await
+                    .map_err(|e| RestError(format!("Failed to select the block template transactions - {e}")))?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(BlockTemplate {
+            height: latest_block.height().saturating_add(1),
+            previous_hash: latest_block.hash(),
+            challenge: CoinbaseChallenge {
+                epoch_number: epoch_challenge.epoch_number(),
+                epoch_block_hash: epoch_challenge.epoch_block_hash(),
+                degree: epoch_challenge.degree(),
+            },
+            coinbase_target: latest_block.coinbase_target(),
+            proof_target: latest_block.proof_target(),
+            transactions,
+        })
+    }
+}
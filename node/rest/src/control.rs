@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Serialize;
+use std::net::IpAddr;
+
+/// A banned IP.
+#[derive(Debug, Serialize)]
+pub(crate) struct BannedIp {
+    /// The IP address.
+    ip: IpAddr,
+    /// The number of seconds until the ban expires, or `None` if the ban is permanent.
+    expires_in_secs: Option<u64>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Connects to the given peer, returning `true` if the connection succeeded.
+    pub(crate) async fn add_node(&self, peer_ip: SocketAddr) -> Result<bool, RestError> {
+        let Some(handle) = self.routing.router().connect(peer_ip) else {
+            return Err(RestError(format!("Not allowed to connect to '{peer_ip}'")));
+        };
+        handle.await.map_err(|e| RestError(format!("Failed to connect to '{peer_ip}' - {e}")))
+    }
+
+    /// Disconnects from the given peer, returning `true` if the peer was disconnected.
+    pub(crate) async fn disconnect_node(&self, peer_ip: SocketAddr) -> Result<bool, RestError> {
+        let router = self.routing.router();
+        if !router.is_connected(&peer_ip) {
+            return Err(RestError(format!("Peer '{peer_ip}' is not connected")));
+        }
+        router.disconnect(peer_ip).await.map_err(|e| RestError(format!("Failed to disconnect from '{peer_ip}' - {e}")))
+    }
+
+    /// Bans the given IP for the given number of seconds, or permanently if no duration is given.
+    pub(crate) fn ban_ip(&self, ip: IpAddr, duration_secs: Option<u64>) {
+        info!("Banning '{ip}' ({})", match duration_secs {
+            Some(duration_secs) => format!("for {duration_secs}s"),
+            None => "permanently".to_string(),
+        });
+        self.routing.router().ban_ip(ip, duration_secs.map(Duration::from_secs));
+    }
+
+    /// Removes the ban on the given IP.
+    pub(crate) fn unban_ip(&self, ip: &IpAddr) -> Result<(), RestError> {
+        match self.routing.router().unban_ip(ip) {
+            true => Ok(()),
+            false => Err(RestError(format!("'{ip}' is not banned"))),
+        }
+    }
+
+    /// Returns the banned IPs.
+    pub(crate) fn banned_ips(&self) -> Vec<BannedIp> {
+        self.routing
+            .router()
+            .banned_ips()
+            .into_iter()
+            .map(|(ip, remaining)| BannedIp { ip, expires_in_secs: remaining.map(|remaining| remaining.as_secs()) })
+            .collect()
+    }
+}
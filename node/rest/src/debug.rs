@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Serialize;
+
+/// The number of running and finished tasks of a component.
+#[derive(Debug, Serialize)]
+pub(crate) struct TaskCounts {
+    /// The number of running tasks.
+    running: usize,
+    /// The number of finished tasks.
+    finished: usize,
+}
+
+impl TaskCounts {
+    /// Counts the running and finished tasks among the given handles.
+    fn new(handles: &[JoinHandle<()>]) -> Self {
+        let finished = handles.iter().filter(|handle| handle.is_finished()).count();
+        Self { running: handles.len() - finished, finished }
+    }
+}
+
+/// A snapshot of the long-running tasks of the node.
+#[derive(Debug, Serialize)]
+pub(crate) struct TaskStats {
+    /// The tasks spawned by the REST server.
+    rest: TaskCounts,
+    /// The tasks spawned by the router.
+    router: TaskCounts,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/node/debug/peers
+    pub(crate) async fn get_debug_peers(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().debug_state())
+    }
+
+    // GET /mainnet/node/debug/sync
+    pub(crate) async fn get_debug_sync(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.sync.debug_state())
+    }
+
+    // GET /mainnet/node/debug/tasks
+    pub(crate) async fn get_debug_tasks(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.task_stats())
+    }
+
+    /// Returns a snapshot of the tasks of the node.
+    pub(crate) fn task_stats(&self) -> TaskStats {
+        let (running, finished) = self.routing.router().task_counts();
+        TaskStats { rest: TaskCounts::new(&self.handles.lock()), router: TaskCounts { running, finished } }
+    }
+}
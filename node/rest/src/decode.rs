@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::routes::DecodedTransition;
+use snarkvm::prelude::{block::Transaction, FromBytes, ToBytes};
+
+use axum::body::Bytes;
+use serde::Serialize;
+
+/// The fee of a decoded transaction.
+#[derive(Serialize)]
+pub(crate) struct DecodedFee {
+    /// The fee amount, in microcredits.
+    amount: u64,
+    /// Whether the fee is paid from public balance.
+    is_public: bool,
+    /// The size of the fee proof in bytes, if the fee is proven.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_size_in_bytes: Option<usize>,
+    /// The global state root that the fee was proven against.
+    global_state_root: String,
+}
+
+/// The deployment of a decoded transaction.
+#[derive(Serialize)]
+pub(crate) struct DecodedDeployment {
+    /// The deployed program ID.
+    program_id: String,
+    /// The program edition.
+    edition: u16,
+    /// The number of function verifying keys.
+    num_verifying_keys: usize,
+}
+
+/// The execution of a decoded transaction.
+#[derive(Serialize)]
+pub(crate) struct DecodedExecution {
+    /// The size of the execution proof in bytes, if the execution is proven.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_size_in_bytes: Option<usize>,
+    /// The global state root that the execution was proven against.
+    global_state_root: String,
+}
+
+/// A transaction decoded from its byte representation.
+#[derive(Serialize)]
+pub(crate) struct DecodedTransaction {
+    /// The transaction ID.
+    id: String,
+    /// The transaction type.
+    #[serde(rename = "type")]
+    type_: &'static str,
+    /// The size of the transaction, in bytes.
+    size_in_bytes: usize,
+    /// The decoded transitions, including the fee transition.
+    transitions: Vec<DecodedTransition>,
+    /// The fee, if the transaction has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee: Option<DecodedFee>,
+    /// The deployment, if this is a deployment transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deployment: Option<DecodedDeployment>,
+    /// The execution, if this is an execution transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    execution: Option<DecodedExecution>,
+}
+
+/// Returns the size of the given proof in bytes.
+fn proof_size(proof: &impl ToBytes) -> Option<usize> {
+    proof.to_bytes_le().ok().map(|bytes| bytes.len())
+}
+
+impl DecodedTransaction {
+    /// Decodes the given transaction bytes, which may also be given as a hex string.
+    pub(crate) fn new<N: Network>(bytes: &[u8]) -> Result<Self, RestError> {
+        // Decode the hex string, if the bytes are one.
+        let hex_bytes = std::str::from_utf8(bytes).ok().and_then(|text| hex::decode(text.trim()).ok());
+        let bytes = hex_bytes.as_deref().unwrap_or(bytes);
+        let transaction = Transaction::<N>::from_bytes_le(bytes)
+            .map_err(|e| RestError(format!("Failed to decode the transaction - {e}")))?;
+
+        let type_ = match &transaction {
+            Transaction::Deploy(..) => "deploy",
+            Transaction::Execute(..) => "execute",
+            Transaction::Fee(..) => "fee",
+        };
+        let fee = match transaction.fee_transition() {
+            Some(fee) => Some(DecodedFee {
+                amount: *transaction.fee_amount()?,
+                is_public: fee.is_fee_public(),
+                proof_size_in_bytes: fee.proof().and_then(proof_size),
+                global_state_root: fee.global_state_root().to_string(),
+            }),
+            None => None,
+        };
+        let deployment = transaction.deployment().map(|deployment| DecodedDeployment {
+            program_id: deployment.program_id().to_string(),
+            edition: deployment.edition(),
+            num_verifying_keys: deployment.verifying_keys().len(),
+        });
+        let execution = transaction.execution().map(|execution| DecodedExecution {
+            proof_size_in_bytes: execution.proof().and_then(proof_size),
+            global_state_root: execution.global_state_root().to_string(),
+        });
+
+        Ok(Self {
+            id: transaction.id().to_string(),
+            type_,
+            size_in_bytes: bytes.len(),
+            transitions: transaction.transitions().map(DecodedTransition::new).collect(),
+            fee,
+            deployment,
+            execution,
+        })
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /mainnet/transaction/decode
+    pub(crate) async fn transaction_decode(body: Bytes) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(DecodedTransaction::new::<N>(&body)?))
+    }
+}
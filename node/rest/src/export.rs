@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::ledger_range::{blocks, headers};
+
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+
+/// The maximum number of blocks streamed per export.
+pub const MAX_EXPORT_RANGE: u32 = 100_000;
+/// The number of serialized blocks buffered per export, which bounds the memory held for a slow client.
+const EXPORT_CHANNEL_CAPACITY: usize = 16;
+
+/// The `get_blocks_export` query object.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub(crate) struct BlockExportQuery {
+    /// The starting block height (inclusive).
+    pub(crate) start: u32,
+    /// The ending block height (exclusive).
+    pub(crate) end: u32,
+    /// Whether to only export the block headers.
+    #[serde(default)]
+    pub(crate) headers_only: bool,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/blocks/export?start={start_height}&end={end_height}&headers_only={bool}
+    pub(crate) async fn get_blocks_export(
+        State(rest): State<Self>,
+        Query(query): Query<BlockExportQuery>,
+    ) -> Result<Response, RestError> {
+        // Stream the blocks as newline-delimited JSON, so the response starts before the export completes.
+        let lines = ReceiverStream::new(rest.export_blocks(query)?).map(|line| line.map(|line| line + "\n"));
+        Ok(([(CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(lines)).into_response())
+    }
+
+    /// Starts an export of the blocks (or block headers) in the given range, returning the receiver
+    /// of the serialized blocks. The export stops early if the receiver is dropped.
+    pub(crate) fn export_blocks(&self, query: BlockExportQuery) -> Result<mpsc::Receiver<Result<String>>, RestError> {
+        let BlockExportQuery { start, end, headers_only } = query;
+        // Ensure the block range is valid and bounded.
+        if start > end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+        if end - start > MAX_EXPORT_RANGE {
+            return Err(RestError(format!(
+                "Cannot export more than {MAX_EXPORT_RANGE} blocks per call (requested {})",
+                end - start
+            )));
+        }
+
+        let (sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+        let ledger = self.ledger.clone();
+        tokio::task::spawn_blocking(move || {
+            let lines: Box<dyn Iterator<Item = Result<String>>> = match headers_only {
+                true => Box::new(headers(&ledger, start..end).map(|header| Ok(serde_json::to_string(&header?)?))),
+                false => Box::new(blocks(&ledger, start..end).map(|block| Ok(serde_json::to_string(&block?)?))),
+            };
+            for line in lines {
+                let is_err = line.is_err();
+                // Stop exporting once the client disconnects, or after an error.
+                if sender.blocking_send(line).is_err() || is_err {
+                    return;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
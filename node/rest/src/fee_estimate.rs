@@ -0,0 +1,172 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{block::Transaction, ToBytes};
+
+use serde::{Deserialize, Serialize};
+
+/// The number of recent blocks sampled to estimate fees.
+const FEE_SAMPLE_BLOCKS: u32 = 20;
+/// The default number of blocks within which the transaction should be included.
+const DEFAULT_TARGET_BLOCKS: u32 = 2;
+/// The maximum number of blocks that may be targeted.
+pub const MAX_FEE_TARGET_BLOCKS: u32 = 100;
+
+/// The `estimate_fee` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct FeeEstimateQuery {
+    /// The number of blocks within which the transaction should be included.
+    pub(crate) blocks: Option<u32>,
+    /// The size of the transaction in bytes, used to compute the total fee.
+    pub(crate) size: Option<usize>,
+}
+
+/// A suggested fee for inclusion within a number of blocks.
+#[derive(Debug, Serialize)]
+pub(crate) struct FeeEstimate {
+    /// The number of blocks within which the transaction should be included.
+    target_blocks: u32,
+    /// The suggested fee per byte, in microcredits.
+    fee_per_byte: u64,
+    /// The suggested total fee for a transaction of the requested size, in microcredits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee: Option<u64>,
+    /// The number of confirmed transactions sampled from recent blocks.
+    sampled_transactions: usize,
+    /// The number of transactions in the memory pool, or queued to enter it, ahead of the suggested fee.
+    memory_pool_ahead: usize,
+}
+
+/// Returns the fee per byte of the given transaction, in microcredits.
+fn fee_per_byte<N: Network>(transaction: &Transaction<N>) -> Option<u64> {
+    let fee = *transaction.fee_amount().ok()?;
+    let size = transaction.to_bytes_le().ok()?.len() as u64;
+    fee.checked_div(size)
+}
+
+/// Returns the value at the given percentile of the sorted values, or `0` if there are none.
+fn percentile(sorted: &[u64], percentile: usize) -> u64 {
+    match sorted.len() {
+        0 => 0,
+        len => sorted[(len - 1) * percentile.min(100) / 100],
+    }
+}
+
+/// Returns the percentile of recent fees to pay for inclusion within the given number of blocks.
+fn target_percentile(target_blocks: u32) -> usize {
+    match target_blocks {
+        1 => 90,
+        2..=3 => 75,
+        4..=6 => 50,
+        _ => 25,
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/fee/estimate?blocks={target_blocks}&size={size_in_bytes}
+    pub(crate) async fn get_fee_estimate(
+        State(rest): State<Self>,
+        Query(query): Query<FeeEstimateQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.estimate_fee(&query).await?))
+    }
+
+    /// Returns a suggested fee for inclusion within the requested number of blocks,
+    /// based on the fees paid in recent blocks and the fees of the transactions in the memory pool.
+    pub(crate) async fn estimate_fee(&self, query: &FeeEstimateQuery) -> Result<FeeEstimate, RestError> {
+        let target_blocks = query.blocks.unwrap_or(DEFAULT_TARGET_BLOCKS);
+        if target_blocks == 0 || target_blocks > MAX_FEE_TARGET_BLOCKS {
+            return Err(RestError(format!(
+                "The target must be between 1 and {MAX_FEE_TARGET_BLOCKS} blocks (requested {target_blocks})"
+            )));
+        }
+
+        // Load the fees off the async runtime.
+        let (ledger, consensus, template_cache) =
+            (self.ledger.clone(), self.consensus.clone(), self.template_cache.clone());
+        let fees = tokio::task::spawn_blocking(move || {
+            // Sample the fees of the accepted transactions in recent blocks.
+            let latest_height = ledger.latest_height();
+            let mut recent_fees = Vec::new();
+            for height in latest_height.saturating_sub(FEE_SAMPLE_BLOCKS - 1)..=latest_height {
+                let block = ledger.get_block(height)?;
+                recent_fees.extend(
+                    block
+                        .transactions()
+                        .iter()
+                        .filter(|confirmed| confirmed.is_accepted())
+                        .filter_map(|confirmed| fee_per_byte(confirmed.transaction())),
+                );
+            }
+            // Collect the fees of the transactions in the memory pool, and queued to enter it. Their fee densities
+            // are computed once, when they enter the block template cache and the queue, so nothing is re-serialized.
+            let pending_fees = match consensus {
+                Some(consensus) => {
+                    template_cache.refresh(consensus.unconfirmed_transactions());
+                    template_cache
+                        .fee_densities()
+                        .into_iter()
+                        .chain(consensus.queued_fee_densities())
+                        .map(|fee_density| fee_density.fee_per_byte())
+                        .collect::<Vec<_>>()
+                }
+                None => Vec::new(),
+            };
+            Ok::<_, RestError>((recent_fees, pending_fees))
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to estimate the fee - {e}")))?;
+        let (mut recent_fees, mut pending_fees) = fees?;
+
+        let sampled_transactions = recent_fees.len();
+        recent_fees.sort_unstable();
+        let mut suggested = percentile(&recent_fees, target_percentile(target_blocks));
+
+        // Order the pending fees, highest first.
+        pending_fees.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Outbid the memory pool transactions that would fill the target blocks at the recent rate.
+        let capacity = (sampled_transactions / FEE_SAMPLE_BLOCKS as usize).max(1) * target_blocks as usize;
+        if let Some(competing) = pending_fees.get(capacity - 1) {
+            suggested = suggested.max(competing.saturating_add(1));
+        }
+        let memory_pool_ahead = pending_fees.iter().take_while(|fee| **fee >= suggested).count();
+
+        Ok(FeeEstimate {
+            target_blocks,
+            fee_per_byte: suggested,
+            fee: query.size.map(|size| suggested.saturating_mul(size as u64)),
+            sampled_transactions,
+            memory_pool_ahead,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[7], 90), 7);
+        let fees = (1..=100).collect::<Vec<u64>>();
+        assert_eq!(percentile(&fees, 0), 1);
+        assert_eq!(percentile(&fees, 50), 50);
+        assert_eq!(percentile(&fees, 90), 90);
+        assert_eq!(percentile(&fees, 100), 100);
+        assert_eq!(percentile(&fees, 150), 100);
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_router::PeerInfo;
+use snarkvm::prelude::block::{Transaction, Transition};
+
+use async_graphql::{EmptyMutation, EmptySubscription, Error, Object, Schema};
+
+/// The maximum depth of a GraphQL query.
+const MAX_QUERY_DEPTH: usize = 8;
+/// The maximum complexity of a GraphQL query.
+const MAX_QUERY_COMPLEXITY: usize = 2_000;
+
+/// The GraphQL schema of the REST server.
+pub(crate) type ChainSchema<N, C, R> = Schema<QueryRoot<N, C, R>, EmptyMutation, EmptySubscription>;
+
+/// Returns the GraphQL schema for the given server.
+pub(crate) fn schema<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>>(
+    rest: Rest<N, C, R>,
+) -> ChainSchema<N, C, R> {
+    Schema::build(QueryRoot(rest), EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// The root of the GraphQL queries.
+pub(crate) struct QueryRoot<N: Network, C: ConsensusStorage<N>, R: Routing<N>>(Rest<N, C, R>);
+
+#[Object]
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> QueryRoot<N, C, R> {
+    /// Returns the latest block height.
+    async fn latest_height(&self) -> u32 {
+        self.0.ledger.latest_height()
+    }
+
+    /// Returns the block with the given height or hash, or the latest block if neither is given.
+    async fn block(&self, height: Option<u32>, hash: Option<String>) -> Result<BlockObject<N>, Error> {
+        let block = match (height, hash) {
+            (Some(height), _) => self.0.ledger.get_block(height)?,
+            (None, Some(hash)) => {
+                let hash = hash.parse::<N::BlockHash>().map_err(|_| Error::new("Invalid block hash"))?;
+                self.0.ledger.get_block_by_hash(&hash)?
+            }
+            (None, None) => self.0.ledger.latest_block(),
+        };
+        Ok(BlockObject(block))
+    }
+
+    /// Returns the blocks from `start` (inclusive) to `end` (exclusive).
+    async fn blocks(&self, start: u32, end: u32) -> Result<Vec<BlockObject<N>>, Error> {
+        let blocks = self.0.get_blocks_in_range(start, end).map_err(|error| Error::new(error.0))?;
+        Ok(blocks.into_iter().map(BlockObject).collect())
+    }
+
+    /// Returns the transaction with the given ID.
+    async fn transaction(&self, id: String) -> Result<TransactionObject<N>, Error> {
+        let id = id.parse::<N::TransactionID>().map_err(|_| Error::new("Invalid transaction ID"))?;
+        Ok(TransactionObject(self.0.ledger.get_transaction(id)?))
+    }
+
+    /// Returns the connected peers.
+    async fn peers(&self) -> Vec<PeerObject<N>> {
+        self.0.peer_info().into_iter().map(PeerObject).collect()
+    }
+}
+
+/// A block.
+pub(crate) struct BlockObject<N: Network>(Block<N>);
+
+#[Object(name = "Block")]
+impl<N: Network> BlockObject<N> {
+    async fn height(&self) -> u32 {
+        self.0.height()
+    }
+
+    async fn hash(&self) -> String {
+        self.0.hash().to_string()
+    }
+
+    async fn previous_hash(&self) -> String {
+        self.0.previous_hash().to_string()
+    }
+
+    async fn round(&self) -> u64 {
+        self.0.round()
+    }
+
+    async fn timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    async fn num_transactions(&self) -> usize {
+        self.0.transactions().len()
+    }
+
+    async fn transactions(&self) -> Vec<TransactionObject<N>> {
+        self.0.transactions().iter().map(|confirmed| TransactionObject(confirmed.transaction().clone())).collect()
+    }
+}
+
+/// A transaction.
+pub(crate) struct TransactionObject<N: Network>(Transaction<N>);
+
+#[Object(name = "Transaction")]
+impl<N: Network> TransactionObject<N> {
+    async fn id(&self) -> String {
+        self.0.id().to_string()
+    }
+
+    /// The transaction type, one of `deploy`, `execute`, or `fee`.
+    async fn r#type(&self) -> &'static str {
+        match &self.0 {
+            Transaction::Deploy(..) => "deploy",
+            Transaction::Execute(..) => "execute",
+            Transaction::Fee(..) => "fee",
+        }
+    }
+
+    /// The fee, in microcredits.
+    async fn fee(&self) -> Option<u64> {
+        self.0.fee_amount().ok().map(|fee| *fee)
+    }
+
+    async fn transitions(&self) -> Vec<TransitionObject<N>> {
+        self.0.transitions().cloned().map(TransitionObject).collect()
+    }
+}
+
+/// A transition.
+pub(crate) struct TransitionObject<N: Network>(Transition<N>);
+
+#[Object(name = "Transition")]
+impl<N: Network> TransitionObject<N> {
+    async fn id(&self) -> String {
+        self.0.id().to_string()
+    }
+
+    async fn program_id(&self) -> String {
+        self.0.program_id().to_string()
+    }
+
+    async fn function_name(&self) -> String {
+        self.0.function_name().to_string()
+    }
+
+    async fn num_inputs(&self) -> usize {
+        self.0.inputs().len()
+    }
+
+    async fn num_outputs(&self) -> usize {
+        self.0.outputs().len()
+    }
+}
+
+/// A connected peer.
+pub(crate) struct PeerObject<N: Network>(PeerInfo<N>);
+
+#[Object(name = "Peer")]
+impl<N: Network> PeerObject<N> {
+    async fn ip(&self) -> String {
+        self.0.ip.to_string()
+    }
+
+    async fn address(&self) -> String {
+        self.0.address.to_string()
+    }
+
+    async fn node_type(&self) -> String {
+        self.0.node_type.to_string()
+    }
+
+    async fn version(&self) -> u32 {
+        self.0.version
+    }
+
+    async fn height(&self) -> Option<u32> {
+        self.0.height
+    }
+
+    async fn connected_secs(&self) -> u64 {
+        self.0.connected_secs
+    }
+
+    async fn ping_latency_ms(&self) -> Option<u64> {
+        self.0.ping_latency_ms
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{block::Transaction, ToBytes};
+
+use anyhow::anyhow;
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use std::{
+    fs,
+    net::IpAddr,
+    num::NonZeroU32,
+    path::PathBuf,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tokio::sync::{mpsc, SemaphorePermit};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tonic::{
+    transport::{Certificate, Identity, Server, ServerTlsConfig},
+    Request,
+    Response,
+    Status,
+};
+
+/// The generated protobuf types and gRPC service.
+pub mod proto {
+    tonic::include_proto!("snarkos.v1");
+}
+
+use proto::{
+    get_block_request::Id,
+    node_server::{Node, NodeServer},
+};
+
+/// The interval at which the ledger is checked for new blocks to stream, in milliseconds.
+const LEDGER_POLL_INTERVAL_IN_MS: u64 = 1_000;
+/// The number of blocks buffered per block subscription.
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 64;
+/// The maximum number of blocks behind the tip that a block subscription may start from.
+/// Older blocks must be fetched with `GetBlocks`.
+const MAX_SUBSCRIPTION_BACKFILL: u32 = 100;
+/// The maximum number of concurrent block subscriptions.
+const MAX_BLOCK_SUBSCRIBERS: usize = 64;
+
+/// The gRPC service of the REST server.
+pub(crate) struct GrpcService<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
+    /// The REST server.
+    rest: Rest<N, C, R>,
+    /// The per-IP rate limiter, which mirrors the one of the REST server. A rate of `0` disables it.
+    rate_limiter: Option<Arc<DefaultKeyedRateLimiter<IpAddr>>>,
+    /// The number of active block subscriptions.
+    num_subscribers: Arc<AtomicUsize>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Spawns the gRPC server on the given address, until the server shuts down.
+    pub(crate) fn spawn_grpc_server(&self, grpc_ip: SocketAddr) -> Result<()> {
+        let service = NodeServer::new(GrpcService {
+            rest: self.clone(),
+            rate_limiter: NonZeroU32::new(self.config.rps)
+                .map(|rps| Arc::new(RateLimiter::keyed(Quota::per_second(NonZeroU32::MIN).allow_burst(rps)))),
+            num_subscribers: Default::default(),
+        });
+
+        // If TLS is configured, terminate TLS in the server, with the certificates of the REST server.
+        let mut builder = Server::builder();
+        if let Some(tls) = &self.config.tls {
+            let read = |path: &PathBuf| fs::read(path).map_err(|e| anyhow!("Failed to read '{}': {e}", path.display()));
+            let mut tls_config =
+                ServerTlsConfig::new().identity(Identity::from_pem(read(&tls.cert_path)?, read(&tls.key_path)?));
+            if let Some(client_ca_path) = &tls.client_ca_path {
+                tls_config = tls_config.client_ca_root(Certificate::from_pem(read(client_ca_path)?));
+            }
+            builder = builder.tls_config(tls_config)?;
+        }
+
+        info!("Starting the gRPC server at {grpc_ip}");
+        let shutdown = self.shutdown.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            // Stop accepting connections once the shutdown is signaled.
+            let signal = async move { shutdown.wait_for_signal().await };
+            if let Err(error) = builder.add_service(service).serve_with_shutdown(grpc_ip, signal).await {
+                error!("The gRPC server stopped - {error}");
+            }
+        }));
+        Ok(())
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> GrpcService<N, C, R> {
+    /// Applies the controls of the JSON-RPC method backing the given call: the per-IP rate limit,
+    /// the method policy, and the concurrency limit. Returns the concurrency permit, if the method is limited.
+    async fn authorize<T>(&self, request: &Request<T>, method: &str) -> Result<Option<SemaphorePermit<'_>>, Status> {
+        if let (Some(limiter), Some(addr)) = (&self.rate_limiter, request.remote_addr()) {
+            if limiter.check_key(&addr.ip()).is_err() {
+                return Err(Status::resource_exhausted("Too many requests"));
+            }
+        }
+        if !self.rest.config.rpc_methods.is_enabled(method) {
+            return Err(Status::permission_denied(format!("Method '{method}' is disabled on this node")));
+        }
+        self.rest.config.rpc_limits.acquire(method).await.map_err(|error| Status::unavailable(error.to_string()))
+    }
+
+    /// Runs the given ledger read on the blocking thread pool.
+    async fn blocking<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(Rest<N, C, R>) -> Result<T, Status> + Send + 'static,
+    ) -> Result<T, Status> {
+        let rest = self.rest.clone();
+        tokio::task::spawn_blocking(move || f(rest)).await.map_err(|e| Status::internal(e.to_string()))?
+    }
+}
+
+/// Releases a block subscription slot when the subscription ends.
+struct SubscriberGuard(Arc<AtomicUsize>);
+
+impl Drop for SubscriberGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Converts the given block into its protobuf representation.
+fn to_proto_block<N: Network>(block: &Block<N>) -> Result<proto::Block, Status> {
+    Ok(proto::Block {
+        height: block.height(),
+        hash: block.hash().to_string(),
+        previous_hash: block.previous_hash().to_string(),
+        timestamp: block.timestamp(),
+        transaction_ids: block.transaction_ids().map(ToString::to_string).collect(),
+        data: block.to_bytes_le().map_err(|e| Status::internal(e.to_string()))?,
+    })
+}
+
+/// Converts the given transaction into its protobuf representation.
+fn to_proto_transaction<N: Network>(transaction: &Transaction<N>) -> Result<proto::Transaction, Status> {
+    let r#type = match transaction {
+        Transaction::Deploy(..) => "deploy",
+        Transaction::Execute(..) => "execute",
+        Transaction::Fee(..) => "fee",
+    };
+    Ok(proto::Transaction {
+        id: transaction.id().to_string(),
+        r#type: r#type.to_string(),
+        data: transaction.to_bytes_le().map_err(|e| Status::internal(e.to_string()))?,
+    })
+}
+
+#[tonic::async_trait]
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Node for GrpcService<N, C, R> {
+    type SubscribeBlocksStream = Pin<Box<dyn Stream<Item = Result<proto::Block, Status>> + Send>>;
+
+    async fn get_status(
+        &self,
+        request: Request<proto::GetStatusRequest>,
+    ) -> Result<Response<proto::NodeStatus>, Status> {
+        let _permit = self.authorize(&request, "getstatus").await?;
+        let router = self.rest.routing.router();
+        Ok(Response::new(proto::NodeStatus {
+            node_type: router.node_type().to_string(),
+            height: self.rest.ledger.latest_height(),
+            hash: self.rest.ledger.latest_hash().to_string(),
+            is_synced: self.rest.sync.is_block_synced(),
+            num_peers: router.number_of_connected_peers() as u32,
+        }))
+    }
+
+    async fn get_block(&self, request: Request<proto::GetBlockRequest>) -> Result<Response<proto::Block>, Status> {
+        let _permit = self.authorize(&request, "getblock").await?;
+        let id = request
+            .into_inner()
+            .id
+            .ok_or_else(|| Status::invalid_argument("Expected a block height or a block hash"))?;
+        let block = self
+            .blocking(move |rest| {
+                let block = match id {
+                    Id::Height(height) => rest.ledger.get_block(height),
+                    Id::Hash(hash) => {
+                        let hash =
+                            hash.parse::<N::BlockHash>().map_err(|_| Status::invalid_argument("Invalid block hash"))?;
+                        rest.ledger.get_block_by_hash(&hash)
+                    }
+                };
+                to_proto_block(&block.map_err(|e| Status::not_found(e.to_string()))?)
+            })
+            .await?;
+        Ok(Response::new(block))
+    }
+
+    async fn get_blocks(&self, request: Request<proto::GetBlocksRequest>) -> Result<Response<proto::Blocks>, Status> {
+        let _permit = self.authorize(&request, "getblocks").await?;
+        let request = request.into_inner();
+        let blocks = self
+            .blocking(move |rest| {
+                let blocks = rest
+                    .get_blocks_in_range(request.start, request.end)
+                    .map_err(|error| Status::invalid_argument(error.0))?;
+                blocks.iter().map(to_proto_block).collect::<Result<Vec<_>, _>>()
+            })
+            .await?;
+        Ok(Response::new(proto::Blocks { blocks }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<proto::GetTransactionRequest>,
+    ) -> Result<Response<proto::Transaction>, Status> {
+        let _permit = self.authorize(&request, "gettransaction").await?;
+        let id = request
+            .into_inner()
+            .id
+            .parse::<N::TransactionID>()
+            .map_err(|_| Status::invalid_argument("Invalid transaction ID"))?;
+        let transaction = self
+            .blocking(move |rest| {
+                let transaction = rest.ledger.get_transaction(id).map_err(|e| Status::not_found(e.to_string()))?;
+                to_proto_transaction(&transaction)
+            })
+            .await?;
+        Ok(Response::new(transaction))
+    }
+
+    async fn get_peer_info(
+        &self,
+        request: Request<proto::GetPeerInfoRequest>,
+    ) -> Result<Response<proto::Peers>, Status> {
+        let _permit = self.authorize(&request, "getpeerinfo").await?;
+        let peers = self
+            .rest
+            .peer_info()
+            .into_iter()
+            .map(|peer| proto::Peer {
+                ip: peer.ip.to_string(),
+                address: peer.address.to_string(),
+                node_type: peer.node_type.to_string(),
+                version: peer.version,
+                is_inbound: peer.direction == snarkos_node_router::PeerDirection::Inbound,
+                height: peer.height,
+                connected_secs: peer.connected_secs,
+                ping_latency_ms: peer.ping_latency_ms,
+            })
+            .collect();
+        Ok(Response::new(proto::Peers { peers }))
+    }
+
+    async fn subscribe_blocks(
+        &self,
+        request: Request<proto::SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        // The subscription holds no concurrency permit, as it is bounded by the number of subscribers instead.
+        drop(self.authorize(&request, "getblocks").await?);
+
+        let latest_height = self.rest.ledger.latest_height();
+        let mut next_height = request.into_inner().start_height.unwrap_or_else(|| latest_height.saturating_add(1));
+        // Ensure the backfill range is bounded.
+        if next_height < latest_height.saturating_sub(MAX_SUBSCRIPTION_BACKFILL) {
+            return Err(Status::invalid_argument(format!(
+                "Cannot start more than {MAX_SUBSCRIPTION_BACKFILL} blocks behind the latest height ({latest_height})"
+            )));
+        }
+        // Ensure the number of subscribers is bounded.
+        if self.num_subscribers.fetch_add(1, Ordering::SeqCst) >= MAX_BLOCK_SUBSCRIBERS {
+            self.num_subscribers.fetch_sub(1, Ordering::SeqCst);
+            return Err(Status::resource_exhausted("Too many block subscriptions"));
+        }
+        let guard = SubscriberGuard(self.num_subscribers.clone());
+
+        let (rest, shutdown) = (self.rest.clone(), self.rest.shutdown.clone());
+        let (sender, receiver) = mpsc::channel(SUBSCRIPTION_CHANNEL_CAPACITY);
+        tokio::spawn(async move {
+            let _guard = guard;
+            let mut interval = tokio::time::interval(Duration::from_millis(LEDGER_POLL_INTERVAL_IN_MS));
+            loop {
+                // End the subscription once the server shuts down.
+                tokio::select! {
+                    _ = interval.tick() => (),
+                    _ = shutdown.wait_for_signal() => return,
+                }
+                // Stream the blocks added since the last poll.
+                while next_height <= rest.ledger.latest_height() {
+                    let ledger = rest.ledger.clone();
+                    let block = tokio::task::spawn_blocking(move || {
+                        to_proto_block(&ledger.get_block(next_height).map_err(|e| Status::internal(e.to_string()))?)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(Status::internal(e.to_string())));
+                    let is_err = block.is_err();
+                    // Stop streaming once the client disconnects, or after an error.
+                    if sender.send(block).await.is_err() || is_err {
+                        return;
+                    }
+                    next_height += 1;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+    }
+}
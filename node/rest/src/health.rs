@@ -0,0 +1,113 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::Serialize;
+
+/// The readiness of the node to serve requests.
+#[derive(Debug, Serialize)]
+pub(crate) struct Readiness {
+    /// Whether the node is ready.
+    ready: bool,
+    /// The latest block height of the node.
+    height: u32,
+    /// The highest block height among the connected peers, if any is known.
+    peer_height: Option<u32>,
+    /// The number of blocks the node is behind its peers.
+    blocks_behind: u32,
+    /// The number of connected peers.
+    num_peers: usize,
+    /// Whether block sync and the memory pool are paused, as the node is low on disk space.
+    low_disk_space: bool,
+    /// The reasons the node is not ready, if it is not ready.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    reasons: Vec<String>,
+}
+
+/// Returns the status code for the given probe outcome.
+fn probe_status(is_ok: bool) -> StatusCode {
+    match is_ok {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /livez
+    pub(crate) async fn livez() -> (StatusCode, ErasedJson) {
+        (StatusCode::OK, ErasedJson::pretty(serde_json::json!({ "status": "ok" })))
+    }
+
+    // GET /healthz
+    pub(crate) async fn healthz(State(rest): State<Self>) -> (StatusCode, ErasedJson) {
+        // Ensure the ledger can serve its latest block.
+        let ledger = rest.ledger.clone();
+        let result = tokio::task::spawn_blocking(move || ledger.get_block(ledger.latest_height()).map(|_| ()))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        match result {
+            Ok(()) => (StatusCode::OK, ErasedJson::pretty(serde_json::json!({ "status": "ok" }))),
+            Err(error) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErasedJson::pretty(serde_json::json!({ "status": "error", "reason": error.to_string() })),
+            ),
+        }
+    }
+
+    // GET /readyz
+    pub(crate) async fn readyz(State(rest): State<Self>) -> (StatusCode, ErasedJson) {
+        let readiness = rest.readiness();
+        (probe_status(readiness.ready), ErasedJson::pretty(readiness))
+    }
+
+    /// Returns the readiness of the node, which is ready once it has peers,
+    /// and is synced to within the configured number of blocks of them.
+    pub(crate) fn readiness(&self) -> Readiness {
+        let router = self.routing.router();
+        let height = self.ledger.latest_height();
+        let peers = router.connected_peers();
+        let peer_height = peers.iter().filter_map(|peer_ip| self.sync.get_peer_height(peer_ip)).max();
+        let blocks_behind = peer_height.map_or(0, |peer_height| peer_height.saturating_sub(height));
+
+        let low_disk_space = self.sync.is_paused();
+
+        let mut reasons = Vec::new();
+        if self.shutdown.is_draining() {
+            reasons.push("The node is shutting down".to_string());
+        }
+        if low_disk_space {
+            reasons.push("The node is low on disk space, so block sync and the memory pool are paused".to_string());
+        }
+        if peers.is_empty() {
+            reasons.push("The node has no connected peers".to_string());
+        }
+        let max_blocks_behind = self.config.ready_max_blocks_behind;
+        if blocks_behind > max_blocks_behind {
+            reasons
+                .push(format!("The node is {blocks_behind} blocks behind its peers (tolerance {max_blocks_behind})"));
+        }
+
+        Readiness {
+            ready: reasons.is_empty(),
+            height,
+            peer_height,
+            blocks_behind,
+            num_peers: peers.len(),
+            low_disk_space,
+            reasons,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RPC_METHODS;
+
+use serde_json::Value;
+use std::{fmt, net::IpAddr, time::Duration};
+
+/// The tracing target of the JSON-RPC access log.
+pub const ACCESS_LOG_TARGET: &str = "snarkos_node_rest::access";
+
+/// The parameters that are redacted from the access log, as they may identify a user or carry secrets.
+const REDACTED_PARAMS: &[&str] = &["address", "commitment", "transaction"];
+/// The placeholder for a redacted parameter.
+const REDACTED: &str = "[redacted]";
+
+/// The caller of a JSON-RPC method.
+#[derive(Copy, Clone, Debug)]
+pub enum RpcCaller {
+    /// A caller connected over TCP, from the given IP.
+    Ip(IpAddr),
+    /// A caller connected over the Unix socket.
+    Unix,
+}
+
+impl fmt::Display for RpcCaller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip(ip) => write!(f, "{ip}"),
+            Self::Unix => write!(f, "unix"),
+        }
+    }
+}
+
+/// Returns the given method parameters, with the sensitive parameters redacted.
+/// Positional parameters are matched to their names through the method definitions.
+pub fn redact_params(method: &str, params: &Value) -> Value {
+    let is_redacted = |name: &str| REDACTED_PARAMS.contains(&name);
+    match params {
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(name, value)| match is_redacted(name) {
+                    true => (name.clone(), Value::from(REDACTED)),
+                    false => (name.clone(), value.clone()),
+                })
+                .collect(),
+        ),
+        Value::Array(values) => {
+            let params = RPC_METHODS.iter().find(|rpc_method| rpc_method.name == method).map(|method| method.params);
+            Value::Array(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        // Redact the parameters of unknown methods, as their names are unknown.
+                        match params.map(|params| params.get(index).map(|param| param.name)) {
+                            Some(Some(name)) if !is_redacted(name) => value.clone(),
+                            _ => Value::from(REDACTED),
+                        }
+                    })
+                    .collect(),
+            )
+        }
+        params => params.clone(),
+    }
+}
+
+/// Writes the access log entry of a JSON-RPC call, where a result code of `0` denotes success.
+/// The API key is identified by its name, never by its secret.
+pub fn log_rpc_call(
+    caller: RpcCaller,
+    api_key: Option<&str>,
+    method: &str,
+    params: &Value,
+    latency: Duration,
+    result_code: i64,
+) {
+    info!(
+        target: ACCESS_LOG_TARGET,
+        caller = %caller,
+        api_key,
+        method,
+        params = %redact_params(method, params),
+        latency_ms = latency.as_millis() as u64,
+        result_code,
+        "RPC call"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_params() {
+        // Named parameters are redacted by name.
+        let params = json!({ "address": "aleo1...", "cursor": 5 });
+        assert_eq!(redact_params("gettransactionsbyaddress", &params), json!({ "address": REDACTED, "cursor": 5 }));
+
+        // Positional parameters are redacted through the method definitions.
+        let params = json!(["aleo1...", 5]);
+        assert_eq!(redact_params("gettransactionsbyaddress", &params), json!([REDACTED, 5]));
+        assert_eq!(redact_params("getblock", &json!([7])), json!([7]));
+
+        // The parameters of unknown methods, and extra positional parameters, are redacted.
+        assert_eq!(redact_params("unknown", &json!([1])), json!([REDACTED]));
+        assert_eq!(redact_params("getblock", &json!([7, 8])), json!([7, REDACTED]));
+        assert_eq!(redact_params("getstatus", &Value::Null), Value::Null);
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{constant_time_eq, rpc_method_group, RpcMethodGroup, RpcMethodPolicy};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use governor::{
+    clock::{Clock, DefaultClock},
+    DefaultDirectRateLimiter,
+    Quota,
+    RateLimiter,
+};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, num::NonZeroU32, sync::Arc, time::Duration};
+use time::OffsetDateTime;
+
+/// The header that carries the API key of a JSON-RPC request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The usage counters of an API key.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ApiKeyUsage {
+    /// The name of the API key.
+    pub name: String,
+    /// The number of calls made with the key.
+    pub calls: u64,
+    /// The number of calls that returned an error, including the rejected calls.
+    pub errors: u64,
+    /// The number of calls rejected by the rate limit of the key.
+    pub rate_limited: u64,
+    /// The number of calls of each method.
+    pub methods: BTreeMap<String, u64>,
+    /// The UNIX timestamp of the last call, if any.
+    pub last_used: Option<i64>,
+}
+
+/// A named API key, with its own rate limit and method permissions.
+pub struct ApiKey {
+    /// The name of the key, which identifies its holder.
+    name: String,
+    /// The secret key.
+    key: String,
+    /// The methods the key may call.
+    methods: RpcMethodPolicy,
+    /// The rate limiter of the key, if limited.
+    limiter: Option<DefaultDirectRateLimiter>,
+    /// The usage counters of the key.
+    usage: Mutex<ApiKeyUsage>,
+}
+
+impl ApiKey {
+    /// Parses an API key from the given `name:key:rps:methods` entry.
+    /// A rate of `0` disables the rate limit, and an empty method list permits every public method.
+    fn new(entry: &str) -> Result<Self> {
+        let [name, key, rps, methods] = entry.splitn(4, ':').collect::<Vec<_>>()[..] else {
+            bail!("Invalid API key entry (expected 'name:key:rps:methods')");
+        };
+        ensure!(!name.is_empty() && !key.is_empty(), "Invalid API key entry (missing the name or the key)");
+        let rps = rps.parse::<u32>().map_err(|_| anyhow!("Invalid rate limit '{rps}' for the API key '{name}'"))?;
+        let allow = methods.split(',').map(str::trim).filter(|method| !method.is_empty()).map(str::to_string).collect();
+        let methods = RpcMethodPolicy::new(allow, vec![])?;
+        let limiter = NonZeroU32::new(rps).map(|rps| RateLimiter::direct(Quota::per_second(rps)));
+        let usage = Mutex::new(ApiKeyUsage { name: name.to_string(), ..Default::default() });
+        Ok(Self { name: name.to_string(), key: key.to_string(), methods, limiter, usage })
+    }
+
+    /// Returns the name of the key.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the key may call the given method.
+    /// The control and debug methods must be listed explicitly, as they are never permitted by default.
+    pub fn is_permitted(&self, method: &str) -> bool {
+        match rpc_method_group(method) {
+            Some(RpcMethodGroup::Control | RpcMethodGroup::Debug) => self.methods.is_explicitly_allowed(method),
+            _ => self.methods.is_enabled(method),
+        }
+    }
+
+    /// Consumes a call from the rate limit of the key, returning the wait time if the limit is exceeded.
+    pub fn check_rate_limit(&self) -> Result<(), Duration> {
+        match &self.limiter {
+            Some(limiter) => {
+                limiter.check().map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Records a call of the given method, with its result code, where `0` denotes success.
+    pub fn record_call(&self, method: &str, result_code: i64, is_rate_limited: bool) {
+        let mut usage = self.usage.lock();
+        usage.calls += 1;
+        usage.errors += u64::from(result_code != 0);
+        usage.rate_limited += u64::from(is_rate_limited);
+        *usage.methods.entry(method.to_string()).or_default() += 1;
+        usage.last_used = Some(OffsetDateTime::now_utc().unix_timestamp());
+    }
+}
+
+/// The API keys accepted by the JSON-RPC endpoint.
+#[derive(Clone, Default)]
+pub struct ApiKeys(Arc<[Arc<ApiKey>]>);
+
+impl fmt::Debug for ApiKeys {
+    /// Formats the names of the keys, leaving out the secrets.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|key| &key.name)).finish()
+    }
+}
+
+impl ApiKeys {
+    /// Initializes the API keys from the given `name:key:rps:methods` entries.
+    pub fn new(entries: &[String]) -> Result<Self> {
+        let mut keys = Vec::<Arc<ApiKey>>::with_capacity(entries.len());
+        for entry in entries {
+            let key = ApiKey::new(entry)?;
+            ensure!(!keys.iter().any(|other| other.name == key.name), "Duplicate API key name '{}'", key.name);
+            ensure!(!keys.iter().any(|other| other.key == key.key), "The API key '{}' reuses another key", key.name);
+            keys.push(Arc::new(key));
+        }
+        Ok(Self(keys.into()))
+    }
+
+    /// Returns `true` if no API keys are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the API key matching the given secret, if any.
+    pub fn get(&self, key: &str) -> Option<Arc<ApiKey>> {
+        // Compare every key in constant time, so the lookup does not leak the position of a match.
+        self.0.iter().fold(None, |found, expected| match constant_time_eq(expected.key.as_bytes(), key.as_bytes()) {
+            true => Some(expected.clone()),
+            false => found,
+        })
+    }
+
+    /// Returns the usage counters of every API key.
+    pub fn usage(&self) -> Vec<ApiKeyUsage> {
+        self.0.iter().map(|key| key.usage.lock().clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn test_api_keys() {
+        let keys =
+            ApiKeys::new(&entries(&["indexer:secret-1:1:read", "ops:secret-2:0:read,setban,listbanned"])).unwrap();
+        assert!(keys.get("secret-3").is_none());
+
+        // The permissions are checked per key, and the private methods must be listed explicitly.
+        let indexer = keys.get("secret-1").unwrap();
+        assert_eq!(indexer.name(), "indexer");
+        assert!(indexer.is_permitted("getblock"));
+        assert!(!indexer.is_permitted("estimatefee"));
+        assert!(!indexer.is_permitted("setban"));
+        let ops = keys.get("secret-2").unwrap();
+        assert!(ops.is_permitted("setban"));
+        assert!(!ops.is_permitted("clearbanned"));
+
+        // The rate limit applies per key.
+        assert!(indexer.check_rate_limit().is_ok());
+        assert!(indexer.check_rate_limit().is_err());
+        assert!(ops.check_rate_limit().is_ok());
+        assert!(ops.check_rate_limit().is_ok());
+
+        // The usage is counted per key and method.
+        indexer.record_call("getblock", 0, false);
+        indexer.record_call("getblock", -32003, true);
+        let usage = keys.usage();
+        assert_eq!((usage[0].calls, usage[0].errors, usage[0].rate_limited), (2, 1, 1));
+        assert_eq!(usage[0].methods.get("getblock"), Some(&2));
+        assert_eq!(usage[1].calls, 0);
+
+        // Invalid entries are rejected.
+        assert!(ApiKeys::new(&entries(&["indexer:secret-1"])).is_err());
+        assert!(ApiKeys::new(&entries(&["indexer:secret-1:x:"])).is_err());
+        assert!(ApiKeys::new(&entries(&["indexer:secret-1:0:admin"])).is_err());
+        assert!(ApiKeys::new(&entries(&["a:secret-1:0:", "a:secret-2:0:"])).is_err());
+        assert!(ApiKeys::new(&entries(&["a:secret-1:0:", "b:secret-1:0:"])).is_err());
+    }
+}
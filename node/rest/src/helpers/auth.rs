@@ -18,6 +18,7 @@ use ::time::OffsetDateTime;
 use anyhow::{anyhow, Result};
 use axum::{
     body::Body,
+    extract::State,
     http::{Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
@@ -30,6 +31,7 @@ use axum_extra::{
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// The time a jwt token is valid for.
 pub const EXPIRATION: i64 = 10 * 365 * 24 * 60 * 60; // 10 years.
@@ -56,8 +58,13 @@ pub struct Claims {
 
 impl Claims {
     pub fn new<N: Network>(address: Address<N>) -> Self {
+        Self::with_expiration(address, EXPIRATION)
+    }
+
+    /// Initializes new claims for the given address, which expire after the given number of seconds.
+    pub fn with_expiration<N: Network>(address: Address<N>, expiration_in_secs: i64) -> Self {
         let issued_at = OffsetDateTime::now_utc().unix_timestamp();
-        let expiration = issued_at.saturating_add(EXPIRATION);
+        let expiration = issued_at.saturating_add(expiration_in_secs);
 
         Self { sub: address.to_string(), iat: issued_at, exp: expiration }
     }
@@ -73,22 +80,55 @@ impl Claims {
     }
 }
 
-pub async fn auth_middleware(request: Request<Body>, next: Next) -> Result<Response, Response> {
+/// The static bearer tokens accepted by the private routes.
+#[derive(Clone, Debug, Default)]
+pub struct BearerTokens(Arc<[String]>);
+
+impl BearerTokens {
+    /// Initializes the bearer tokens, skipping any empty tokens.
+    pub fn new(tokens: &[String]) -> Self {
+        Self(tokens.iter().filter(|token| !token.is_empty()).cloned().collect())
+    }
+
+    /// Returns `true` if the given token is one of the static bearer tokens.
+    pub fn contains(&self, token: &str) -> bool {
+        // Note: Every token is compared in full, to avoid leaking the matched prefix through timing.
+        self.0.iter().fold(false, |found, expected| found | constant_time_eq(expected.as_bytes(), token.as_bytes()))
+    }
+}
+
+/// Returns `true` if the given byte strings are equal, in time independent of their contents.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub async fn auth_middleware(
+    State(bearer_tokens): State<BearerTokens>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
     // Deconstruct the request to extract the auth token.
     let (mut parts, body) = request.into_parts();
     let auth: TypedHeader<Authorization<Bearer>> =
         parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
 
-    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
-        Ok(decoded) => {
-            let claims = decoded.claims;
-            if claims.is_expired() {
-                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
+    // Accept the static bearer tokens, if any are configured, and otherwise check the JWT.
+    if !bearer_tokens.contains(auth.token()) {
+        match decode::<Claims>(
+            auth.token(),
+            &DecodingKey::from_secret(jwt_secret()),
+            &Validation::new(Algorithm::HS256),
+        ) {
+            Ok(decoded) => {
+                let claims = decoded.claims;
+                if claims.is_expired() {
+                    return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
+                }
             }
-        }
 
-        Err(_) => {
-            return Err(StatusCode::UNAUTHORIZED.into_response());
+            Err(_) => {
+                return Err(StatusCode::UNAUTHORIZED.into_response());
+            }
         }
     }
 
@@ -97,3 +137,18 @@ pub async fn auth_middleware(request: Request<Body>, next: Next) -> Result<Respo
 
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bearer_tokens() {
+        let tokens = BearerTokens::new(&["secret".to_string(), "".to_string()]);
+        assert!(tokens.contains("secret"));
+        assert!(!tokens.contains("secreT"));
+        assert!(!tokens.contains("secret2"));
+        // Empty tokens are never accepted.
+        assert!(!tokens.contains(""));
+    }
+}
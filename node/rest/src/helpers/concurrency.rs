@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::rpc_method_group;
+
+use anyhow::{anyhow, bail, Result};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// The default number of concurrent calls of the expensive JSON-RPC methods.
+const DEFAULT_RPC_CONCURRENCY: &[(&str, usize)] = &[
+    ("getblocks", 4),
+    ("scanblocks", 2),
+    ("getledgerproof", 4),
+    ("validaterawtransaction", 2),
+    ("estimatefee", 4),
+    ("getblockstats", 4),
+    ("getmemorypoolinfo", 4),
+    ("getmemorypooltransactions", 4),
+];
+/// The default number of seconds a call waits for a free slot, before it is rejected.
+pub const DEFAULT_RPC_QUEUE_TIMEOUT_IN_SECS: u64 = 5;
+
+/// The limits on the number of concurrent calls of each JSON-RPC method.
+/// A call that exceeds the limit waits in a queue, until a slot frees up or the queue timeout elapses.
+#[derive(Clone, Debug)]
+pub struct RpcConcurrencyLimits {
+    /// The semaphore of each limited method.
+    semaphores: HashMap<String, Arc<Semaphore>>,
+    /// The maximum duration a call waits for a free slot.
+    queue_timeout: Duration,
+}
+
+impl Default for RpcConcurrencyLimits {
+    /// Initializes the default limits, which only apply to the expensive methods.
+    fn default() -> Self {
+        Self::new(&[], DEFAULT_RPC_QUEUE_TIMEOUT_IN_SECS).expect("The default RPC concurrency limits are valid")
+    }
+}
+
+impl RpcConcurrencyLimits {
+    /// Initializes the limits from the given `method=limit` entries, which override the defaults.
+    /// A limit of `0` removes the limit of the method.
+    pub fn new(entries: &[String], queue_timeout_secs: u64) -> Result<Self> {
+        let mut limits = DEFAULT_RPC_CONCURRENCY
+            .iter()
+            .map(|(method, limit)| (method.to_string(), *limit))
+            .collect::<HashMap<_, _>>();
+        for entry in entries {
+            let Some((method, limit)) = entry.split_once('=') else {
+                bail!("Invalid RPC concurrency limit '{entry}' (expected 'method=limit')");
+            };
+            if rpc_method_group(method).is_none() {
+                bail!("Unknown RPC method '{method}' in the concurrency limits");
+            }
+            let limit = limit.parse::<usize>().map_err(|_| anyhow!("Invalid RPC concurrency limit '{entry}'"))?;
+            limits.insert(method.to_string(), limit);
+        }
+        let semaphores = limits
+            .into_iter()
+            .filter(|(_, limit)| *limit > 0)
+            .map(|(method, limit)| (method, Arc::new(Semaphore::new(limit))))
+            .collect();
+        Ok(Self { semaphores, queue_timeout: Duration::from_secs(queue_timeout_secs) })
+    }
+
+    /// Returns `true` if the given method is limited, and every slot is taken.
+    pub fn is_saturated(&self, method: &str) -> bool {
+        self.semaphores.get(method).map_or(false, |semaphore| semaphore.available_permits() == 0)
+    }
+
+    /// Waits for a free slot for the given method, returning `None` if the method is not limited,
+    /// or an error if the queue timeout elapsed first.
+    pub async fn acquire(&self, method: &str) -> Result<Option<SemaphorePermit<'_>>> {
+        let Some(semaphore) = self.semaphores.get(method) else {
+            return Ok(None);
+        };
+        match tokio::time::timeout(self.queue_timeout, semaphore.acquire()).await {
+            Ok(permit) => Ok(Some(permit?)),
+            Err(_) => bail!("Too many concurrent '{method}' calls, try again later"),
+        }
+    }
+}
+
+/// Limits the concurrent requests of a REST route by the limit of the equivalent JSON-RPC method,
+/// so the expensive work cannot bypass the limit through the REST routes.
+pub async fn rpc_concurrency_middleware(
+    State((limits, method)): State<(RpcConcurrencyLimits, &'static str)>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    match limits.acquire(method).await {
+        // Hold the slot until the response is ready.
+        Ok(_permit) => next.run(request).await,
+        Err(error) => (StatusCode::SERVICE_UNAVAILABLE, error.to_string()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rpc_concurrency_limits() {
+        let limits = RpcConcurrencyLimits::new(&["getblocks=1".to_string(), "scanblocks=0".to_string()], 0).unwrap();
+
+        // The unlimited methods never wait.
+        assert!(limits.acquire("getstatus").await.unwrap().is_none());
+        assert!(limits.acquire("scanblocks").await.unwrap().is_none());
+
+        // A call beyond the limit times out, until the slot is released.
+        let permit = limits.acquire("getblocks").await.unwrap();
+        assert!(permit.is_some());
+        assert!(limits.is_saturated("getblocks"));
+        assert!(!limits.is_saturated("scanblocks"));
+        assert!(limits.acquire("getblocks").await.is_err());
+        drop(permit);
+        assert!(limits.acquire("getblocks").await.unwrap().is_some());
+
+        // Invalid entries are rejected.
+        assert!(RpcConcurrencyLimits::new(&["getblocks".to_string()], 5).is_err());
+        assert!(RpcConcurrencyLimits::new(&["getblok=1".to_string()], 5).is_err());
+        assert!(RpcConcurrencyLimits::new(&["getblocks=x".to_string()], 5).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{ApiKeys, CorsConfig, RpcConcurrencyLimits, RpcMethodPolicy, RpcReplicas, TlsConfig};
+
+use std::{net::SocketAddr, path::PathBuf};
+
+/// The configuration of the REST server.
+#[derive(Clone, Debug)]
+pub struct RestConfig {
+    /// The requests per second (RPS) rate limit per IP.
+    pub rps: u32,
+    /// The static bearer tokens accepted by the private routes, in addition to the node's JWT.
+    pub bearer_tokens: Vec<String>,
+    /// The requests per second (RPS) rate limit per static bearer token (`0` disables the limit).
+    pub token_rps: u32,
+    /// The burst size per static bearer token (`0` defaults to the token rate limit).
+    pub token_burst: u32,
+    /// The named API keys accepted by the JSON-RPC endpoint, each with its own rate limit and method permissions.
+    pub api_keys: ApiKeys,
+    /// The TLS configuration, if the server terminates TLS itself.
+    pub tls: Option<TlsConfig>,
+    /// Whether to maintain an index of the transactions that reference each address.
+    pub address_index: bool,
+    /// Whether to maintain an index of the location of each transaction, to speed up transaction lookups.
+    pub transaction_index: bool,
+    /// The path of the on-disk transaction index, or `None` to keep the transaction index in memory.
+    pub transaction_index_path: Option<PathBuf>,
+    /// The address of the gRPC server, if enabled.
+    pub grpc: Option<SocketAddr>,
+    /// The path of the Unix socket serving the JSON-RPC endpoint, if enabled.
+    pub unix_socket: Option<PathBuf>,
+    /// The JSON-RPC methods that are enabled.
+    pub rpc_methods: RpcMethodPolicy,
+    /// The limits on the number of concurrent calls of the expensive JSON-RPC methods.
+    pub rpc_limits: RpcConcurrencyLimits,
+    /// The replicas that serve the expensive read methods, when those methods are at their concurrency limit.
+    pub replicas: RpcReplicas,
+    /// The number of blocks the node may be behind its peers, and still report as ready.
+    pub ready_max_blocks_behind: u32,
+    /// The CORS policy.
+    pub cors: CorsConfig,
+    /// The number of seconds the server reports as not ready before it stops accepting connections, on shutdown.
+    pub shutdown_delay_secs: u64,
+    /// The number of seconds the server waits for in-flight requests to complete, on shutdown.
+    pub shutdown_timeout_secs: u64,
+    /// Whether to write an access log entry for each JSON-RPC call, with the sensitive parameters redacted.
+    pub access_log: bool,
+}
+
+impl RestConfig {
+    /// Initializes a new REST configuration with the given rate limit, and the default values.
+    pub fn new(rps: u32) -> Self {
+        Self { rps, ..Default::default() }
+    }
+}
+
+impl Default for RestConfig {
+    /// Initializes a new REST configuration with the default values.
+    fn default() -> Self {
+        Self {
+            rps: 10,
+            bearer_tokens: Vec::new(),
+            token_rps: 0,
+            token_burst: 0,
+            api_keys: ApiKeys::default(),
+            tls: None,
+            address_index: false,
+            transaction_index: false,
+            transaction_index_path: None,
+            grpc: None,
+            unix_socket: None,
+            rpc_methods: RpcMethodPolicy::default(),
+            rpc_limits: RpcConcurrencyLimits::default(),
+            replicas: RpcReplicas::default(),
+            ready_max_blocks_behind: 10,
+            cors: CorsConfig::default(),
+            shutdown_delay_secs: 0,
+            shutdown_timeout_secs: 10,
+            access_log: false,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use axum::http::{
+    header::{HeaderName, CONTENT_TYPE},
+    HeaderValue,
+    Method,
+};
+use tower_http::cors::{Any, CorsLayer};
+
+/// The CORS policy of the REST server.
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    /// The allowed origins, or `None` to allow any origin.
+    origins: Option<Vec<HeaderValue>>,
+    /// The allowed methods.
+    methods: Vec<Method>,
+    /// The allowed request headers, or `None` to allow any header.
+    headers: Option<Vec<HeaderName>>,
+}
+
+impl CorsConfig {
+    /// Initializes a new CORS policy from the given origins, methods, and headers.
+    /// An empty list, or a list containing `*`, keeps the default for origins and methods,
+    /// and allows any header.
+    pub fn new(origins: &[String], methods: &[String], headers: &[String]) -> Result<Self> {
+        let is_default = |values: &[String]| values.is_empty() || values.iter().any(|value| value == "*");
+
+        let origins = match is_default(origins) {
+            true => None,
+            false => Some(
+                origins
+                    .iter()
+                    .map(|origin| HeaderValue::from_str(origin).map_err(|_| anyhow!("Invalid CORS origin '{origin}'")))
+                    .collect::<Result<_>>()?,
+            ),
+        };
+        let methods = match is_default(methods) {
+            true => Self::default().methods,
+            false => methods
+                .iter()
+                .map(|method| {
+                    Method::from_bytes(method.to_uppercase().as_bytes())
+                        .map_err(|_| anyhow!("Invalid CORS method '{method}'"))
+                })
+                .collect::<Result<_>>()?,
+        };
+        let headers = match headers.iter().any(|header| header == "*") {
+            true => None,
+            false => Some(
+                headers
+                    .iter()
+                    .map(|header| {
+                        HeaderName::from_bytes(header.as_bytes()).map_err(|_| anyhow!("Invalid CORS header '{header}'"))
+                    })
+                    .chain(std::iter::once(Ok(CONTENT_TYPE)))
+                    .collect::<Result<_>>()?,
+            ),
+        };
+        Ok(Self { origins, methods, headers })
+    }
+
+    /// Returns the CORS layer enforcing this policy.
+    pub(crate) fn layer(&self) -> CorsLayer {
+        let layer = CorsLayer::new().allow_methods(self.methods.clone());
+        let layer = match &self.origins {
+            Some(origins) => layer.allow_origin(origins.clone()),
+            None => layer.allow_origin(Any),
+        };
+        match &self.headers {
+            Some(headers) => layer.allow_headers(headers.clone()),
+            None => layer.allow_headers(Any),
+        }
+    }
+}
+
+impl Default for CorsConfig {
+    /// Initializes the default CORS policy, which allows `GET`, `POST`, and `OPTIONS` requests from any origin.
+    fn default() -> Self {
+        Self {
+            origins: None,
+            methods: vec![Method::GET, Method::POST, Method::OPTIONS],
+            headers: Some(vec![CONTENT_TYPE]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cors_config() {
+        // The empty policy is the default policy.
+        let config = CorsConfig::new(&[], &[], &[]).unwrap();
+        assert!(config.origins.is_none());
+        assert_eq!(config.methods, CorsConfig::default().methods);
+        assert_eq!(config.headers, Some(vec![CONTENT_TYPE]));
+
+        // A restricted policy.
+        let config =
+            CorsConfig::new(&values(&["https://wallet.example"]), &values(&["get"]), &values(&["authorization"]))
+                .unwrap();
+        assert_eq!(config.origins, Some(vec![HeaderValue::from_static("https://wallet.example")]));
+        assert_eq!(config.methods, vec![Method::GET]);
+        assert_eq!(config.headers, Some(vec![HeaderName::from_static("authorization"), CONTENT_TYPE]));
+
+        // Wildcards allow anything.
+        let config = CorsConfig::new(&values(&["*"]), &[], &values(&["*"])).unwrap();
+        assert!(config.origins.is_none());
+        assert!(config.headers.is_none());
+
+        // Invalid values are rejected.
+        assert!(CorsConfig::new(&values(&["bad\norigin"]), &[], &[]).is_err());
+        assert!(CorsConfig::new(&[], &[], &values(&["bad header"])).is_err());
+    }
+}
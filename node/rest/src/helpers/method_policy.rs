@@ -0,0 +1,132 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RPC_METHODS;
+
+use anyhow::{bail, Result};
+
+/// The group of a JSON-RPC method.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RpcMethodGroup {
+    /// Methods that read the ledger, the memory pool, or the network state.
+    Read,
+    /// Methods that serve wallets, such as fee estimation and transaction checks.
+    Wallet,
+    /// Methods that change the state of the node.
+    Control,
+    /// Methods that expose the internals of the node.
+    Debug,
+}
+
+impl RpcMethodGroup {
+    /// Returns the group with the given name, if it exists.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "read" => Some(Self::Read),
+            "wallet" => Some(Self::Wallet),
+            "control" => Some(Self::Control),
+            "debug" => Some(Self::Debug),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the group.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Wallet => "wallet",
+            Self::Control => "control",
+            Self::Debug => "debug",
+        }
+    }
+}
+
+/// Returns the group of the given JSON-RPC method, if the method exists.
+pub fn rpc_method_group(method: &str) -> Option<RpcMethodGroup> {
+    RPC_METHODS.iter().find(|rpc_method| rpc_method.name == method).map(|rpc_method| rpc_method.group)
+}
+
+/// The JSON-RPC methods that are enabled on the server.
+///
+/// Each entry is either the name of a method or the name of a method group.
+/// A method is enabled if the allowlist is empty or matches it, and the denylist does not match it.
+#[derive(Clone, Debug, Default)]
+pub struct RpcMethodPolicy {
+    /// The methods and groups that are enabled; if empty, every method is enabled.
+    allow: Vec<String>,
+    /// The methods and groups that are disabled, which takes precedence over the allowlist.
+    deny: Vec<String>,
+}
+
+impl RpcMethodPolicy {
+    /// Initializes a new policy from the given allowlist and denylist.
+    pub fn new(allow: Vec<String>, deny: Vec<String>) -> Result<Self> {
+        // Ensure every entry names a method or a group.
+        for entry in allow.iter().chain(deny.iter()) {
+            if rpc_method_group(entry).is_none() && RpcMethodGroup::from_name(entry).is_none() {
+                bail!("Unknown RPC method or method group '{entry}'");
+            }
+        }
+        Ok(Self { allow, deny })
+    }
+
+    /// Returns `true` if the given method is enabled.
+    pub fn is_enabled(&self, method: &str) -> bool {
+        let Some(group) = rpc_method_group(method) else {
+            return false;
+        };
+        let matches = |entry: &String| entry == method || RpcMethodGroup::from_name(entry) == Some(group);
+        (self.allow.is_empty() || self.allow.iter().any(matches)) && !self.deny.iter().any(matches)
+    }
+
+    /// Returns `true` if the given method is enabled by a matching allowlist entry, rather than an empty allowlist.
+    pub fn is_explicitly_allowed(&self, method: &str) -> bool {
+        !self.allow.is_empty() && self.is_enabled(method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rpc_method_policy() {
+        // The default policy enables every method.
+        let policy = RpcMethodPolicy::default();
+        assert!(RPC_METHODS.iter().all(|method| policy.is_enabled(method.name)));
+        assert!(!policy.is_enabled("unknown"));
+
+        // An allowlist of groups and methods.
+        let policy = RpcMethodPolicy::new(entries(&["read", "estimatefee"]), vec![]).unwrap();
+        assert!(policy.is_enabled("getblock"));
+        assert!(policy.is_enabled("estimatefee"));
+        assert!(!policy.is_enabled("validaterawtransaction"));
+
+        // The denylist takes precedence over the allowlist.
+        let policy = RpcMethodPolicy::new(entries(&["read"]), entries(&["scanblocks"])).unwrap();
+        assert!(policy.is_enabled("getblock"));
+        assert!(!policy.is_enabled("scanblocks"));
+        let policy = RpcMethodPolicy::new(vec![], entries(&["wallet"])).unwrap();
+        assert!(policy.is_enabled("getblock"));
+        assert!(!policy.is_enabled("estimatefee"));
+
+        // Unknown entries are rejected.
+        assert!(RpcMethodPolicy::new(entries(&["admin"]), vec![]).is_err());
+        assert!(RpcMethodPolicy::new(vec![], entries(&["getblok"])).is_err());
+    }
+}
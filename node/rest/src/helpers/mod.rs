@@ -12,8 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod access_log;
+pub use access_log::*;
+
+mod api_keys;
+pub use api_keys::*;
+
 mod auth;
 pub use auth::*;
 
+mod concurrency;
+pub use concurrency::*;
+
+mod config;
+pub use config::*;
+
+mod cors;
+pub use cors::*;
+
 mod error;
 pub use error::*;
+
+mod method_policy;
+pub use method_policy::*;
+
+mod rate_limit;
+pub use rate_limit::*;
+
+mod replicas;
+pub use replicas::*;
+
+mod rpc_spec;
+pub use rpc_spec::*;
+
+mod shutdown;
+pub(crate) use shutdown::*;
+
+mod tls;
+pub use tls::*;
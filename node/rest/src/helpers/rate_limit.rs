@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::BearerTokens;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        header::{AUTHORIZATION, RETRY_AFTER},
+        Request,
+        StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use governor::{
+    clock::{Clock, DefaultClock},
+    DefaultKeyedRateLimiter,
+    Quota,
+    RateLimiter,
+};
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
+
+/// Returns a `429 Too Many Requests` response, advising the client to retry after the given duration.
+pub fn too_many_requests(wait_time: Duration) -> Response {
+    // Round up to the nearest second, as `Retry-After` is in whole seconds.
+    let retry_after = wait_time.as_secs() + u64::from(wait_time.subsec_nanos() > 0);
+    (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, retry_after.to_string())], "Too many requests").into_response()
+}
+
+/// The per-token rate limiter of the REST server.
+///
+/// Only the configured static bearer tokens are rate limited by token, so that
+/// arbitrary unauthenticated tokens cannot grow the limiter state.
+#[derive(Clone)]
+pub struct TokenRateLimiter {
+    /// The static bearer tokens.
+    bearer_tokens: BearerTokens,
+    /// The keyed rate limiter, if enabled.
+    limiter: Option<Arc<DefaultKeyedRateLimiter<String>>>,
+}
+
+impl TokenRateLimiter {
+    /// Initializes a new per-token rate limiter, with the given requests per second and burst size.
+    /// A rate of `0` disables the limiter.
+    pub fn new(bearer_tokens: BearerTokens, rps: u32, burst: u32) -> Self {
+        let limiter = NonZeroU32::new(rps).map(|rps| {
+            let burst = NonZeroU32::new(burst).unwrap_or(rps);
+            Arc::new(RateLimiter::keyed(Quota::per_second(rps).allow_burst(burst)))
+        });
+        Self { bearer_tokens, limiter }
+    }
+}
+
+pub async fn token_rate_limit_middleware(
+    State(rate_limiter): State<TokenRateLimiter>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if let Some(limiter) = &rate_limiter.limiter {
+        // Retrieve the bearer token, if one was provided.
+        let token = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|token| rate_limiter.bearer_tokens.contains(token));
+        // Check the rate limit for the token.
+        if let Some(token) = token {
+            if let Err(not_until) = limiter.check_key(&token.to_string()) {
+                return too_many_requests(not_until.wait_time_from(DefaultClock::default().now()));
+            }
+        }
+    }
+    next.run(request).await
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use serde_json::{json, Value};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// The maximum duration of a call forwarded to a replica.
+const REPLICA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The replica JSON-RPC endpoints, which serve the heavy read methods when this node is at capacity.
+#[derive(Clone, Debug, Default)]
+pub struct RpcReplicas {
+    /// The JSON-RPC URLs of the replicas.
+    endpoints: Arc<[String]>,
+    /// The index of the next replica, as the replicas are used in turn.
+    next: Arc<AtomicUsize>,
+    /// The HTTP client.
+    client: reqwest::Client,
+}
+
+impl RpcReplicas {
+    /// Initializes the replicas from the given base URLs, such as `http://10.0.0.2:3030`.
+    pub fn new(urls: &[String]) -> Result<Self> {
+        for url in urls {
+            ensure!(
+                url.starts_with("http://") || url.starts_with("https://"),
+                "Invalid replica URL '{url}' (expected 'http://' or 'https://')"
+            );
+        }
+        let endpoints = urls.iter().map(|url| format!("{}/mainnet/rpc", url.trim_end_matches('/'))).collect();
+        let client = reqwest::Client::builder().timeout(REPLICA_TIMEOUT).build()?;
+        Ok(Self { endpoints, next: Default::default(), client })
+    }
+
+    /// Returns `true` if no replicas are configured.
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Forwards the given call to the next replica, returning the JSON-RPC response object of the replica.
+    pub async fn forward(&self, method: &str, params: &Value) -> Result<Value> {
+        ensure!(!self.is_empty(), "No replicas are configured");
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.endpoints.len();
+        let endpoint = &self.endpoints[index];
+        let request = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 0 });
+        let response = self.client.post(endpoint).json(&request).send().await?.error_for_status()?;
+        let response = response.json::<Value>().await?;
+        ensure!(
+            response.get("result").is_some() || response.get("error").is_some(),
+            "Invalid response from the replica '{endpoint}'"
+        );
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_replicas() {
+        let replicas = RpcReplicas::new(&["http://10.0.0.2:3030/".to_string(), "https://rpc.example".to_string()]);
+        let replicas = replicas.unwrap();
+        assert_eq!(&*replicas.endpoints, ["http://10.0.0.2:3030/mainnet/rpc", "https://rpc.example/mainnet/rpc"]);
+        assert!(RpcReplicas::default().is_empty());
+
+        // Invalid URLs are rejected.
+        assert!(RpcReplicas::new(&["10.0.0.2:3030".to_string()]).is_err());
+    }
+}
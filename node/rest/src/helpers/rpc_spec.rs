@@ -0,0 +1,435 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RpcMethodGroup;
+
+use serde_json::{json, Value};
+
+/// The version of the OpenRPC specification that the document follows.
+const OPENRPC_VERSION: &str = "1.2.6";
+
+/// The definition of a JSON-RPC method parameter.
+#[derive(Copy, Clone, Debug)]
+pub struct RpcParam {
+    /// The name of the parameter, as given in named parameters.
+    pub name: &'static str,
+    /// The JSON types that the parameter accepts.
+    pub types: &'static [&'static str],
+    /// Whether the parameter must be given.
+    pub required: bool,
+    /// A short description of the parameter.
+    pub description: &'static str,
+}
+
+/// The definition of a JSON-RPC method.
+#[derive(Copy, Clone, Debug)]
+pub struct RpcMethod {
+    /// The name of the method.
+    pub name: &'static str,
+    /// The group of the method.
+    pub group: RpcMethodGroup,
+    /// A short description of the method.
+    pub summary: &'static str,
+    /// The parameters of the method, in positional order.
+    pub params: &'static [RpcParam],
+    /// A short description of the result.
+    pub result: &'static str,
+}
+
+/// Returns a required parameter definition.
+const fn required(name: &'static str, types: &'static [&'static str], description: &'static str) -> RpcParam {
+    RpcParam { name, types, required: true, description }
+}
+
+/// Returns an optional parameter definition.
+const fn optional(name: &'static str, types: &'static [&'static str], description: &'static str) -> RpcParam {
+    RpcParam { name, types, required: false, description }
+}
+
+const INTEGER: &[&str] = &["integer"];
+const STRING: &[&str] = &["string"];
+const BOOLEAN: &[&str] = &["boolean"];
+const OBJECT: &[&str] = &["object"];
+
+/// The JSON-RPC methods served by the node.
+pub const RPC_METHODS: &[RpcMethod] = &[
+    RpcMethod {
+        name: "rpc.discover",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the OpenRPC document describing the JSON-RPC methods",
+        params: &[],
+        result: "The OpenRPC document",
+    },
+    RpcMethod {
+        name: "getstatus",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the node type, the latest block, and the sync status",
+        params: &[],
+        result: "The node status",
+    },
+    RpcMethod {
+        name: "latestheight",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the latest block height",
+        params: &[],
+        result: "The block height",
+    },
+    RpcMethod {
+        name: "latesthash",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the latest block hash",
+        params: &[],
+        result: "The block hash",
+    },
+    RpcMethod {
+        name: "latestblock",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the latest block",
+        params: &[],
+        result: "The block",
+    },
+    RpcMethod {
+        name: "getblock",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the block with the given height or hash",
+        params: &[required("height_or_hash", &["integer", "string"], "The block height or block hash")],
+        result: "The block",
+    },
+    RpcMethod {
+        name: "getblocks",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the blocks in the given range, or a page of blocks if no start height is given",
+        params: &[
+            optional("start", INTEGER, "The first block height, inclusive"),
+            optional("end", INTEGER, "The last block height, exclusive"),
+            optional("cursor", INTEGER, "The first block height of the page"),
+            optional("limit", INTEGER, "The maximum number of blocks in the page"),
+            optional("headers_only", BOOLEAN, "Whether to return the block headers instead of the blocks"),
+        ],
+        result: "The blocks, or a page of blocks",
+    },
+    RpcMethod {
+        name: "getblockstats",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the statistics of a block, or the aggregated statistics of a range of blocks",
+        params: &[
+            required("start", INTEGER, "The block height, or the first block height of the range"),
+            optional("end", INTEGER, "The last block height of the range, exclusive"),
+        ],
+        result: "The block statistics, or the aggregated statistics of the range",
+    },
+    RpcMethod {
+        name: "scanblocks",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the transactions in the given range that match the filters",
+        params: &[
+            required("start", INTEGER, "The first block height, inclusive"),
+            required("end", INTEGER, "The last block height, exclusive"),
+            optional("program_id", STRING, "The program ID that a transition must call"),
+            optional("address", STRING, "The address that a transaction must reference publicly"),
+            optional("commitment", STRING, "The record commitment that a transaction must output"),
+        ],
+        result: "The matching transactions",
+    },
+    RpcMethod {
+        name: "gettransaction",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the transaction with the given ID, along with its block metadata",
+        params: &[
+            required("id", STRING, "The transaction ID"),
+            optional("transitions", BOOLEAN, "Whether to include the transitions"),
+        ],
+        result: "The transaction and its metadata",
+    },
+    RpcMethod {
+        name: "getconfirmedtransaction",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the confirmed transaction with the given ID",
+        params: &[required("id", STRING, "The transaction ID")],
+        result: "The confirmed transaction",
+    },
+    RpcMethod {
+        name: "getledgerproof",
+        group: RpcMethodGroup::Read,
+        summary:
+            "Returns the state path of a record commitment, along with the headers of the blocks from its block onward",
+        params: &[
+            optional("commitment", STRING, "The record commitment to prove"),
+            optional("transaction_id", STRING, "The transaction to prove, through the first record it creates"),
+            optional("headers", INTEGER, "The maximum number of block headers to return"),
+        ],
+        result: "The ledger proof",
+    },
+    RpcMethod {
+        name: "getpeerinfo",
+        group: RpcMethodGroup::Read,
+        summary: "Returns information about the connected peers",
+        params: &[],
+        result: "The peer information",
+    },
+    RpcMethod {
+        name: "getnetworkinfo",
+        group: RpcMethodGroup::Read,
+        summary: "Returns information about the network state of the node",
+        params: &[],
+        result: "The network information",
+    },
+    RpcMethod {
+        name: "getblocktemplate",
+        group: RpcMethodGroup::Read,
+        summary: "Returns a template for the next block, including the coinbase challenge",
+        params: &[],
+        result: "The block template",
+    },
+    RpcMethod {
+        name: "waitfornewblock",
+        group: RpcMethodGroup::Read,
+        summary: "Waits until a new block is added to the ledger, or the timeout elapses",
+        params: &[optional("timeout", INTEGER, "The timeout, in seconds")],
+        result: "The latest block tip",
+    },
+    RpcMethod {
+        name: "waitforheight",
+        group: RpcMethodGroup::Read,
+        summary: "Waits until the ledger reaches the given height, or the timeout elapses",
+        params: &[
+            required("height", INTEGER, "The block height to wait for"),
+            optional("timeout", INTEGER, "The timeout, in seconds"),
+        ],
+        result: "The latest block tip",
+    },
+    RpcMethod {
+        name: "getmemorypoolinfo",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the size and fee statistics, and the retention policy, of the memory pool",
+        params: &[],
+        result: "The memory pool information",
+    },
+    RpcMethod {
+        name: "getmemorypooltransactions",
+        group: RpcMethodGroup::Read,
+        summary: "Returns a page of the transactions in the memory pool",
+        params: &[
+            optional("cursor", INTEGER, "The index of the first transaction in the page"),
+            optional("limit", INTEGER, "The maximum number of transactions in the page"),
+        ],
+        result: "A page of transactions",
+    },
+    RpcMethod {
+        name: "getmemorypooltransaction",
+        group: RpcMethodGroup::Read,
+        summary: "Returns the memory pool transaction with the given ID",
+        params: &[required("id", STRING, "The transaction ID")],
+        result: "The transaction",
+    },
+    RpcMethod {
+        name: "gettransactionsbyaddress",
+        group: RpcMethodGroup::Wallet,
+        summary: "Returns a page of the IDs of the transactions that reference the given address",
+        params: &[
+            required("address", STRING, "The address"),
+            optional("cursor", INTEGER, "The index of the first transaction in the page"),
+            optional("limit", INTEGER, "The maximum number of transactions in the page"),
+        ],
+        result: "A page of transaction IDs",
+    },
+    RpcMethod {
+        name: "getcommitmentsbyaddress",
+        group: RpcMethodGroup::Wallet,
+        summary: "Returns a page of the commitments of the records that the given address owns publicly",
+        params: &[
+            required("address", STRING, "The address"),
+            optional("cursor", INTEGER, "The index of the first commitment in the page"),
+            optional("limit", INTEGER, "The maximum number of commitments in the page"),
+        ],
+        result: "A page of record commitments",
+    },
+    RpcMethod {
+        name: "validateaddress",
+        group: RpcMethodGroup::Wallet,
+        summary: "Checks whether the given string is a well-formed address or view key",
+        params: &[required("address", STRING, "The address or view key")],
+        result: "The validation outcome, with the address and its network",
+    },
+    RpcMethod {
+        name: "validaterawtransaction",
+        group: RpcMethodGroup::Wallet,
+        summary: "Runs the memory pool acceptance checks on the given transaction, without broadcasting it",
+        params: &[required("transaction", OBJECT, "The transaction")],
+        result: "The outcome of each check",
+    },
+    RpcMethod {
+        name: "decoderawtransaction",
+        group: RpcMethodGroup::Wallet,
+        summary: "Decodes the given transaction bytes, without validating or broadcasting the transaction",
+        params: &[required("transaction", STRING, "The transaction bytes, as a hex string")],
+        result: "The decoded transaction",
+    },
+    RpcMethod {
+        name: "estimatefee",
+        group: RpcMethodGroup::Wallet,
+        summary: "Estimates the fee for a transaction to be included within the given number of blocks",
+        params: &[
+            optional("blocks", INTEGER, "The number of blocks to target"),
+            optional("size", INTEGER, "The size of the transaction, in bytes"),
+        ],
+        result: "The fee estimate",
+    },
+    RpcMethod {
+        name: "addnode",
+        group: RpcMethodGroup::Control,
+        summary: "Connects to the peer at the given address",
+        params: &[required("ip", STRING, "The socket address of the peer")],
+        result: "Whether the connection succeeded",
+    },
+    RpcMethod {
+        name: "disconnectnode",
+        group: RpcMethodGroup::Control,
+        summary: "Disconnects from the peer at the given address",
+        params: &[required("ip", STRING, "The socket address of the peer")],
+        result: "Whether the peer was disconnected",
+    },
+    RpcMethod {
+        name: "setban",
+        group: RpcMethodGroup::Control,
+        summary: "Bans or unbans the given IP address",
+        params: &[
+            required("ip", STRING, "The IP address"),
+            required("command", STRING, "Either 'add' or 'remove'"),
+            optional("duration_secs", INTEGER, "The duration of the ban, in seconds; if omitted, the ban is permanent"),
+        ],
+        result: "Nothing",
+    },
+    RpcMethod {
+        name: "listbanned",
+        group: RpcMethodGroup::Control,
+        summary: "Returns the banned IP addresses",
+        params: &[],
+        result: "The banned IP addresses",
+    },
+    RpcMethod {
+        name: "clearbanned",
+        group: RpcMethodGroup::Control,
+        summary: "Removes every IP address from the ban list",
+        params: &[],
+        result: "Nothing",
+    },
+    RpcMethod {
+        name: "getapikeyusage",
+        group: RpcMethodGroup::Control,
+        summary: "Returns the usage counters of each API key",
+        params: &[],
+        result: "The usage of each API key, by name",
+    },
+    RpcMethod {
+        name: "debug_dumppeers",
+        group: RpcMethodGroup::Debug,
+        summary: "Returns the internal state of each peer connection",
+        params: &[],
+        result: "The router state",
+    },
+    RpcMethod {
+        name: "debug_syncstate",
+        group: RpcMethodGroup::Debug,
+        summary: "Returns the internal state of block sync",
+        params: &[],
+        result: "The sync state",
+    },
+    RpcMethod {
+        name: "debug_taskstats",
+        group: RpcMethodGroup::Debug,
+        summary: "Returns the number of running and finished background tasks",
+        params: &[],
+        result: "The task counts",
+    },
+];
+
+impl RpcMethod {
+    /// Returns the OpenRPC method object for this method.
+    fn to_openrpc(self) -> Value {
+        let params = self
+            .params
+            .iter()
+            .map(|param| {
+                let schema = match param.types {
+                    [ty] => json!({ "type": ty }),
+                    types => json!({ "type": types }),
+                };
+                json!({
+                    "name": param.name,
+                    "description": param.description,
+                    "required": param.required,
+                    "schema": schema,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "name": self.name,
+            "summary": self.summary,
+            "tags": [{ "name": self.group.name() }],
+            "paramStructure": "either",
+            "params": params,
+            "result": { "name": "result", "description": self.result, "schema": {} },
+        })
+    }
+}
+
+/// Returns the OpenRPC document for the JSON-RPC methods enabled by the given filter.
+pub fn openrpc_document(is_enabled: impl Fn(&str) -> bool) -> Value {
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "snarkOS JSON-RPC",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [
+            { "name": "public", "url": "/mainnet/rpc" },
+            { "name": "private", "url": "/mainnet/node/rpc" },
+        ],
+        "methods": RPC_METHODS
+            .iter()
+            .filter(|method| is_enabled(method.name))
+            .map(|method| method.to_openrpc())
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_openrpc_document() {
+        // Ensure the method names are unique.
+        let names = RPC_METHODS.iter().map(|method| method.name).collect::<HashSet<_>>();
+        assert_eq!(names.len(), RPC_METHODS.len());
+
+        // Ensure the required parameters precede the optional ones, so they can be given positionally.
+        for method in RPC_METHODS {
+            let first_optional = method.params.iter().position(|param| !param.required).unwrap_or(method.params.len());
+            assert!(method.params[first_optional..].iter().all(|param| !param.required), "{}", method.name);
+        }
+
+        // Ensure the document only lists the enabled methods.
+        let document = openrpc_document(|method| method != "setban");
+        let methods = document["methods"].as_array().unwrap();
+        assert_eq!(methods.len(), RPC_METHODS.len() - 1);
+        assert!(methods.iter().all(|method| method["name"] != "setban"));
+        let getblock = methods.iter().find(|method| method["name"] == "getblock").unwrap();
+        assert_eq!(getblock["params"][0]["schema"]["type"], json!(["integer", "string"]));
+        assert_eq!(getblock["tags"][0]["name"], "read");
+    }
+}
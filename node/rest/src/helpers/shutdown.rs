@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::{sync::Notify, task::JoinHandle};
+
+/// The graceful shutdown state of the REST server.
+#[derive(Debug, Default)]
+pub(crate) struct ShutdownState {
+    /// Whether the server is draining, in which case it reports as not ready.
+    is_draining: AtomicBool,
+    /// Whether the server was signaled to stop accepting connections.
+    is_signaled: AtomicBool,
+    /// The signal for the listeners to stop accepting connections.
+    signal: Notify,
+    /// The handle of the task serving the connections.
+    server: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ShutdownState {
+    /// Returns `true` if the server is draining.
+    pub(crate) fn is_draining(&self) -> bool {
+        self.is_draining.load(Ordering::SeqCst)
+    }
+
+    /// Marks the server as draining.
+    pub(crate) fn start_draining(&self) {
+        self.is_draining.store(true, Ordering::SeqCst);
+    }
+
+    /// Signals every listener to stop accepting connections.
+    pub(crate) fn signal(&self) {
+        self.is_signaled.store(true, Ordering::SeqCst);
+        self.signal.notify_waiters();
+    }
+
+    /// Waits until the server is signaled to stop accepting connections.
+    pub(crate) async fn wait_for_signal(&self) {
+        // Note: The future is created before checking the flag, so a signal in between is not lost.
+        let notified = self.signal.notified();
+        if self.is_signaled.load(Ordering::SeqCst) {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Sets the handle of the task serving the connections.
+    pub(crate) fn set_server(&self, handle: JoinHandle<()>) {
+        *self.server.lock() = Some(handle);
+    }
+
+    /// Takes the handle of the task serving the connections.
+    pub(crate) fn take_server(&self) -> Option<JoinHandle<()>> {
+        self.server.lock().take()
+    }
+}
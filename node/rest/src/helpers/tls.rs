@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, Result};
+use rustls::{server::AllowAnyAuthenticatedClient, Certificate, PrivateKey, RootCertStore, ServerConfig};
+use std::{fs::File, io::BufReader, path::PathBuf, sync::Arc};
+
+/// The TLS configuration of the REST server.
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    /// The path to the PEM-encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// The path to the PEM-encoded private key.
+    pub key_path: PathBuf,
+    /// The path to the PEM-encoded CA certificates used to verify client certificates, if client
+    /// authentication is required.
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Loads the certificates and keys, and returns the corresponding server configuration.
+    pub(crate) fn load(&self) -> Result<Arc<ServerConfig>> {
+        // Load the certificate chain and private key.
+        let certs = load_certs(&self.cert_path)?;
+        let key = load_private_key(&self.key_path)?;
+
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let mut config = match &self.client_ca_path {
+            // Require clients to present a certificate signed by one of the given CAs.
+            Some(client_ca_path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in load_certs(client_ca_path)? {
+                    roots.add(&cert)?;
+                }
+                builder
+                    .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+                    .with_single_cert(certs, key)?
+            }
+            None => builder.with_no_client_auth().with_single_cert(certs, key)?,
+        };
+        // Advertise HTTP/2 and HTTP/1.1.
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(Arc::new(config))
+    }
+}
+
+/// Loads the PEM-encoded certificates from the given path.
+fn load_certs(path: &PathBuf) -> Result<Vec<Certificate>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open '{}': {e}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))?;
+    if certs.is_empty() {
+        bail!("No certificates found in '{}'", path.display());
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Loads the first PEM-encoded (PKCS#8, PKCS#1, or SEC1) private key from the given path.
+fn load_private_key(path: &PathBuf) -> Result<PrivateKey> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open '{}': {e}", path.display()))?;
+    for item in rustls_pemfile::read_all(&mut BufReader::new(file))? {
+        match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => return Ok(PrivateKey(key)),
+            _ => continue,
+        }
+    }
+    bail!("No private key found in '{}'", path.display())
+}
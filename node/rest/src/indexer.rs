@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The interval at which the ledger is checked for blocks to index, in milliseconds.
+const INDEX_POLL_INTERVAL_IN_MS: u64 = 1_000;
+/// The maximum number of blocks indexed before yielding to the runtime.
+const MAX_BLOCKS_PER_INDEX_PASS: u32 = 100;
+
+/// An index that is built from the blocks of the ledger, in ledger order.
+pub(crate) trait BlockIndex<N: Network>: Send + Sync + 'static {
+    /// The name of the index, used in the logs.
+    const NAME: &'static str;
+
+    /// Returns the next block height to index.
+    fn next_height(&self) -> u32;
+
+    /// Indexes the given block, which must be at the next block height.
+    fn index_block(&self, block: &Block<N>) -> Result<()>;
+}
+
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Spawns the task that keeps the given index up to date with the ledger,
+    /// starting with a backfill from the next height of the index.
+    pub(crate) fn spawn_indexer<I: BlockIndex<N>>(&self, index: Arc<I>) {
+        let ledger = self.ledger.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            info!("Building the {} from block {}...", I::NAME, index.next_height());
+            let mut interval = tokio::time::interval(Duration::from_millis(INDEX_POLL_INTERVAL_IN_MS));
+            loop {
+                interval.tick().await;
+                // Index the next blocks, in bounded passes so the index becomes usable as it catches up.
+                loop {
+                    let (index, ledger) = (index.clone(), ledger.clone());
+                    let result = tokio::task::spawn_blocking(move || {
+                        let start_height = index.next_height();
+                        let end_height =
+                            ledger.latest_height().min(start_height.saturating_add(MAX_BLOCKS_PER_INDEX_PASS));
+                        for height in start_height..=end_height {
+                            index.index_block(&ledger.get_block(height)?)?;
+                        }
+                        Ok::<_, anyhow::Error>(end_height >= ledger.latest_height())
+                    })
+                    .await;
+                    match result {
+                        Ok(Ok(true)) => break,
+                        Ok(Ok(false)) => continue,
+                        Ok(Err(error)) => {
+                            warn!("Failed to update the {} - {error}", I::NAME);
+                            break;
+                        }
+                        Err(error) => {
+                            error!("The {} indexer panicked - {error}", I::NAME);
+                            return;
+                        }
+                    }
+                }
+            }
+        }));
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{
+    block::{Header, Output},
+    StatePath,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of block headers returned with a ledger proof.
+pub const MAX_LEDGER_PROOF_HEADERS: u32 = 100;
+
+/// The `get_ledger_proof` query object. Exactly one of `commitment` and `transaction_id` must be given.
+#[derive(Deserialize, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct LedgerProofQuery<N: Network> {
+    /// The record commitment to prove.
+    commitment: Option<Field<N>>,
+    /// The transaction to prove, through the first record it creates.
+    transaction_id: Option<N::TransactionID>,
+    /// The maximum number of block headers to return, starting from the block containing the commitment.
+    headers: Option<u32>,
+}
+
+/// A block header, along with the hashes that link it into the chain.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct LinkedHeader<N: Network> {
+    /// The block height.
+    height: u32,
+    /// The block hash.
+    hash: N::BlockHash,
+    /// The hash of the previous block.
+    previous_hash: N::BlockHash,
+    /// The block header.
+    header: Header<N>,
+}
+
+/// A ledger inclusion proof for a record commitment.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct LedgerProof<N: Network> {
+    /// The proven record commitment.
+    commitment: Field<N>,
+    /// The ID of the transaction that created the record.
+    transaction_id: N::TransactionID,
+    /// The height of the block containing the transaction.
+    block_height: u32,
+    /// The state path from the commitment to the global state root.
+    state_path: StatePath<N>,
+    /// The headers of the consecutive blocks starting at `block_height`.
+    headers: Vec<LinkedHeader<N>>,
+    /// The latest block height.
+    latest_height: u32,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/ledgerProof?commitment={commitment}&headers={count}
+    // GET /mainnet/ledgerProof?transaction_id={transactionID}&headers={count}
+    pub(crate) async fn get_ledger_proof(
+        State(rest): State<Self>,
+        Query(query): Query<LedgerProofQuery<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.ledger_proof(&query)?))
+    }
+
+    /// Returns the ledger inclusion proof for the given commitment or transaction, along with a header chain segment.
+    pub(crate) fn ledger_proof(&self, query: &LedgerProofQuery<N>) -> Result<LedgerProof<N>, RestError> {
+        // Ensure the number of headers is bounded.
+        let num_headers = query.headers.unwrap_or(MAX_LEDGER_PROOF_HEADERS);
+        if num_headers == 0 || num_headers > MAX_LEDGER_PROOF_HEADERS {
+            return Err(RestError(format!(
+                "The number of headers must be between 1 and {MAX_LEDGER_PROOF_HEADERS} (requested {num_headers})"
+            )));
+        }
+        // Determine the commitment to prove.
+        let commitment = match (query.commitment, query.transaction_id) {
+            (Some(commitment), None) => commitment,
+            (None, Some(transaction_id)) => {
+                let transaction = self.ledger.get_transaction(transaction_id)?;
+                let commitment = transaction.transitions().flat_map(|transition| transition.outputs()).find_map(
+                    |output| match output {
+                        Output::Record(commitment, ..) => Some(*commitment),
+                        _ => None,
+                    },
+                );
+                commitment.ok_or_else(|| {
+                    RestError(format!("Transaction '{transaction_id}' does not create a record to prove"))
+                })?
+            }
+            _ => return Err(RestError("Expected exactly one of 'commitment' and 'transaction_id'".to_string())),
+        };
+
+        // Compute the state path, and locate the block that contains the commitment.
+        let state_path = self.state_path_cache.get(&self.ledger, &commitment)?;
+        let transaction_id = *state_path.transaction_id();
+        let block_height = self.ledger.get_height(state_path.block_hash())?;
+
+        // Collect the header chain segment, from the block containing the commitment towards the tip.
+        let latest_height = self.ledger.latest_height();
+        let end_height = latest_height.min(block_height.saturating_add(num_headers - 1));
+        let headers = (block_height..=end_height)
+            .map(|height| {
+                Ok(LinkedHeader {
+                    height,
+                    hash: self.ledger.get_hash(height)?,
+                    previous_hash: self.ledger.get_previous_hash(height)?,
+                    header: self.ledger.get_header(height)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(LedgerProof { commitment, transaction_id, block_height, state_path, headers, latest_height })
+    }
+}
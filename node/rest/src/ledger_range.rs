@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::block::{Header, Transaction};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, ops::Range};
+
+/// The number of items a ledger range loads at once, in parallel.
+const RANGE_BATCH_SIZE: u32 = 16;
+
+/// An iterator over the items of a block height range, which loads the items in parallel batches.
+/// The ledger has no range reads, so each item is still a point lookup. The iterator stops after the first error.
+pub(crate) struct LedgerRange<N: Network, C: ConsensusStorage<N>, T> {
+    /// The ledger.
+    ledger: Ledger<N, C>,
+    /// The heights that are left to load.
+    heights: Range<u32>,
+    /// Loads the item at the given height.
+    load: fn(&Ledger<N, C>, u32) -> Result<T>,
+    /// The loaded items that are left to return.
+    buffer: VecDeque<Result<T>>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, T: Send> LedgerRange<N, C, T> {
+    /// Initializes a range over the given heights, bounded by the latest height.
+    fn new(ledger: &Ledger<N, C>, heights: Range<u32>, load: fn(&Ledger<N, C>, u32) -> Result<T>) -> Self {
+        let end = heights.end.min(ledger.latest_height().saturating_add(1));
+        Self { ledger: ledger.clone(), heights: heights.start.min(end)..end, load, buffer: Default::default() }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, T: Send> Iterator for LedgerRange<N, C, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Load the next batch, once the previous one is returned.
+        if self.buffer.is_empty() && !self.heights.is_empty() {
+            let batch_end = self.heights.start.saturating_add(RANGE_BATCH_SIZE).min(self.heights.end);
+            let (ledger, load) = (&self.ledger, self.load);
+            self.buffer = cfg_into_iter!((self.heights.start..batch_end))
+                .map(|height| load(ledger, height))
+                .collect::<Vec<_>>()
+                .into();
+            self.heights.start = batch_end;
+        }
+        let item = self.buffer.pop_front()?;
+        if item.is_err() {
+            self.buffer.clear();
+            self.heights.start = self.heights.end;
+        }
+        Some(item)
+    }
+}
+
+/// Returns an iterator over the blocks in the given height range.
+pub(crate) fn blocks<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    heights: Range<u32>,
+) -> LedgerRange<N, C, Block<N>> {
+    LedgerRange::new(ledger, heights, |ledger, height| ledger.get_block(height))
+}
+
+/// Returns an iterator over the block headers in the given height range.
+pub(crate) fn headers<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    heights: Range<u32>,
+) -> LedgerRange<N, C, Header<N>> {
+    LedgerRange::new(ledger, heights, |ledger, height| ledger.get_header(height))
+}
+
+/// Returns an iterator over the fields selected from the transactions in the given height range,
+/// with the heights of their blocks.
+fn block_fields<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    heights: Range<u32>,
+    select: fn(&Transaction<N>) -> Vec<Field<N>>,
+) -> impl Iterator<Item = Result<(u32, Field<N>)>> {
+    blocks(ledger, heights).flat_map(move |block| match block {
+        Ok(block) => block
+            .transactions()
+            .iter()
+            .flat_map(|confirmed| select(confirmed.transaction()))
+            .map(|field| Ok((block.height(), field)))
+            .collect::<Vec<_>>(),
+        Err(error) => vec![Err(error)],
+    })
+}
+
+/// Returns an iterator over the record commitments created in the given height range.
+pub(crate) fn commitments<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    heights: Range<u32>,
+) -> impl Iterator<Item = Result<(u32, Field<N>)>> {
+    block_fields(ledger, heights, |transaction| transaction.commitments().copied().collect())
+}
+
+/// Returns an iterator over the serial numbers spent in the given height range.
+pub(crate) fn serial_numbers<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    heights: Range<u32>,
+) -> impl Iterator<Item = Result<(u32, Field<N>)>> {
+    block_fields(ledger, heights, |transaction| transaction.serial_numbers().copied().collect())
+}
+
+/// The `get_block_commitments` and `get_block_serial_numbers` query object.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub(crate) struct FieldRangeQuery {
+    /// The starting block height (inclusive).
+    pub(crate) start: u32,
+    /// The ending block height (exclusive).
+    pub(crate) end: u32,
+}
+
+/// A field of a transaction, with the height of its block.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct BlockField<N: Network> {
+    /// The height of the block.
+    block_height: u32,
+    /// The field.
+    field: Field<N>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/blocks/commitments?start={start_height}&end={end_height}
+    pub(crate) async fn get_block_commitments(
+        State(rest): State<Self>,
+        Query(query): Query<FieldRangeQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Self::ensure_field_range(query)?;
+        // Load the blocks off the async runtime.
+        let ledger = rest.ledger.clone();
+        let fields =
+            tokio::task::spawn_blocking(move || Self::collect_fields(commitments(&ledger, query.start..query.end)))
+                .await
+                .map_err(|e| RestError(format!("Failed to collect the commitments - {e}")))?;
+        Ok(ErasedJson::pretty(fields?))
+    }
+
+    // GET /mainnet/blocks/serialNumbers?start={start_height}&end={end_height}
+    pub(crate) async fn get_block_serial_numbers(
+        State(rest): State<Self>,
+        Query(query): Query<FieldRangeQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Self::ensure_field_range(query)?;
+        // Load the blocks off the async runtime.
+        let ledger = rest.ledger.clone();
+        let fields =
+            tokio::task::spawn_blocking(move || Self::collect_fields(serial_numbers(&ledger, query.start..query.end)))
+                .await
+                .map_err(|e| RestError(format!("Failed to collect the serial numbers - {e}")))?;
+        Ok(ErasedJson::pretty(fields?))
+    }
+
+    /// Ensures the given block range is valid, and bounded by the scan range.
+    fn ensure_field_range(query: FieldRangeQuery) -> Result<(), RestError> {
+        if query.start > query.end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+        if query.end - query.start > MAX_SCAN_RANGE {
+            return Err(RestError(format!(
+                "Cannot scan more than {MAX_SCAN_RANGE} blocks per call (requested {})",
+                query.end - query.start
+            )));
+        }
+        Ok(())
+    }
+
+    /// Collects the given fields, with the heights of their blocks.
+    fn collect_fields(fields: impl Iterator<Item = Result<(u32, Field<N>)>>) -> Result<Vec<BlockField<N>>, RestError> {
+        Ok(fields
+            .map(|field| field.map(|(block_height, field)| BlockField { block_height, field }))
+            .collect::<Result<_>>()?)
+    }
+}
@@ -17,27 +17,102 @@
 #[macro_use]
 extern crate tracing;
 
+mod address_index;
+pub use address_index::MAX_ADDRESS_TRANSACTIONS_PER_PAGE;
+
+mod block_stats;
+pub use block_stats::MAX_BLOCK_STATS_RANGE;
+
+mod block_template;
+pub use block_template::MAX_TEMPLATE_TRANSACTIONS;
+
+#[cfg(feature = "graphql")]
+mod graphql;
+
+#[cfg(feature = "grpc")]
+mod grpc;
+
+mod control;
+
+mod debug;
+
+mod decode;
+
+mod export;
+pub use export::MAX_EXPORT_RANGE;
+
+mod fee_estimate;
+pub use fee_estimate::MAX_FEE_TARGET_BLOCKS;
+
+mod health;
+
+mod indexer;
+
 mod helpers;
 pub use helpers::*;
 
+mod ledger_proof;
+pub use ledger_proof::MAX_LEDGER_PROOF_HEADERS;
+
+mod ledger_range;
+
+mod participation;
+
+#[cfg(feature = "metrics")]
+mod prometheus;
+
 mod routes;
+pub use routes::{MAX_BLOCKS_PER_PAGE, MAX_HEADERS_PER_PAGE, MAX_MEMORY_POOL_TRANSACTIONS_PER_PAGE};
+
+mod rpc;
+pub use rpc::MAX_BATCH_SIZE;
+
+mod scan;
+pub use scan::MAX_SCAN_RANGE;
+
+mod state_path_cache;
+
+mod template_cache;
+
+mod transaction_index;
+pub use transaction_index::transaction_index_path;
 
+mod v1;
+
+mod wait;
+pub use wait::MAX_WAIT_TIMEOUT_IN_SECS;
+
+#[cfg(unix)]
+mod unix_socket;
+
+mod validation;
+
+mod websocket;
+
+use address_index::AddressIndex;
+use indexer::BlockIndex;
+use snarkos_node_bft_ledger_service::LedgerCache;
 use snarkos_node_consensus::Consensus;
 use snarkos_node_router::{
     messages::{Message, UnconfirmedTransaction},
     Routing,
 };
+use snarkos_node_sync::BlockSync;
 use snarkvm::{
     console::{program::ProgramID, types::Field},
     ledger::narwhal::Data,
-    prelude::{cfg_into_iter, store::ConsensusStorage, Ledger, Network},
+    prelude::{cfg_into_iter, store::ConsensusStorage, Block, Ledger, Network},
 };
+use state_path_cache::StatePathCache;
+use template_cache::BlockTemplateCache;
+use transaction_index::{TransactionIndex, TransactionLocation};
+use websocket::WebSocketState;
 
 use anyhow::Result;
 use axum::{
     body::Body,
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    http::{header::CONTENT_TYPE, Request, StatusCode},
     middleware,
     middleware::Next,
     response::Response,
@@ -45,14 +120,12 @@ use axum::{
     Json,
 };
 use axum_extra::response::ErasedJson;
+use axum_server::tls_rustls::RustlsConfig;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{net::TcpListener, task::JoinHandle};
-use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
-use tower_http::{
-    cors::{Any, CorsLayer},
-    trace::TraceLayer,
-};
+use tower_governor::{governor::GovernorConfigBuilder, GovernorError, GovernorLayer};
+use tower_http::{compression::CompressionLayer, trace::TraceLayer};
 
 /// A REST API server for the ledger.
 #[derive(Clone)]
@@ -61,8 +134,26 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     consensus: Option<Consensus<N>>,
     /// The ledger.
     ledger: Ledger<N, C>,
+    /// The cache of the recently read blocks and headers.
+    ledger_cache: LedgerCache<N>,
+    /// The cache of the recently computed state paths.
+    state_path_cache: Arc<StatePathCache<N>>,
+    /// The cache of the candidate transactions of the block template.
+    template_cache: Arc<BlockTemplateCache<N>>,
+    /// The state shared by the WebSocket connections.
+    websocket: Arc<WebSocketState<N>>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The block sync module.
+    sync: BlockSync<N>,
+    /// The address index, if enabled.
+    address_index: Option<Arc<AddressIndex<N>>>,
+    /// The transaction index, if enabled.
+    transaction_index: Option<Arc<TransactionIndex<N>>>,
+    /// The server configuration.
+    config: RestConfig,
+    /// The graceful shutdown state.
+    shutdown: Arc<ShutdownState>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -71,15 +162,66 @@ impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R>
     /// Initializes a new instance of the server.
     pub async fn start(
         rest_ip: SocketAddr,
-        rest_rps: u32,
+        config: RestConfig,
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
+        ledger_cache: LedgerCache<N>,
         routing: Arc<R>,
+        sync: BlockSync<N>,
     ) -> Result<Self> {
+        // Initialize the address index, if enabled.
+        let address_index = config.address_index.then(|| Arc::new(AddressIndex::new()));
+        // Initialize the transaction index, if enabled.
+        let transaction_index = match (config.transaction_index, &config.transaction_index_path) {
+            (true, Some(path)) => Some(Arc::new(TransactionIndex::open(path)?)),
+            (true, None) => Some(Arc::new(TransactionIndex::new())),
+            (false, _) => None,
+        };
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            ledger_cache,
+            state_path_cache: Arc::new(StatePathCache::new()),
+            template_cache: Arc::new(BlockTemplateCache::new()),
+            websocket: Arc::new(WebSocketState::new()),
+            routing,
+            sync,
+            address_index,
+            transaction_index,
+            config,
+            shutdown: Default::default(),
+            handles: Default::default(),
+        };
         // Spawn the server.
-        server.spawn_server(rest_ip, rest_rps).await;
+        server.spawn_server(rest_ip).await?;
+        // Spawn the indexers, if enabled.
+        if let Some(address_index) = &server.address_index {
+            server.spawn_indexer(address_index.clone());
+        }
+        if let Some(transaction_index) = &server.transaction_index {
+            server.spawn_indexer(transaction_index.clone());
+        }
+        // Spawn the broadcaster of the new blocks to the WebSocket subscribers.
+        server.spawn_block_broadcaster();
+        // Spawn the block template refresher, if the node runs consensus.
+        if let Some(consensus) = server.consensus.clone() {
+            server.spawn_template_refresher(consensus);
+        }
+        // Spawn the Unix socket server, if configured.
+        if let Some(path) = server.config.unix_socket.clone() {
+            #[cfg(unix)]
+            server.spawn_unix_socket_server(&path)?;
+            #[cfg(not(unix))]
+            warn!("Ignoring the Unix socket '{}', as Unix sockets are not supported on this platform", path.display());
+        }
+        // Spawn the gRPC server, if configured.
+        if let Some(grpc_ip) = server.config.grpc {
+            #[cfg(feature = "grpc")]
+            server.spawn_grpc_server(grpc_ip)?;
+            #[cfg(not(feature = "grpc"))]
+            warn!("Ignoring the gRPC address '{grpc_ip}', as the node was built without the 'grpc' feature");
+        }
         // Return the server.
         Ok(server)
     }
@@ -91,18 +233,43 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         &self.ledger
     }
 
+    /// Returns the server configuration.
+    pub const fn config(&self) -> &RestConfig {
+        &self.config
+    }
+
     /// Returns the handles.
     pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
         &self.handles
     }
+
+    /// Shuts down the server gracefully: it reports as not ready for the configured delay,
+    /// then stops accepting connections, and waits for the in-flight requests until the configured timeout.
+    pub async fn shut_down(&self) {
+        // Fail the readiness probe first, so load balancers stop routing new requests to this node.
+        self.shutdown.start_draining();
+        if self.config.shutdown_delay_secs > 0 {
+            debug!("REST server is draining for {}s...", self.config.shutdown_delay_secs);
+            tokio::time::sleep(Duration::from_secs(self.config.shutdown_delay_secs)).await;
+        }
+        // Stop accepting connections, and wait for the in-flight requests to complete.
+        self.shutdown.signal();
+        if let Some(server) = self.shutdown.take_server() {
+            let timeout = Duration::from_secs(self.config.shutdown_timeout_secs);
+            if tokio::time::timeout(timeout, server).await.is_err() {
+                warn!("REST server did not finish the in-flight requests within {}s", timeout.as_secs());
+            }
+        }
+        // Abort the remaining tasks.
+        self.handles.lock().iter().for_each(|handle| handle.abort());
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_rps: u32) {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
+    async fn spawn_server(&mut self, rest_ip: SocketAddr) -> Result<()> {
+        let rest_rps = self.config.rps;
+
+        let cors = self.config.cors.layer();
 
         // Log the REST rate limit per IP.
         debug!("REST rate limit per IP - {rest_rps} RPS");
@@ -112,17 +279,38 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             GovernorConfigBuilder::default()
                 .per_second(1)
                 .burst_size(rest_rps)
-                .error_handler(|error| Response::new(error.to_string().into()))
+                .error_handler(|error| match error {
+                    GovernorError::TooManyRequests { wait_time, .. } => {
+                        too_many_requests(Duration::from_secs(wait_time))
+                    }
+                    error => Response::new(error.to_string().into()),
+                })
                 .finish()
                 .expect("Couldn't set up rate limiting for the REST server!"),
         );
 
+        // Prepare the static bearer tokens, and their rate limiter.
+        let bearer_tokens = BearerTokens::new(&self.config.bearer_tokens);
+        let token_rate_limiter =
+            TokenRateLimiter::new(bearer_tokens.clone(), self.config.token_rps, self.config.token_burst);
+        // Prepare the concurrency limit of the routes that share an expensive JSON-RPC method.
+        let rpc_limits = self.config.rpc_limits.clone();
+        let rpc_limit = |method: &'static str| {
+            middleware::from_fn_with_state((rpc_limits.clone(), method), rpc_concurrency_middleware)
+        };
+
         let router = {
             axum::Router::new()
 
-            // All the endpoints before the call to `route_layer` are protected with JWT auth.
+            // All the endpoints before the call to `route_layer` are private, and protected with JWT or bearer auth.
             .route("/mainnet/node/address", get(Self::get_node_address))
-            .route_layer(middleware::from_fn(auth_middleware))
+            .route("/mainnet/node/rpc", post(Self::private_rpc))
+            .route("/mainnet/node/apiKeys", get(Self::get_api_key_usage))
+            .route("/mainnet/node/debug/peers", get(Self::get_debug_peers))
+            .route("/mainnet/node/debug/sync", get(Self::get_debug_sync))
+            .route("/mainnet/node/debug/tasks", get(Self::get_debug_tasks))
+            .route("/mainnet/node/validator/participation", get(Self::get_validator_participation))
+            .route_layer(middleware::from_fn_with_state(bearer_tokens.clone(), auth_middleware))
 
             // ----------------- DEPRECATED ROUTES -----------------
             // The following `GET ../latest/..` routes will be removed before mainnet.
@@ -144,15 +332,25 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/mainnet/block/height/latest", get(Self::get_block_height_latest))
             .route("/mainnet/block/hash/latest", get(Self::get_block_hash_latest))
             .route("/mainnet/block/latest", get(Self::get_block_latest))
+            .route("/mainnet/block/template", get(Self::get_block_template))
             .route("/mainnet/block/:height_or_hash", get(Self::get_block))
             // The path param here is actually only the height, but the name must match the route
             // above, otherwise there'll be a conflict at runtime.
             .route("/mainnet/block/:height_or_hash/transactions", get(Self::get_block_transactions))
+            .route(
+                "/mainnet/block/:height_or_hash/stats",
+                get(Self::get_block_stats).route_layer(rpc_limit("getblockstats")),
+            )
 
             // GET and POST ../transaction/..
             .route("/mainnet/transaction/:id", get(Self::get_transaction))
             .route("/mainnet/transaction/confirmed/:id", get(Self::get_confirmed_transaction))
             .route("/mainnet/transaction/broadcast", post(Self::transaction_broadcast))
+            .route(
+                "/mainnet/transaction/validate",
+                post(Self::transaction_validate).route_layer(rpc_limit("validaterawtransaction")),
+            )
+            .route("/mainnet/transaction/decode", post(Self::transaction_decode))
 
             // POST ../solution/broadcast
             .route("/mainnet/solution/broadcast", post(Self::solution_broadcast))
@@ -167,6 +365,43 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/mainnet/peers/count", get(Self::get_peers_count))
             .route("/mainnet/peers/all", get(Self::get_peers_all))
             .route("/mainnet/peers/all/metrics", get(Self::get_peers_all_metrics))
+            .route("/mainnet/peers/info", get(Self::get_peers_info))
+
+            // GET ../network/..
+            .route("/mainnet/network/info", get(Self::get_network_info))
+
+            // GET ../sync/..
+            .route("/mainnet/sync/progress", get(Self::get_sync_progress))
+
+            // GET ../wait/..
+            .route("/mainnet/wait/newBlock", get(Self::get_wait_for_new_block))
+            .route("/mainnet/wait/height/:height", get(Self::get_wait_for_height))
+
+            // POST ../rpc (JSON-RPC 2.0, single or batch)
+            .route("/mainnet/rpc", post(Self::rpc))
+            // GET ../rpc/spec (OpenRPC document)
+            .route("/mainnet/rpc/spec", get(Self::get_rpc_spec))
+
+            // GET /v1/.. (REST facade over the JSON-RPC methods)
+            .route("/v1/status", get(Self::v1_status))
+            .route("/v1/block/:height_or_hash", get(Self::v1_block))
+            .route("/v1/blocks", get(Self::v1_blocks))
+            .route("/v1/transaction/:id", get(Self::v1_transaction))
+            .route("/v1/address/:address/transactions", get(Self::v1_address_transactions))
+            .route("/v1/peers", get(Self::v1_peers))
+            .route("/v1/network", get(Self::v1_network))
+            .route("/v1/mempool", get(Self::v1_mempool))
+            .route("/v1/mempool/transactions", get(Self::v1_mempool_transactions))
+            .route("/v1/mempool/transaction/:id", get(Self::v1_mempool_transaction))
+
+            // GET ../ws (WebSocket subscriptions)
+            .route("/mainnet/ws", get(Self::websocket))
+
+            // GET ../address/..
+            .route("/mainnet/address/:address/transactions", get(Self::get_transactions_by_address))
+            .route("/mainnet/address/:address/commitments", get(Self::get_commitments_by_address))
+            // POST ../address/validate
+            .route("/mainnet/address/validate", post(Self::address_validate))
 
             // GET ../program/..
             .route("/mainnet/program/:id", get(Self::get_program))
@@ -174,15 +409,42 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/mainnet/program/:id/mapping/:name/:key", get(Self::get_mapping_value))
 
             // GET misc endpoints.
-            .route("/mainnet/blocks", get(Self::get_blocks))
+            .route("/mainnet/blocks", get(Self::get_blocks).route_layer(rpc_limit("getblocks")))
+            .route("/mainnet/blocks/page", get(Self::get_block_page).route_layer(rpc_limit("getblocks")))
+            .route("/mainnet/blocks/scan", get(Self::get_scan_blocks).route_layer(rpc_limit("scanblocks")))
+            .route("/mainnet/blocks/export", get(Self::get_blocks_export))
+            .route("/mainnet/blocks/commitments", get(Self::get_block_commitments))
+            .route("/mainnet/blocks/serialNumbers", get(Self::get_block_serial_numbers))
+            .route("/mainnet/blocks/stats", get(Self::get_block_stats_range).route_layer(rpc_limit("getblockstats")))
             .route("/mainnet/height/:hash", get(Self::get_height))
             .route("/mainnet/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
             .route("/mainnet/memoryPool/solutions", get(Self::get_memory_pool_solutions))
             .route("/mainnet/memoryPool/transactions", get(Self::get_memory_pool_transactions))
+            .route(
+                "/mainnet/memoryPool/transactions/page",
+                get(Self::get_memory_pool_transactions_page).route_layer(rpc_limit("getmemorypooltransactions")),
+            )
+            .route("/mainnet/memoryPool/transaction/:id", get(Self::get_memory_pool_transaction))
+            .route(
+                "/mainnet/memoryPool/info",
+                get(Self::get_memory_pool_info).route_layer(rpc_limit("getmemorypoolinfo")),
+            )
+            .route("/mainnet/fee/estimate", get(Self::get_fee_estimate).route_layer(rpc_limit("estimatefee")))
             .route("/mainnet/statePath/:commitment", get(Self::get_state_path_for_commitment))
+            .route("/mainnet/ledgerProof", get(Self::get_ledger_proof).route_layer(rpc_limit("getledgerproof")))
             .route("/mainnet/stateRoot/latest", get(Self::get_state_root_latest))
             .route("/mainnet/committee/latest", get(Self::get_committee_latest))
+        };
 
+        // Serve the GraphQL queries.
+        #[cfg(feature = "graphql")]
+        let router = {
+            let schema = graphql::schema(self.clone());
+            router.route_service("/mainnet/graphql", async_graphql_axum::GraphQL::new(schema))
+        };
+
+        let router = {
+            router
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
             // Enable tower-http tracing.
@@ -191,20 +453,67 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .layer(middleware::from_fn(log_middleware))
             // Enable CORS.
             .layer(cors)
+            // Compress the responses with gzip or zstd, if the client accepts it.
+            .layer(CompressionLayer::new().gzip(true).zstd(true))
             // Cap body size at 10MB.
             .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
             .layer(GovernorLayer {
                 // We can leak this because it is created only once and it persists.
                 config: Box::leak(governor_config),
             })
+            // Rate limit the requests per static bearer token.
+            .layer(middleware::from_fn_with_state(token_rate_limiter, token_rate_limit_middleware))
         };
 
+        // Serve the health probes, without rate limiting, so orchestrators are never throttled.
+        let router = router.merge(
+            axum::Router::new()
+                .route("/livez", get(Self::livez))
+                .route("/healthz", get(Self::healthz))
+                .route("/readyz", get(Self::readyz))
+                .with_state(self.clone()),
+        );
+
+        // Serve the Prometheus metrics.
+        #[cfg(feature = "metrics")]
+        let router = {
+            let metrics_router = axum::Router::new().route("/metrics", get(Self::get_metrics));
+            router.merge(metrics_router.with_state(self.clone()))
+        };
+
+        // If TLS is configured, terminate TLS in the server.
+        if let Some(tls) = &self.config.tls {
+            let tls_config = RustlsConfig::from_config(tls.load()?);
+            match tls.client_ca_path.is_some() {
+                true => info!("REST server is terminating TLS (client certificates required)"),
+                false => info!("REST server is terminating TLS"),
+            }
+            // Stop accepting connections once the shutdown is signaled.
+            let handle = axum_server::Handle::new();
+            let (shutdown, shutdown_handle) = (self.shutdown.clone(), handle.clone());
+            self.handles.lock().push(tokio::spawn(async move {
+                shutdown.wait_for_signal().await;
+                shutdown_handle.graceful_shutdown(None);
+            }));
+            self.shutdown.set_server(tokio::spawn(async move {
+                axum_server::bind_rustls(rest_ip, tls_config)
+                    .handle(handle)
+                    .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                    .await
+                    .expect("couldn't start rest server");
+            }));
+            return Ok(());
+        }
+
         let rest_listener = TcpListener::bind(rest_ip).await.unwrap();
-        self.handles.lock().push(tokio::spawn(async move {
+        let shutdown = self.shutdown.clone();
+        self.shutdown.set_server(tokio::spawn(async move {
             axum::serve(rest_listener, router.into_make_service_with_connect_info::<SocketAddr>())
+                .with_graceful_shutdown(async move { shutdown.wait_for_signal().await })
                 .await
                 .expect("couldn't start rest server");
-        }))
+        }));
+        Ok(())
     }
 }
 
@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::Address;
+
+use serde::Serialize;
+
+/// The number of rounds ahead of the current round that are searched for the upcoming leader rounds.
+const UPCOMING_LEADER_ROUNDS_WINDOW: u64 = 100;
+
+/// An even round that the validator was elected to lead.
+#[derive(Serialize)]
+pub(crate) struct LeaderRound {
+    /// The round.
+    round: u64,
+    /// Whether the validator produced a certificate in the round.
+    has_certificate: bool,
+}
+
+/// The recent participation of the validator in consensus, over the rounds that are still in storage,
+/// and its upcoming duties.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct ValidatorParticipation<N: Network> {
+    /// The address of the validator.
+    address: Address<N>,
+    /// Whether the validator is a member of the current committee.
+    is_committee_member: bool,
+    /// The current round.
+    current_round: u64,
+    /// The first round that participation is reported for.
+    from_round: u64,
+    /// The number of batches the validator proposed that were certified.
+    num_proposed: usize,
+    /// The number of certificates of the other validators that the validator signed.
+    num_signed: usize,
+    /// The rounds the validator was in the committee for, but has no certificate in.
+    missed_rounds: Vec<u64>,
+    /// The rounds the validator was elected to lead.
+    leader_rounds: Vec<LeaderRound>,
+    /// The upcoming rounds the validator is elected to lead, based on the committee of each round.
+    upcoming_leader_rounds: Vec<u64>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/node/validator/participation
+    pub(crate) async fn get_validator_participation(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match &rest.consensus {
+            Some(consensus) => Ok(ErasedJson::pretty(validator_participation(consensus)?)),
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+}
+
+/// Returns the recent participation and the upcoming duties of the validator.
+fn validator_participation<N: Network>(consensus: &Consensus<N>) -> Result<ValidatorParticipation<N>> {
+    let bft = consensus.bft();
+    let (storage, ledger) = (bft.storage(), bft.ledger());
+    let address = bft.primary().gateway().account().address();
+    let current_round = storage.current_round();
+    let from_round = storage.gc_round().saturating_add(1);
+
+    let (mut num_proposed, mut num_signed) = (0, 0);
+    let mut missed_rounds = Vec::new();
+    let mut leader_rounds = Vec::new();
+    // Note: The current round is still in progress, so it is not reported.
+    for round in from_round..current_round {
+        let Ok(committee) = ledger.get_committee_lookback_for_round(round) else {
+            continue;
+        };
+        if !committee.is_committee_member(address) {
+            continue;
+        }
+        let certificates = storage.get_certificates_for_round(round);
+        let has_certificate = certificates.iter().any(|certificate| certificate.author() == address);
+        match has_certificate {
+            true => num_proposed += 1,
+            false => missed_rounds.push(round),
+        }
+        num_signed += certificates
+            .iter()
+            .filter(|certificate| certificate.signatures().any(|signature| signature.to_address() == address))
+            .count();
+        if round % 2 == 0 && committee.get_leader(round)? == address {
+            leader_rounds.push(LeaderRound { round, has_certificate });
+        }
+    }
+
+    // Find the upcoming leader rounds, using the current committee for the rounds beyond the ledger.
+    let current_committee = ledger.current_committee()?;
+    let mut upcoming_leader_rounds = Vec::new();
+    for round in (current_round..current_round.saturating_add(UPCOMING_LEADER_ROUNDS_WINDOW)).filter(|r| r % 2 == 0) {
+        let committee = ledger.get_committee_lookback_for_round(round).unwrap_or_else(|_| current_committee.clone());
+        if committee.is_committee_member(address) && committee.get_leader(round)? == address {
+            upcoming_leader_rounds.push(round);
+        }
+    }
+
+    Ok(ValidatorParticipation {
+        address,
+        is_committee_member: current_committee.is_committee_member(address),
+        current_round,
+        from_round,
+        num_proposed,
+        num_signed,
+        missed_rounds,
+        leader_rounds,
+        upcoming_leader_rounds,
+    })
+}
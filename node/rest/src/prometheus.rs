@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::response::IntoResponse;
+
+/// The content type of the Prometheus text format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /metrics
+    pub(crate) async fn get_metrics(State(rest): State<Self>) -> Response {
+        rest.update_metrics();
+        match metrics::render_metrics() {
+            Some(body) => ([(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response(),
+            None => (StatusCode::SERVICE_UNAVAILABLE, "Metrics are not enabled on this node").into_response(),
+        }
+    }
+
+    /// Updates the node-level gauges, so they are current as of the scrape.
+    fn update_metrics(&self) {
+        let router = self.routing.router();
+        metrics::gauge(metrics::blocks::HEIGHT, self.ledger.latest_height() as f64);
+        metrics::gauge(metrics::router::CONNECTED, router.number_of_connected_peers() as f64);
+        metrics::gauge(metrics::sync::IS_SYNCED, u8::from(self.sync.is_block_synced()) as f64);
+        if let Some(consensus) = &self.consensus {
+            let num_transactions = consensus.num_unconfirmed_transactions() + consensus.num_queued_transactions();
+            metrics::gauge(metrics::consensus::UNCONFIRMED_TRANSACTIONS, num_transactions as f64);
+        }
+    }
+}
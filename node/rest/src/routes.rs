@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use super::*;
-use snarkos_node_router::messages::UnconfirmedSolution;
+use snarkos_node_router::{messages::UnconfirmedSolution, PeerInfo};
 use snarkvm::{
     ledger::coinbase::ProverSolution,
-    prelude::{block::Transaction, Identifier, Plaintext},
+    prelude::{
+        block::{Header, Input, Output, Transaction, Transition},
+        Identifier,
+        Plaintext,
+    },
 };
 
 use indexmap::IndexMap;
@@ -33,6 +37,266 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+/// The maximum number of blocks returned per page.
+pub const MAX_BLOCKS_PER_PAGE: u32 = 50;
+/// The maximum number of block headers returned per page.
+pub const MAX_HEADERS_PER_PAGE: u32 = 1_000;
+
+/// The `get_block_page` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BlockPageQuery {
+    /// The block height to resume from (inclusive), as returned by the previous page.
+    #[serde(default)]
+    cursor: u32,
+    /// The ending block height (exclusive), which defaults to the latest height.
+    end: Option<u32>,
+    /// The maximum number of items in the page.
+    limit: Option<u32>,
+    /// Whether to return the block headers only.
+    #[serde(default)]
+    headers_only: bool,
+}
+
+/// A page of blocks, or of block headers.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct BlockPage<N: Network> {
+    /// The blocks, if full blocks were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocks: Option<Vec<Block<N>>>,
+    /// The block headers, if only headers were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<Vec<Header<N>>>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    next_cursor: Option<u32>,
+}
+
+/// The maximum number of memory pool transactions returned per page.
+pub const MAX_MEMORY_POOL_TRANSACTIONS_PER_PAGE: usize = 50;
+
+/// The `get_memory_pool_transactions_page` query object.
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct MemoryPoolPageQuery {
+    /// The index of the first transaction in the page, as returned by the previous page.
+    #[serde(default)]
+    cursor: usize,
+    /// The maximum number of transactions in the page.
+    limit: Option<usize>,
+}
+
+/// The status of a transaction in the memory pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MemoryPoolStatus {
+    /// The transaction is waiting in the queue to enter the memory pool.
+    Queued,
+    /// The transaction is in the memory pool, waiting to be included in a batch.
+    Pending,
+}
+
+/// A transaction in the memory pool, along with its status and fee.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct MemoryPoolTransaction<N: Network> {
+    /// The status of the transaction.
+    status: MemoryPoolStatus,
+    /// The fee of the transaction, in microcredits.
+    fee: Option<u64>,
+    /// The transaction.
+    transaction: Transaction<N>,
+}
+
+impl<N: Network> MemoryPoolTransaction<N> {
+    /// Initializes a new memory pool transaction with the given status.
+    fn new(status: MemoryPoolStatus, transaction: Transaction<N>) -> Self {
+        let fee = transaction.fee_amount().ok().map(|fee| *fee);
+        Self { status, fee, transaction }
+    }
+}
+
+/// A page of memory pool transactions.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct MemoryPoolPage<N: Network> {
+    /// The transactions.
+    transactions: Vec<MemoryPoolTransaction<N>>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    next_cursor: Option<usize>,
+}
+
+/// A summary of the memory pool.
+#[derive(Serialize)]
+pub(crate) struct MemoryPoolInfo {
+    /// The number of transactions, including the queued transactions.
+    num_transactions: usize,
+    /// The number of deployment transactions.
+    num_deployments: usize,
+    /// The number of execution transactions.
+    num_executions: usize,
+    /// The number of transactions waiting in the queue to enter the memory pool.
+    num_queued_transactions: usize,
+    /// The total size of the transactions waiting in the queue, in bytes.
+    queued_transaction_bytes: usize,
+    /// The number of transactions that expired from the queue.
+    num_expired_transactions: u64,
+    /// The number of transactions evicted from the queue, once it was full.
+    num_evicted_transactions: u64,
+    /// The number of solutions in the memory pool.
+    num_solutions: usize,
+    /// The number of solutions waiting in the queue to enter the memory pool.
+    num_queued_solutions: usize,
+    /// The sum of the transaction fees, in microcredits.
+    total_fees: u64,
+    /// The lowest transaction fee, in microcredits.
+    min_fee: Option<u64>,
+    /// The highest transaction fee, in microcredits.
+    max_fee: Option<u64>,
+    /// The retention and expiry policy of the queue.
+    policy: MemoryPoolPolicy,
+}
+
+/// The running totals of the transactions in the memory pool, used to summarize it.
+#[derive(Default)]
+struct MemoryPoolTotals {
+    /// The number of deployment transactions.
+    num_deployments: usize,
+    /// The number of execution transactions.
+    num_executions: usize,
+    /// The sum of the transaction fees, in microcredits.
+    total_fees: u64,
+    /// The lowest transaction fee, in microcredits.
+    min_fee: Option<u64>,
+    /// The highest transaction fee, in microcredits.
+    max_fee: Option<u64>,
+}
+
+impl MemoryPoolTotals {
+    /// Adds the given transaction fee to the totals.
+    fn add_fee(&mut self, fee: u64) {
+        self.total_fees = self.total_fees.saturating_add(fee);
+        self.min_fee = Some(self.min_fee.map_or(fee, |min_fee| min_fee.min(fee)));
+        self.max_fee = Some(self.max_fee.map_or(fee, |max_fee| max_fee.max(fee)));
+    }
+}
+
+/// The retention and expiry policy of the memory pool queue.
+#[derive(Serialize)]
+pub(crate) struct MemoryPoolPolicy {
+    /// The maximum number of queued transactions.
+    max_transactions: usize,
+    /// The maximum total size of the queued transactions, in bytes.
+    max_bytes: usize,
+    /// The number of seconds a transaction may be queued for, before it expires.
+    max_age_secs: u64,
+    /// The order in which queued transactions are evicted [options: age, fee].
+    eviction: String,
+    /// The order in which queued transactions enter the memory pool [options: arrival, fee-density].
+    ordering: String,
+}
+
+/// The `get_transaction` query object.
+#[derive(Default, Deserialize, Serialize)]
+pub(crate) struct TransactionQuery {
+    /// Whether to include the confirmation metadata.
+    #[serde(default)]
+    metadata: bool,
+    /// Whether to include the decoded transitions (implies `metadata`).
+    #[serde(default)]
+    transitions: bool,
+}
+
+/// A transaction, along with the block that confirmed it.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct TransactionWithMetadata<N: Network> {
+    /// The transaction.
+    transaction: Transaction<N>,
+    /// The hash of the block containing the transaction.
+    block_hash: N::BlockHash,
+    /// The height of the block containing the transaction.
+    block_height: u32,
+    /// The position of the transaction in its block, if the transaction index is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<u32>,
+    /// The number of blocks confirming the transaction, including its own block.
+    confirmations: u32,
+    /// The timestamp of the block containing the transaction.
+    timestamp: i64,
+    /// The decoded transitions, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transitions: Option<Vec<DecodedTransition>>,
+}
+
+/// A transition, with its public values decoded.
+#[derive(Serialize)]
+pub(crate) struct DecodedTransition {
+    /// The transition ID.
+    id: String,
+    /// The program ID.
+    program_id: String,
+    /// The function name.
+    function_name: String,
+    /// The inputs.
+    inputs: Vec<DecodedValue>,
+    /// The outputs.
+    outputs: Vec<DecodedValue>,
+}
+
+/// A transition input or output, with its value decoded if it is public.
+#[derive(Serialize)]
+pub(crate) struct DecodedValue {
+    /// The input or output ID.
+    id: String,
+    /// The visibility of the value.
+    visibility: &'static str,
+    /// The value, if it is public.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl DecodedValue {
+    /// Initializes a new decoded value.
+    fn new(id: impl ToString, visibility: &'static str, value: Option<impl ToString>) -> Self {
+        Self { id: id.to_string(), visibility, value: value.map(|value| value.to_string()) }
+    }
+}
+
+impl DecodedTransition {
+    /// Decodes the given transition.
+    pub(crate) fn new<N: Network>(transition: &Transition<N>) -> Self {
+        let inputs = transition
+            .inputs()
+            .iter()
+            .map(|input| match input {
+                Input::Constant(id, value) => DecodedValue::new(id, "constant", value.as_ref()),
+                Input::Public(id, value) => DecodedValue::new(id, "public", value.as_ref()),
+                Input::Private(id, _) => DecodedValue::new(id, "private", None::<String>),
+                Input::Record(serial_number, _) => DecodedValue::new(serial_number, "record", None::<String>),
+                Input::ExternalRecord(id) => DecodedValue::new(id, "external_record", None::<String>),
+            })
+            .collect();
+        let outputs = transition
+            .outputs()
+            .iter()
+            .map(|output| match output {
+                Output::Constant(id, value) => DecodedValue::new(id, "constant", value.as_ref()),
+                Output::Public(id, value) => DecodedValue::new(id, "public", value.as_ref()),
+                Output::Private(id, _) => DecodedValue::new(id, "private", None::<String>),
+                Output::Record(commitment, _, _) => DecodedValue::new(commitment, "record", None::<String>),
+                Output::ExternalRecord(id) => DecodedValue::new(id, "external_record", None::<String>),
+                Output::Future(id, value) => DecodedValue::new(id, "future", value.as_ref()),
+            })
+            .collect();
+        Self {
+            id: transition.id().to_string(),
+            program_id: transition.program_id().to_string(),
+            function_name: transition.function_name().to_string(),
+            inputs,
+            outputs,
+        }
+    }
+}
+
 /// The `get_mapping_value` query object.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct Metadata {
@@ -100,13 +364,13 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // Manually parse the height or the height or the hash, axum doesn't support different types
         // for the same path param.
         let block = if let Ok(height) = height_or_hash.parse::<u32>() {
-            rest.ledger.get_block(height)?
+            rest.ledger_cache.get_block(&rest.ledger, height)?
         } else {
             let hash = height_or_hash
                 .parse::<N::BlockHash>()
                 .map_err(|_| RestError("invalid input, it is neither a block height nor a block hash".to_string()))?;
 
-            rest.ledger.get_block_by_hash(&hash)?
+            rest.ledger_cache.get_block(&rest.ledger, rest.ledger.get_height(&hash)?)?
         };
 
         Ok(ErasedJson::pretty(block))
@@ -117,29 +381,15 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         State(rest): State<Self>,
         Query(block_range): Query<BlockRange>,
     ) -> Result<ErasedJson, RestError> {
-        let start_height = block_range.start;
-        let end_height = block_range.end;
-
-        const MAX_BLOCK_RANGE: u32 = 50;
-
-        // Ensure the end height is greater than the start height.
-        if start_height > end_height {
-            return Err(RestError("Invalid block range".to_string()));
-        }
-
-        // Ensure the block range is bounded.
-        if end_height - start_height > MAX_BLOCK_RANGE {
-            return Err(RestError(format!(
-                "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
-                end_height - start_height
-            )));
-        }
-
-        let blocks = cfg_into_iter!((start_height..end_height))
-            .map(|height| rest.ledger.get_block(height))
-            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ErasedJson::pretty(rest.get_blocks_in_range(block_range.start, block_range.end)?))
+    }
 
-        Ok(ErasedJson::pretty(blocks))
+    // GET /mainnet/blocks/page?cursor={height}&end={end_height}&limit={limit}&headers_only={bool}
+    pub(crate) async fn get_block_page(
+        State(rest): State<Self>,
+        Query(query): Query<BlockPageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.get_blocks_page(&query)?))
     }
 
     // GET /mainnet/height/{blockHash}
@@ -159,11 +409,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 
     // GET /mainnet/transaction/{transactionID}
+    // GET /mainnet/transaction/{transactionID}?metadata={true}&transitions={true}
     pub(crate) async fn get_transaction(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
+        query: Option<Query<TransactionQuery>>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
+        let query = query.map(|Query(query)| query).unwrap_or_default();
+        // Check if metadata is requested and return the transaction with metadata if so.
+        if query.metadata || query.transitions {
+            return Ok(ErasedJson::pretty(rest.transaction_with_metadata(tx_id, query.transitions)?));
+        }
+        match rest.indexed_transaction(tx_id) {
+            Some((transaction, _)) => Ok(ErasedJson::pretty(transaction)),
+            None => Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?)),
+        }
     }
 
     // GET /mainnet/transaction/confirmed/{transactionID}
@@ -200,6 +460,27 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
     }
 
+    // GET /mainnet/memoryPool/info
+    pub(crate) async fn get_memory_pool_info(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.memory_pool_info().await?))
+    }
+
+    // GET /mainnet/memoryPool/transactions/page?cursor={index}&limit={limit}
+    pub(crate) async fn get_memory_pool_transactions_page(
+        State(rest): State<Self>,
+        Query(query): Query<MemoryPoolPageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.memory_pool_transactions_page(&query).await?))
+    }
+
+    // GET /mainnet/memoryPool/transaction/{transactionID}
+    pub(crate) async fn get_memory_pool_transaction(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.memory_pool_transaction(&tx_id)?))
+    }
+
     // GET /mainnet/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
@@ -243,7 +524,7 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         State(rest): State<Self>,
         Path(commitment): Path<Field<N>>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_state_path_for_commitment(&commitment)?))
+        Ok(ErasedJson::pretty(rest.state_path_cache.get(&rest.ledger, &commitment)?))
     }
 
     // GET /mainnet/stateRoot/latest
@@ -271,6 +552,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         ErasedJson::pretty(rest.routing.router().connected_metrics())
     }
 
+    // GET /mainnet/peers/info
+    pub(crate) async fn get_peers_info(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.peer_info())
+    }
+
+    // GET /mainnet/network/info
+    pub(crate) async fn get_network_info(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().network_info())
+    }
+
+    // GET /mainnet/sync/progress
+    pub(crate) async fn get_sync_progress(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.sync.sync_progress())
+    }
+
     // GET /mainnet/node/address
     pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().address())
@@ -297,6 +593,12 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         State(rest): State<Self>,
         Path(transition_id): Path<N::TransitionID>,
     ) -> Result<ErasedJson, RestError> {
+        // Resolve the transition through the transaction index, if enabled.
+        if let Some((transaction_id, _)) =
+            rest.transaction_index.as_ref().and_then(|index| index.get_transition(&transition_id))
+        {
+            return Ok(ErasedJson::pretty(Some(transaction_id)));
+        }
         Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?))
     }
 
@@ -316,7 +618,7 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
         if let Some(consensus) = rest.consensus {
             // Add the unconfirmed transaction to the memory pool.
-            consensus.add_unconfirmed_transaction(tx.clone()).await?;
+            consensus.add_unconfirmed_transaction(tx.clone(), None).await?;
         }
 
         // Prepare the unconfirmed transaction message.
@@ -355,4 +657,258 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         Ok(ErasedJson::pretty(commitment))
     }
+
+    /// Returns the blocks in the given range, from `start_height` (inclusive) to `end_height` (exclusive).
+    pub(crate) fn get_blocks_in_range(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>, RestError> {
+        const MAX_BLOCK_RANGE: u32 = 50;
+
+        // Ensure the end height is greater than the start height.
+        if start_height > end_height {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+
+        // Ensure the block range is bounded.
+        if end_height - start_height > MAX_BLOCK_RANGE {
+            return Err(RestError(format!(
+                "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
+                end_height - start_height
+            )));
+        }
+
+        let blocks = cfg_into_iter!((start_height..end_height))
+            .map(|height| self.ledger_cache.get_block(&self.ledger, height))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(blocks)
+    }
+
+    /// Returns the page of blocks (or block headers) starting at the given cursor.
+    pub(crate) fn get_blocks_page(&self, query: &BlockPageQuery) -> Result<BlockPage<N>, RestError> {
+        // Ensure the page size is bounded.
+        let max_page_size = match query.headers_only {
+            true => MAX_HEADERS_PER_PAGE,
+            false => MAX_BLOCKS_PER_PAGE,
+        };
+        let limit = query.limit.unwrap_or(max_page_size);
+        if limit == 0 || limit > max_page_size {
+            return Err(RestError(format!("The page size must be between 1 and {max_page_size} (requested {limit})")));
+        }
+
+        // Bound the page by the requested end height and the latest height.
+        let latest_end = self.ledger.latest_height().saturating_add(1);
+        let end_height = query.end.map_or(latest_end, |end| end.min(latest_end));
+        let start_height = query.cursor.min(end_height);
+        let page_end = start_height.saturating_add(limit).min(end_height);
+        // Only return a cursor if there are more items past this page.
+        let next_cursor = (page_end < end_height).then_some(page_end);
+
+        let page = match query.headers_only {
+            true => BlockPage {
+                blocks: None,
+                headers: Some(
+                    cfg_into_iter!((start_height..page_end))
+                        .map(|height| self.ledger_cache.get_header(&self.ledger, height))
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                next_cursor,
+            },
+            false => BlockPage {
+                blocks: Some(
+                    cfg_into_iter!((start_height..page_end))
+                        .map(|height| self.ledger_cache.get_block(&self.ledger, height))
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                headers: None,
+                next_cursor,
+            },
+        };
+
+        Ok(page)
+    }
+
+    /// Returns a snapshot of the state of each connected peer, including their latest known height.
+    pub(crate) fn peer_info(&self) -> Vec<PeerInfo<N>> {
+        let mut peers = self.routing.router().connected_peer_info();
+        for peer in peers.iter_mut() {
+            peer.height = self.sync.get_peer_height(&peer.ip);
+        }
+        peers
+    }
+
+    /// Returns the consensus module, if this node runs consensus.
+    fn consensus(&self) -> Result<&Consensus<N>, RestError> {
+        self.consensus.as_ref().ok_or_else(|| RestError("Route isn't available for this node type".to_string()))
+    }
+
+    /// Returns the IDs of the transactions in the memory pool, followed by those of the queued transactions.
+    fn memory_pool_transaction_ids(&self) -> Result<Vec<(MemoryPoolStatus, N::TransactionID)>, RestError> {
+        let consensus = self.consensus()?;
+        let pending = consensus.unconfirmed_transaction_ids().map(|id| (MemoryPoolStatus::Pending, id));
+        let queued = consensus.queued_transaction_ids().into_iter().map(|id| (MemoryPoolStatus::Queued, id));
+        Ok(pending.chain(queued).collect())
+    }
+
+    /// Returns a summary of the memory pool.
+    pub(crate) async fn memory_pool_info(&self) -> Result<MemoryPoolInfo, RestError> {
+        let consensus = self.consensus()?.clone();
+        let (num_expired_transactions, num_evicted_transactions) = consensus.num_dropped_transactions();
+        let policy = consensus.mempool_policy();
+        let num_queued_transactions = consensus.num_queued_transactions();
+        let queued_transaction_bytes = consensus.num_queued_transaction_bytes();
+        let num_solutions = consensus.num_unconfirmed_solutions();
+        let num_queued_solutions = consensus.num_queued_solutions();
+
+        // The queue tracks the fees of its transactions, so only the memory pool needs to be deserialized.
+        let mut totals = MemoryPoolTotals::default();
+        let num_queued_deployments = consensus.num_queued_deployments();
+        totals.num_deployments += num_queued_deployments;
+        totals.num_executions += num_queued_transactions.saturating_sub(num_queued_deployments);
+        consensus.queued_transaction_fees().into_iter().for_each(|fee| totals.add_fee(fee));
+
+        // Deserialize the transactions of the memory pool one at a time, off the async runtime.
+        let totals = tokio::task::spawn_blocking(move || {
+            for transaction_id in consensus.unconfirmed_transaction_ids().collect::<Vec<_>>() {
+                let Some(transaction) = consensus.get_unconfirmed_transaction(&transaction_id) else { continue };
+                let Ok(transaction) = transaction.deserialize_blocking() else { continue };
+                totals.num_deployments += usize::from(transaction.is_deploy());
+                totals.num_executions += usize::from(transaction.is_execute());
+                if let Ok(fee) = transaction.fee_amount() {
+                    totals.add_fee(*fee);
+                }
+            }
+            totals
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to summarize the memory pool - {e}")))?;
+
+        Ok(MemoryPoolInfo {
+            num_transactions: totals.num_deployments + totals.num_executions,
+            num_deployments: totals.num_deployments,
+            num_executions: totals.num_executions,
+            num_queued_transactions,
+            queued_transaction_bytes,
+            num_expired_transactions,
+            num_evicted_transactions,
+            num_solutions,
+            num_queued_solutions,
+            total_fees: totals.total_fees,
+            min_fee: totals.min_fee,
+            max_fee: totals.max_fee,
+            policy: MemoryPoolPolicy {
+                max_transactions: policy.max_transactions,
+                max_bytes: policy.max_bytes,
+                max_age_secs: policy.max_age.as_secs(),
+                eviction: policy.eviction.to_string(),
+                ordering: policy.ordering.to_string(),
+            },
+        })
+    }
+
+    /// Returns the page of memory pool transactions starting at the given cursor.
+    /// The transactions that leave the memory pool while the page is loaded are skipped.
+    pub(crate) async fn memory_pool_transactions_page(
+        &self,
+        query: &MemoryPoolPageQuery,
+    ) -> Result<MemoryPoolPage<N>, RestError> {
+        // Ensure the page size is bounded.
+        let limit = query.limit.unwrap_or(MAX_MEMORY_POOL_TRANSACTIONS_PER_PAGE);
+        if limit == 0 || limit > MAX_MEMORY_POOL_TRANSACTIONS_PER_PAGE {
+            return Err(RestError(format!(
+                "The page size must be between 1 and {MAX_MEMORY_POOL_TRANSACTIONS_PER_PAGE} (requested {limit})"
+            )));
+        }
+
+        // Page over the transaction IDs, so only the transactions of the page are loaded.
+        let transaction_ids = self.memory_pool_transaction_ids()?;
+        let num_transactions = transaction_ids.len();
+        let page_end = query.cursor.saturating_add(limit).min(num_transactions);
+        let page = transaction_ids.into_iter().skip(query.cursor).take(limit).collect::<Vec<_>>();
+        // Only return a cursor if there are more transactions past this page.
+        let next_cursor = (page_end < num_transactions).then_some(page_end);
+
+        // Deserialize the transactions of the page off the async runtime.
+        let consensus = self.consensus()?.clone();
+        let transactions = tokio::task::spawn_blocking(move || {
+            page.into_iter()
+                .filter_map(|(status, transaction_id)| {
+                    let transaction = match status {
+                        MemoryPoolStatus::Pending => {
+                            consensus.get_unconfirmed_transaction(&transaction_id)?.deserialize_blocking().ok()?
+                        }
+                        MemoryPoolStatus::Queued => consensus.get_queued_transaction(&transaction_id)?,
+                    };
+                    Some(MemoryPoolTransaction::new(status, transaction))
+                })
+                .collect()
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to load the memory pool transactions - {e}")))?;
+
+        Ok(MemoryPoolPage { transactions, next_cursor })
+    }
+
+    /// Returns the given transaction, if it is in the memory pool or the queue.
+    pub(crate) fn memory_pool_transaction(
+        &self,
+        transaction_id: &N::TransactionID,
+    ) -> Result<MemoryPoolTransaction<N>, RestError> {
+        let consensus = self.consensus()?;
+        // Check the memory pool.
+        if let Some(transaction) = consensus.get_unconfirmed_transaction(transaction_id) {
+            let transaction = transaction.deserialize_blocking()?;
+            return Ok(MemoryPoolTransaction::new(MemoryPoolStatus::Pending, transaction));
+        }
+        // Check the queue.
+        match consensus.get_queued_transaction(transaction_id) {
+            Some(transaction) => Ok(MemoryPoolTransaction::new(MemoryPoolStatus::Queued, transaction)),
+            None => Err(RestError(format!("Transaction '{transaction_id}' is not in the memory pool"))),
+        }
+    }
+
+    /// Returns the given transaction and its location, if the transaction index is enabled and holds it.
+    fn indexed_transaction(&self, transaction_id: N::TransactionID) -> Option<(Transaction<N>, TransactionLocation)> {
+        let location = self.transaction_index.as_ref()?.get(&transaction_id)?;
+        let transactions = self.ledger.get_transactions(location.block_height).ok()?;
+        let confirmed = transactions.iter().nth(location.index as usize)?;
+        (confirmed.id() == transaction_id).then(|| (confirmed.transaction().clone(), location))
+    }
+
+    /// Returns the given transaction, along with the block that confirmed it,
+    /// and optionally its decoded transitions.
+    pub(crate) fn transaction_with_metadata(
+        &self,
+        transaction_id: N::TransactionID,
+        include_transitions: bool,
+    ) -> Result<TransactionWithMetadata<N>, RestError> {
+        // Retrieve the transaction and the block containing it, through the transaction index if enabled.
+        let (transaction, location) = match self.indexed_transaction(transaction_id) {
+            Some((transaction, location)) => (transaction, Some(location)),
+            None => (self.ledger.get_transaction(transaction_id)?, None),
+        };
+        let (block_hash, block_height, index) = match location {
+            Some(location) => {
+                (self.ledger.get_hash(location.block_height)?, location.block_height, Some(location.index))
+            }
+            None => {
+                let Some(block_hash) = self.ledger.find_block_hash(&transaction_id)? else {
+                    return Err(RestError(format!("Missing the block for transaction '{transaction_id}'")));
+                };
+                (block_hash, self.ledger.get_height(&block_hash)?, None)
+            }
+        };
+        let timestamp = self.ledger.get_header(block_height)?.timestamp();
+        let confirmations = self.ledger.latest_height().saturating_sub(block_height).saturating_add(1);
+        // Decode the transitions, if requested.
+        let transitions = include_transitions.then(|| transaction.transitions().map(DecodedTransition::new).collect());
+
+        Ok(TransactionWithMetadata {
+            transaction,
+            block_hash,
+            block_height,
+            index,
+            confirmations,
+            timestamp,
+            transitions,
+        })
+    }
 }
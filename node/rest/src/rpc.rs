@@ -0,0 +1,507 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    address_index::AddressTransactionsQuery,
+    decode::DecodedTransaction,
+    fee_estimate::FeeEstimateQuery,
+    ledger_proof::LedgerProofQuery,
+    routes::{BlockPageQuery, MemoryPoolPageQuery},
+    scan::ScanQuery,
+    validation::AddressValidation,
+};
+
+use axum::{http::HeaderMap, response::IntoResponse};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The maximum number of calls in a single JSON-RPC batch.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// The JSON-RPC error code for a malformed JSON payload.
+const PARSE_ERROR: i64 = -32700;
+/// The JSON-RPC error code for a request that is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The JSON-RPC error code for an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code for invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The JSON-RPC error code for errors raised while executing a method.
+const SERVER_ERROR: i64 = -32000;
+/// The JSON-RPC error code for a method that requires an authenticated request.
+const UNAUTHORIZED: i64 = -32001;
+/// The JSON-RPC error code for a call rejected because the method is at its concurrency limit.
+const SERVER_BUSY: i64 = -32002;
+/// The JSON-RPC error code for a call rejected by the rate limit of its API key.
+const RATE_LIMITED: i64 = -32003;
+
+/// A JSON-RPC 2.0 request object.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RpcRequest {
+    /// The protocol version, which must be "2.0".
+    jsonrpc: String,
+    /// The method name.
+    method: String,
+    /// The method parameters, as a positional array or a named object.
+    #[serde(default)]
+    params: Value,
+    /// The request ID. A request without an ID is a notification, and receives no response.
+    id: Option<Value>,
+}
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    /// The error code.
+    pub code: i64,
+    /// The error message.
+    pub message: String,
+}
+
+impl RpcError {
+    /// Initializes a new error with the given code and message.
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    /// Returns an error for invalid method parameters.
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    /// Returns the HTTP status code corresponding to the error.
+    pub fn status_code(&self) -> StatusCode {
+        match self.code {
+            PARSE_ERROR | INVALID_REQUEST | INVALID_PARAMS => StatusCode::BAD_REQUEST,
+            METHOD_NOT_FOUND => StatusCode::NOT_FOUND,
+            UNAUTHORIZED => StatusCode::UNAUTHORIZED,
+            SERVER_BUSY => StatusCode::SERVICE_UNAVAILABLE,
+            RATE_LIMITED => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(SERVER_ERROR, error.to_string())
+    }
+}
+
+impl From<RestError> for RpcError {
+    fn from(error: RestError) -> Self {
+        Self::new(SERVER_ERROR, error.0)
+    }
+}
+
+/// A JSON-RPC 2.0 response object.
+#[derive(Debug, Serialize)]
+pub(crate) struct RpcResponse {
+    /// The protocol version.
+    jsonrpc: &'static str,
+    /// The result, on success.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    /// The error, on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+    /// The request ID.
+    id: Value,
+}
+
+impl RpcResponse {
+    /// Initializes a new response from the given result.
+    fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        match result {
+            Ok(result) => Self { jsonrpc: "2.0", result: Some(result), error: None, id },
+            Err(error) => Self { jsonrpc: "2.0", result: None, error: Some(error), id },
+        }
+    }
+}
+
+/// Returns the parameter at the given position (or with the given name), deserialized into `T`.
+pub(crate) fn param<T: DeserializeOwned>(params: &Value, index: usize, name: &str) -> Result<T, RpcError> {
+    let value = match params {
+        Value::Array(values) => values.get(index),
+        Value::Object(values) => values.get(name),
+        _ => None,
+    };
+    let value = value.cloned().ok_or_else(|| RpcError::invalid_params(format!("Missing parameter '{name}'")))?;
+    serde_json::from_value(value).map_err(|e| RpcError::invalid_params(format!("Invalid parameter '{name}' - {e}")))
+}
+
+/// Returns the optional parameter at the given position (or with the given name), deserialized into `T`.
+pub(crate) fn optional_param<T: DeserializeOwned>(
+    params: &Value,
+    index: usize,
+    name: &str,
+) -> Result<Option<T>, RpcError> {
+    match params {
+        Value::Array(values) if values.get(index).map_or(true, Value::is_null) => Ok(None),
+        Value::Object(values) if values.get(name).map_or(true, Value::is_null) => Ok(None),
+        Value::Null => Ok(None),
+        _ => param(params, index, name).map(Some),
+    }
+}
+
+/// Returns the named parameters, deserialized into `T`. Omitted parameters are treated as an empty object.
+pub(crate) fn named_params<T: DeserializeOwned>(params: &Value) -> Result<T, RpcError> {
+    let params = match params {
+        Value::Null => Value::Object(Default::default()),
+        Value::Array(values) if values.is_empty() => Value::Object(Default::default()),
+        params => params.clone(),
+    };
+    serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+/// Serializes the given object into a JSON-RPC result.
+pub(crate) fn to_result<T: Serialize>(object: T) -> Result<Value, RpcError> {
+    serde_json::to_value(object).map_err(|e| RpcError::new(SERVER_ERROR, e.to_string()))
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /mainnet/rpc
+    pub(crate) async fn rpc(
+        State(rest): State<Self>,
+        ConnectInfo(caller): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        body: String,
+    ) -> Response {
+        // Resolve the API key, if one was provided.
+        let api_key = match headers.get(API_KEY_HEADER).map(|value| value.to_str().unwrap_or_default()) {
+            Some(key) => match rest.config.api_keys.get(key) {
+                Some(api_key) => Some(api_key),
+                None => return (StatusCode::UNAUTHORIZED, "Invalid API key").into_response(),
+            },
+            None => None,
+        };
+        rest.handle_rpc(&body, false, api_key.as_deref(), RpcCaller::Ip(caller.ip())).await
+    }
+
+    // POST /mainnet/node/rpc
+    pub(crate) async fn private_rpc(
+        State(rest): State<Self>,
+        ConnectInfo(caller): ConnectInfo<SocketAddr>,
+        body: String,
+    ) -> Response {
+        rest.handle_rpc(&body, true, None, RpcCaller::Ip(caller.ip())).await
+    }
+
+    // GET /mainnet/node/apiKeys
+    pub(crate) async fn get_api_key_usage(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.config.api_keys.usage())
+    }
+
+    // GET /mainnet/rpc/spec
+    pub(crate) async fn get_rpc_spec(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.rpc_spec())
+    }
+
+    /// Returns the OpenRPC document for the JSON-RPC methods enabled on this server.
+    fn rpc_spec(&self) -> Value {
+        openrpc_document(|method| self.config.rpc_methods.is_enabled(method))
+    }
+
+    /// Handles the given JSON-RPC payload, which may be a single call or a batch of calls.
+    /// The control methods are only executed if the request is authenticated, or the API key permits them.
+    pub(crate) async fn handle_rpc(
+        &self,
+        body: &str,
+        is_authenticated: bool,
+        api_key: Option<&ApiKey>,
+        caller: RpcCaller,
+    ) -> Response {
+        // Parse the payload.
+        let payload = match serde_json::from_str::<Value>(body) {
+            Ok(payload) => payload,
+            Err(error) => {
+                let error = RpcError::new(PARSE_ERROR, format!("Parse error - {error}"));
+                return Json(RpcResponse::new(Value::Null, Err(error))).into_response();
+            }
+        };
+
+        match payload {
+            // Process a batch of calls.
+            Value::Array(calls) => {
+                if calls.is_empty() {
+                    let error = RpcError::new(INVALID_REQUEST, "Empty batch");
+                    return Json(RpcResponse::new(Value::Null, Err(error))).into_response();
+                }
+                if calls.len() > MAX_BATCH_SIZE {
+                    let error = RpcError::new(INVALID_REQUEST, format!("Batch exceeds {MAX_BATCH_SIZE} calls"));
+                    return Json(RpcResponse::new(Value::Null, Err(error))).into_response();
+                }
+                let mut responses = Vec::with_capacity(calls.len());
+                for call in calls {
+                    responses.extend(self.process_rpc_call(call, is_authenticated, api_key, caller).await);
+                }
+                // A batch of notifications receives no response.
+                match responses.is_empty() {
+                    true => StatusCode::NO_CONTENT.into_response(),
+                    false => Json(responses).into_response(),
+                }
+            }
+            // Process a single call.
+            call => match self.process_rpc_call(call, is_authenticated, api_key, caller).await {
+                Some(response) => Json(response).into_response(),
+                None => StatusCode::NO_CONTENT.into_response(),
+            },
+        }
+    }
+
+    /// Processes a single JSON-RPC call, returning `None` if the call is a notification.
+    async fn process_rpc_call(
+        &self,
+        call: Value,
+        is_authenticated: bool,
+        api_key: Option<&ApiKey>,
+        caller: RpcCaller,
+    ) -> Option<RpcResponse> {
+        let request = match serde_json::from_value::<RpcRequest>(call) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            _ => {
+                let error = RpcError::new(INVALID_REQUEST, "Invalid request");
+                return Some(RpcResponse::new(Value::Null, Err(error)));
+            }
+        };
+        let timer = std::time::Instant::now();
+        let result = match Self::authorize_rpc_call(&request.method, is_authenticated, api_key) {
+            Ok(()) => self.call_rpc_method(&request.method, &request.params).await,
+            Err(error) => Err(error),
+        };
+        let latency = timer.elapsed();
+        let result_code = result.as_ref().map_or_else(|error| error.code, |_| 0);
+        // Count the call towards the usage of the API key.
+        if let Some(api_key) = api_key {
+            api_key.record_call(&request.method, result_code, result_code == RATE_LIMITED);
+        }
+        #[cfg(feature = "metrics")]
+        {
+            metrics::increment_counter(metrics::rest::RPC_REQUESTS);
+            metrics::histogram(metrics::rest::RPC_LATENCY, latency.as_secs_f64());
+        }
+        // Write the access log entry, if enabled.
+        if self.config.access_log {
+            let api_key = api_key.map(ApiKey::name);
+            log_rpc_call(caller, api_key, &request.method, &request.params, latency, result_code);
+        }
+        request.id.map(|id| RpcResponse::new(id, result))
+    }
+
+    /// Ensures the caller may call the given method.
+    /// A call with an API key is checked against the permissions and the rate limit of the key;
+    /// otherwise, the control and debug methods are only called through the authenticated endpoint.
+    fn authorize_rpc_call(method: &str, is_authenticated: bool, api_key: Option<&ApiKey>) -> Result<(), RpcError> {
+        if let Some(api_key) = api_key {
+            if !api_key.is_permitted(method) {
+                let message = format!("Method '{method}' is not permitted for the API key '{}'", api_key.name());
+                return Err(RpcError::new(UNAUTHORIZED, message));
+            }
+            if let Err(wait_time) = api_key.check_rate_limit() {
+                let message = format!("Rate limit exceeded, retry in {}ms", wait_time.as_millis());
+                return Err(RpcError::new(RATE_LIMITED, message));
+            }
+            return Ok(());
+        }
+        let is_private = matches!(rpc_method_group(method), Some(RpcMethodGroup::Control | RpcMethodGroup::Debug));
+        match is_private && !is_authenticated {
+            true => Err(RpcError::new(UNAUTHORIZED, format!("Method '{method}' requires authentication"))),
+            false => Ok(()),
+        }
+    }
+
+    /// Forwards the given call to a replica, returning `None` if the method is not a read method,
+    /// or if the replica failed, in which case the call is served locally.
+    async fn forward_rpc_call(&self, method: &str, params: &Value) -> Option<Result<Value, RpcError>> {
+        if !matches!(rpc_method_group(method), Some(RpcMethodGroup::Read | RpcMethodGroup::Wallet)) {
+            return None;
+        }
+        let mut response = match self.config.replicas.forward(method, params).await {
+            Ok(response) => response,
+            Err(error) => {
+                warn!("Failed to forward '{method}' to a replica - {error}");
+                return None;
+            }
+        };
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::rest::RPC_FORWARDED);
+        match response.get_mut("error").map(Value::take) {
+            Some(error) => Some(Err(RpcError::new(
+                error["code"].as_i64().unwrap_or(SERVER_ERROR),
+                error["message"].as_str().unwrap_or("Replica error"),
+            ))),
+            None => Some(Ok(response["result"].take())),
+        }
+    }
+
+    /// Executes the given JSON-RPC method.
+    pub(crate) async fn call_rpc_method(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        // Ensure the method is enabled on this server.
+        if !self.config.rpc_methods.is_enabled(method) {
+            let message = match rpc_method_group(method) {
+                Some(_) => format!("Method '{method}' is disabled on this node"),
+                None => format!("Method '{method}' not found"),
+            };
+            return Err(RpcError::new(METHOD_NOT_FOUND, message));
+        }
+        // Forward the call to a replica, if the method is at its limit and may be served by a replica.
+        if self.config.rpc_limits.is_saturated(method) && !self.config.replicas.is_empty() {
+            if let Some(result) = self.forward_rpc_call(method, params).await {
+                return result;
+            }
+        }
+        // Wait for a free slot, if the method is limited.
+        let _permit = self
+            .config
+            .rpc_limits
+            .acquire(method)
+            .await
+            .map_err(|error| RpcError::new(SERVER_BUSY, error.to_string()))?;
+        match method {
+            "rpc.discover" => Ok(self.rpc_spec()),
+            "getstatus" => to_result(json!({
+                "node_type": self.routing.router().node_type(),
+                "height": self.ledger.latest_height(),
+                "hash": self.ledger.latest_hash(),
+                "is_synced": self.sync.is_block_synced(),
+                "num_peers": self.routing.router().number_of_connected_peers(),
+                "sync_progress": self.sync.sync_progress(),
+            })),
+            "latestheight" => to_result(self.ledger.latest_height()),
+            "latesthash" => to_result(self.ledger.latest_hash()),
+            "latestblock" => to_result(self.ledger.latest_block()),
+            "getblock" => {
+                let height_or_hash: Value = param(params, 0, "height_or_hash")?;
+                let block = match &height_or_hash {
+                    Value::Number(height) => {
+                        let height = height.as_u64().and_then(|height| u32::try_from(height).ok());
+                        self.ledger.get_block(height.ok_or_else(|| RpcError::invalid_params("Invalid height"))?)?
+                    }
+                    Value::String(hash) => {
+                        let hash =
+                            hash.parse::<N::BlockHash>().map_err(|_| RpcError::invalid_params("Invalid hash"))?;
+                        self.ledger.get_block_by_hash(&hash)?
+                    }
+                    _ => return Err(RpcError::invalid_params("Expected a block height or a block hash")),
+                };
+                to_result(block)
+            }
+            "getblocks" => match params {
+                // Named parameters without a start height request a page, starting from the given cursor.
+                Value::Object(object) if !object.contains_key("start") => {
+                    to_result(self.get_blocks_page(&named_params::<BlockPageQuery>(params)?)?)
+                }
+                _ => {
+                    let start: u32 = param(params, 0, "start")?;
+                    let end: u32 = param(params, 1, "end")?;
+                    to_result(self.get_blocks_in_range(start, end)?)
+                }
+            },
+            "getblockstats" => {
+                let start = param(params, 0, "start")?;
+                match optional_param(params, 1, "end")? {
+                    Some(end) => to_result(self.block_stats_summary(start, end).await?),
+                    None => to_result(self.block_stats(start)?),
+                }
+            }
+            "scanblocks" => to_result(self.scan_blocks(named_params::<ScanQuery<N>>(params)?).await?),
+            "gettransaction" => {
+                let id = param(params, 0, "id")?;
+                let include_transitions = optional_param(params, 1, "transitions")?.unwrap_or(false);
+                to_result(self.transaction_with_metadata(id, include_transitions)?)
+            }
+            "getledgerproof" => to_result(self.ledger_proof(&named_params::<LedgerProofQuery<N>>(params)?)?),
+            "getconfirmedtransaction" => to_result(self.ledger.get_confirmed_transaction(param(params, 0, "id")?)?),
+            "getpeerinfo" => to_result(self.peer_info()),
+            "getnetworkinfo" => to_result(self.routing.router().network_info()),
+            "gettransactionsbyaddress" => {
+                let address = param(params, 0, "address")?;
+                // The pagination parameters may only be given by name.
+                let query = match params {
+                    Value::Object(_) => named_params::<AddressTransactionsQuery>(params)?,
+                    _ => AddressTransactionsQuery::default(),
+                };
+                to_result(self.transactions_by_address(&address, &query)?)
+            }
+            "getcommitmentsbyaddress" => {
+                let address = param(params, 0, "address")?;
+                // The pagination parameters may only be given by name.
+                let query = match params {
+                    Value::Object(_) => named_params::<AddressTransactionsQuery>(params)?,
+                    _ => AddressTransactionsQuery::default(),
+                };
+                to_result(self.commitments_by_address(&address, &query)?)
+            }
+            "validateaddress" => to_result(AddressValidation::<N>::new(&param::<String>(params, 0, "address")?)),
+            "decoderawtransaction" => {
+                let transaction = param::<String>(params, 0, "transaction")?;
+                to_result(DecodedTransaction::new::<N>(transaction.as_bytes())?)
+            }
+            "validaterawtransaction" => to_result(self.validate_transaction(param(params, 0, "transaction")?).await?),
+            "waitfornewblock" => to_result(self.wait_for_new_block(optional_param(params, 0, "timeout")?).await?),
+            "waitforheight" => {
+                let height = param(params, 0, "height")?;
+                to_result(self.wait_for_height(height, optional_param(params, 1, "timeout")?).await?)
+            }
+            "getblocktemplate" => to_result(self.block_template().await?),
+            "estimatefee" => {
+                let blocks = optional_param(params, 0, "blocks")?;
+                let size = optional_param(params, 1, "size")?;
+                to_result(self.estimate_fee(&FeeEstimateQuery { blocks, size }).await?)
+            }
+            "getmemorypoolinfo" => to_result(self.memory_pool_info().await?),
+            "getmemorypooltransactions" => {
+                to_result(self.memory_pool_transactions_page(&named_params::<MemoryPoolPageQuery>(params)?).await?)
+            }
+            "getmemorypooltransaction" => to_result(self.memory_pool_transaction(&param(params, 0, "id")?)?),
+            "addnode" => to_result(self.add_node(param(params, 0, "ip")?).await?),
+            "disconnectnode" => to_result(self.disconnect_node(param(params, 0, "ip")?).await?),
+            "setban" => {
+                let ip = param(params, 0, "ip")?;
+                let command = param::<String>(params, 1, "command")?;
+                let duration_secs = optional_param(params, 2, "duration_secs")?;
+                match command.as_str() {
+                    "add" => to_result(self.ban_ip(ip, duration_secs)),
+                    "remove" => to_result(self.unban_ip(&ip)?),
+                    _ => Err(RpcError::invalid_params("Expected the command 'add' or 'remove'")),
+                }
+            }
+            "listbanned" => to_result(self.banned_ips()),
+            "clearbanned" => to_result(self.routing.router().clear_banned_ips()),
+            "getapikeyusage" => to_result(self.config.api_keys.usage()),
+            "debug_dumppeers" => to_result(self.routing.router().debug_state()),
+            "debug_syncstate" => to_result(self.sync.debug_state()),
+            "debug_taskstats" => to_result(self.task_stats()),
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method '{method}' not found"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_param() {
+        // Positional and named parameters resolve to the same value.
+        assert_eq!(param::<u32>(&json!([5, 10]), 1, "end").unwrap(), 10);
+        assert_eq!(param::<u32>(&json!({"start": 5, "end": 10}), 1, "end").unwrap(), 10);
+        // Missing and mistyped parameters are rejected as invalid params.
+        assert_eq!(param::<u32>(&json!([5]), 1, "end").unwrap_err().code, INVALID_PARAMS);
+        assert_eq!(param::<u32>(&json!(["five"]), 0, "start").unwrap_err().code, INVALID_PARAMS);
+        assert_eq!(param::<u32>(&Value::Null, 0, "start").unwrap_err().code, INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{address_index::transaction_addresses, ledger_range::blocks};
+use snarkvm::prelude::{
+    block::{Output, Transaction},
+    Address,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks scanned per call.
+pub const MAX_SCAN_RANGE: u32 = 1_000;
+
+/// The `scan_blocks` query object.
+#[derive(Deserialize, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct ScanQuery<N: Network> {
+    /// The starting block height (inclusive).
+    start: u32,
+    /// The ending block height (exclusive).
+    end: u32,
+    /// Only match transactions that call the given program.
+    program_id: Option<ProgramID<N>>,
+    /// Only match transactions that publicly reference the given address.
+    address: Option<Address<N>>,
+    /// Only match transactions that create the record with the given commitment.
+    commitment: Option<Field<N>>,
+}
+
+impl<N: Network> ScanQuery<N> {
+    /// Returns `true` if the given transaction matches every filter.
+    fn matches(&self, transaction: &Transaction<N>) -> bool {
+        if let Some(program_id) = &self.program_id {
+            if !transaction.transitions().any(|transition| transition.program_id() == program_id) {
+                return false;
+            }
+        }
+        if let Some(commitment) = &self.commitment {
+            let creates_commitment = transaction.transitions().flat_map(|transition| transition.outputs()).any(
+                |output| matches!(output, Output::Record(output_commitment, ..) if output_commitment == commitment),
+            );
+            if !creates_commitment {
+                return false;
+            }
+        }
+        if let Some(address) = &self.address {
+            if !transaction_addresses(transaction).contains(address) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A transaction matched by a block scan.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct ScanMatch<N: Network> {
+    /// The height of the block containing the transaction.
+    block_height: u32,
+    /// The transaction.
+    transaction: Transaction<N>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/blocks/scan?start={start_height}&end={end_height}&program_id={programID}
+    // GET /mainnet/blocks/scan?start={start_height}&end={end_height}&address={address}&commitment={commitment}
+    pub(crate) async fn get_scan_blocks(
+        State(rest): State<Self>,
+        Query(query): Query<ScanQuery<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.scan_blocks(query).await?))
+    }
+
+    /// Returns the transactions in the given block range that match the filters.
+    pub(crate) async fn scan_blocks(&self, query: ScanQuery<N>) -> Result<Vec<ScanMatch<N>>, RestError> {
+        // Ensure the block range is valid and bounded.
+        if query.start > query.end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+        if query.end - query.start > MAX_SCAN_RANGE {
+            return Err(RestError(format!(
+                "Cannot scan more than {MAX_SCAN_RANGE} blocks per call (requested {})",
+                query.end - query.start
+            )));
+        }
+
+        // Load and filter the blocks off the async runtime.
+        let ledger = self.ledger.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut matches = Vec::new();
+            for block in blocks(&ledger, query.start..query.end) {
+                let block = block?;
+                matches.extend(
+                    block.transactions().iter().filter(|confirmed| query.matches(confirmed.transaction())).map(
+                        |confirmed| ScanMatch {
+                            block_height: block.height(),
+                            transaction: confirmed.transaction().clone(),
+                        },
+                    ),
+                );
+            }
+            Ok::<_, RestError>(matches)
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to scan the blocks - {e}")))?
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::StatePath;
+
+use lru::LruCache;
+use std::num::NonZeroUsize;
+
+/// The number of state paths held by the cache.
+const STATE_PATH_CACHE_SIZE: usize = 1024;
+
+/// A cache of the state paths recently computed for record commitments.
+/// A state path proves a commitment against the global state root it was computed at, so the cache only holds
+/// the state paths of the latest state root, and discards them once the ledger advances (or is rolled back).
+pub(crate) struct StatePathCache<N: Network> {
+    /// The state root of the cached state paths, and the state paths keyed by commitment.
+    entries: Mutex<(Option<N::StateRoot>, LruCache<Field<N>, StatePath<N>>)>,
+}
+
+impl<N: Network> StatePathCache<N> {
+    /// Initializes a new, empty state path cache.
+    pub(crate) fn new() -> Self {
+        let capacity = NonZeroUsize::new(STATE_PATH_CACHE_SIZE).expect("The state path cache size is non-zero");
+        Self { entries: Mutex::new((None, LruCache::new(capacity))) }
+    }
+
+    /// Returns the state path for the given commitment at the latest state root, computing it if it is not cached.
+    pub(crate) fn get<C: ConsensusStorage<N>>(
+        &self,
+        ledger: &Ledger<N, C>,
+        commitment: &Field<N>,
+    ) -> Result<StatePath<N>> {
+        let state_root = ledger.latest_state_root();
+        {
+            let mut entries = self.entries.lock();
+            if entries.0 == Some(state_root) {
+                if let Some(state_path) = entries.1.get(commitment) {
+                    return Ok(state_path.clone());
+                }
+            }
+        }
+
+        // Compute the state path without holding the lock, as the computation is expensive.
+        let state_path = ledger.get_state_path_for_commitment(commitment)?;
+        // Only cache the state path if the ledger did not advance in the meantime, as it may belong to either root.
+        if ledger.latest_state_root() == state_root {
+            let mut entries = self.entries.lock();
+            if entries.0 != Some(state_root) {
+                entries.1.clear();
+                entries.0 = Some(state_root);
+            }
+            entries.1.put(*commitment, state_path.clone());
+        }
+        Ok(state_path)
+    }
+}
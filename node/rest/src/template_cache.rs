@@ -0,0 +1,174 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_consensus::FeeDensity;
+use snarkvm::prelude::{block::Transaction, ToBytes};
+
+use indexmap::IndexSet;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
+/// The interval at which the block template is rebuilt in the background, in milliseconds.
+const TEMPLATE_REFRESH_INTERVAL_IN_MS: u64 = 500;
+
+/// The position of a candidate transaction in the block template: the highest fee density first,
+/// and the memory pool order among transactions of equal fee density.
+type CandidateOrder = (Reverse<FeeDensity>, u64);
+
+/// A cache of the candidate transactions of the block template.
+/// The candidates are kept deserialized and ordered by fee density, and follow the memory pool incrementally,
+/// so each update only deserializes and sizes the transactions that entered the memory pool since the previous one.
+pub(crate) struct BlockTemplateCache<N: Network> {
+    /// The candidate transactions.
+    candidates: Mutex<Candidates<N>>,
+}
+
+/// The candidate transactions of the block template.
+struct Candidates<N: Network> {
+    /// The candidate transactions by ID, with their position in the block template.
+    transactions: HashMap<N::TransactionID, (CandidateOrder, Transaction<N>)>,
+    /// The IDs of the candidate transactions, in block template order.
+    order: BTreeMap<CandidateOrder, N::TransactionID>,
+    /// The sequence number of the next candidate transaction.
+    next_sequence: u64,
+}
+
+impl<N: Network> Candidates<N> {
+    /// Inserts the given transaction, unless it is already a candidate.
+    fn insert(&mut self, transaction_id: N::TransactionID, fee_density: FeeDensity, transaction: Transaction<N>) {
+        if self.transactions.contains_key(&transaction_id) {
+            return;
+        }
+        let order = (Reverse(fee_density), self.next_sequence);
+        self.next_sequence += 1;
+        self.order.insert(order, transaction_id);
+        self.transactions.insert(transaction_id, (order, transaction));
+    }
+
+    /// Removes the transactions that are not in the given set.
+    fn retain(&mut self, transaction_ids: &IndexSet<N::TransactionID>) {
+        let order = &mut self.order;
+        self.transactions.retain(|transaction_id, (position, _)| {
+            let is_retained = transaction_ids.contains(transaction_id);
+            if !is_retained {
+                order.remove(&*position);
+            }
+            is_retained
+        });
+    }
+}
+
+impl<N: Network> BlockTemplateCache<N> {
+    /// Initializes a new, empty block template cache.
+    pub(crate) fn new() -> Self {
+        Self {
+            candidates: Mutex::new(Candidates {
+                transactions: HashMap::new(),
+                order: BTreeMap::new(),
+                next_sequence: 0,
+            }),
+        }
+    }
+
+    /// Returns the candidate transactions for the block template, given the transactions of the memory pool.
+    pub(crate) fn candidates(
+        &self,
+        unconfirmed: impl Iterator<Item = (N::TransactionID, Data<Transaction<N>>)>,
+    ) -> Vec<Transaction<N>> {
+        self.refresh(unconfirmed);
+        // Take the candidates with the highest fee densities.
+        let candidates = self.candidates.lock();
+        candidates
+            .order
+            .values()
+            .take(MAX_TEMPLATE_TRANSACTIONS)
+            .map(|transaction_id| candidates.transactions[transaction_id].1.clone())
+            .collect()
+    }
+
+    /// Returns the fee densities of the cached transactions of the memory pool, highest first.
+    pub(crate) fn fee_densities(&self) -> Vec<FeeDensity> {
+        self.candidates.lock().order.keys().map(|(Reverse(fee_density), _)| *fee_density).collect()
+    }
+
+    /// Updates the candidate transactions with the transactions of the memory pool.
+    /// The new transactions are deserialized outside of the lock.
+    pub(crate) fn refresh(&self, unconfirmed: impl Iterator<Item = (N::TransactionID, Data<Transaction<N>>)>) {
+        let mut unconfirmed_ids = IndexSet::new();
+        let new_transactions = {
+            let candidates = self.candidates.lock();
+            unconfirmed
+                .filter(|(transaction_id, _)| {
+                    unconfirmed_ids.insert(*transaction_id);
+                    !candidates.transactions.contains_key(transaction_id)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Deserialize the new transactions, and compute their fee densities.
+        let new_transactions = new_transactions
+            .into_iter()
+            .filter_map(|(transaction_id, transaction)| {
+                let (fee_density, transaction) = to_candidate(transaction)?;
+                Some((transaction_id, fee_density, transaction))
+            })
+            .collect::<Vec<_>>();
+
+        // Add the new transactions, and remove the transactions that left the memory pool.
+        let mut candidates = self.candidates.lock();
+        for (transaction_id, fee_density, transaction) in new_transactions {
+            candidates.insert(transaction_id, fee_density, transaction);
+        }
+        candidates.retain(&unconfirmed_ids);
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Keeps the block template up to date with the memory pool in the background,
+    /// so a template request only pays for the transactions that arrived since the last refresh.
+    pub(crate) fn spawn_template_refresher(&self, consensus: Consensus<N>) {
+        let template_cache = self.template_cache.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(TEMPLATE_REFRESH_INTERVAL_IN_MS));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let (template_cache, consensus) = (template_cache.clone(), consensus.clone());
+                let result = tokio::task::spawn_blocking(move || {
+                    template_cache.refresh(consensus.unconfirmed_transactions());
+                })
+                .await;
+                if let Err(error) = result {
+                    error!("The block template refresher panicked - {error}");
+                    return;
+                }
+            }
+        }));
+    }
+}
+
+/// Deserializes the given transaction of the memory pool, and returns it with its fee density.
+fn to_candidate<N: Network>(transaction: Data<Transaction<N>>) -> Option<(FeeDensity, Transaction<N>)> {
+    // Use the size of the buffer, if the transaction has not been deserialized yet.
+    let size = match &transaction {
+        Data::Buffer(bytes) => bytes.len(),
+        Data::Object(transaction) => transaction.to_bytes_le().ok()?.len(),
+    };
+    let transaction = transaction.deserialize_blocking().ok()?;
+    let fee = transaction.fee_amount().map(|fee| *fee).unwrap_or_default();
+    Some((FeeDensity::new(fee, size), transaction))
+}
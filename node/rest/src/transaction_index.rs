@@ -0,0 +1,206 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use anyhow::ensure;
+use parking_lot::RwLock;
+use rocksdb::{WriteBatch, DB};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// The directory in the ledger directory that holds the on-disk transaction index.
+const TRANSACTION_INDEX_DIR: &str = "TXINDEX";
+/// The key prefix of the transaction entries in the on-disk index.
+const TRANSACTION_PREFIX: u8 = 0;
+/// The key prefix of the transition entries in the on-disk index.
+const TRANSITION_PREFIX: u8 = 1;
+/// The key of the next block height to index, in the on-disk index.
+const NEXT_HEIGHT_KEY: &[u8] = &[2];
+
+/// Returns the path of the on-disk transaction index in the given ledger directory.
+pub fn transaction_index_path(ledger_path: &Path) -> PathBuf {
+    ledger_path.join(TRANSACTION_INDEX_DIR)
+}
+
+/// The location of a confirmed transaction in the ledger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TransactionLocation {
+    /// The height of the block containing the transaction.
+    pub(crate) block_height: u32,
+    /// The position of the transaction in its block.
+    pub(crate) index: u32,
+}
+
+impl TransactionLocation {
+    /// Returns the location as bytes.
+    fn to_bytes(self) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&self.block_height.to_le_bytes());
+        bytes[4..].copy_from_slice(&self.index.to_le_bytes());
+        bytes
+    }
+
+    /// Reads the location from the given bytes.
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(bytes.len() >= 8, "The transaction location is truncated");
+        let block_height = u32::from_le_bytes(bytes[..4].try_into()?);
+        let index = u32::from_le_bytes(bytes[4..8].try_into()?);
+        Ok(Self { block_height, index })
+    }
+}
+
+/// The storage of the transaction index.
+enum IndexStorage<N: Network> {
+    /// The index is kept in memory, and rebuilt on every start.
+    Memory {
+        /// The map of transaction IDs to their locations.
+        transactions: RwLock<HashMap<N::TransactionID, TransactionLocation>>,
+        /// The map of transition IDs to their transaction IDs and locations.
+        transitions: RwLock<HashMap<N::TransitionID, (N::TransactionID, TransactionLocation)>>,
+    },
+    /// The index is kept in its own database, and resumes from the last indexed block on start.
+    Disk(DB),
+}
+
+/// An index of the location of each confirmed transaction, and of the transaction of each transition,
+/// which resolves a transaction to its block without a lookup in the ledger.
+pub(crate) struct TransactionIndex<N: Network> {
+    /// The storage of the index.
+    storage: IndexStorage<N>,
+    /// The next block height to index.
+    next_height: AtomicU32,
+}
+
+impl<N: Network> TransactionIndex<N> {
+    /// Initializes a new, empty transaction index in memory.
+    pub(crate) fn new() -> Self {
+        let storage = IndexStorage::Memory { transactions: Default::default(), transitions: Default::default() };
+        Self { storage, next_height: AtomicU32::new(0) }
+    }
+
+    /// Opens the on-disk transaction index at the given path, creating it if it does not exist.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let db = DB::open_default(path)?;
+        let next_height = match db.get(NEXT_HEIGHT_KEY)? {
+            Some(bytes) => u32::from_le_bytes(bytes.as_slice().try_into()?),
+            None => 0,
+        };
+        Ok(Self { storage: IndexStorage::Disk(db), next_height: AtomicU32::new(next_height) })
+    }
+
+    /// Returns the location of the given transaction, if it is indexed.
+    pub(crate) fn get(&self, transaction_id: &N::TransactionID) -> Option<TransactionLocation> {
+        match &self.storage {
+            IndexStorage::Memory { transactions, .. } => transactions.read().get(transaction_id).copied(),
+            IndexStorage::Disk(db) => {
+                let key = Self::key(TRANSACTION_PREFIX, transaction_id).ok()?;
+                Self::read(db, &key, TransactionLocation::from_bytes)
+            }
+        }
+    }
+
+    /// Returns the ID and the location of the transaction of the given transition, if it is indexed.
+    pub(crate) fn get_transition(
+        &self,
+        transition_id: &N::TransitionID,
+    ) -> Option<(N::TransactionID, TransactionLocation)> {
+        match &self.storage {
+            IndexStorage::Memory { transitions, .. } => transitions.read().get(transition_id).cloned(),
+            IndexStorage::Disk(db) => {
+                let key = Self::key(TRANSITION_PREFIX, transition_id).ok()?;
+                Self::read(db, &key, |bytes| {
+                    let location = TransactionLocation::from_bytes(bytes)?;
+                    Ok((N::TransactionID::from_bytes_le(&bytes[8..])?, location))
+                })
+            }
+        }
+    }
+
+    /// Returns the key of the given ID in the on-disk index.
+    fn key(prefix: u8, id: &impl ToBytes) -> Result<Vec<u8>> {
+        let mut key = vec![prefix];
+        id.write_le(&mut key)?;
+        Ok(key)
+    }
+
+    /// Reads and decodes the value of the given key in the on-disk index.
+    /// Note: An error is logged, and the entry is treated as missing, so the lookup falls back to the ledger.
+    fn read<T>(db: &DB, key: &[u8], decode: impl FnOnce(&[u8]) -> Result<T>) -> Option<T> {
+        match db.get(key).map_err(anyhow::Error::from).and_then(|bytes| bytes.map(|bytes| decode(&bytes)).transpose()) {
+            Ok(value) => value,
+            Err(error) => {
+                warn!("Failed to read the transaction index - {error}");
+                None
+            }
+        }
+    }
+}
+
+impl<N: Network> BlockIndex<N> for TransactionIndex<N> {
+    const NAME: &'static str = "transaction index";
+
+    fn next_height(&self) -> u32 {
+        self.next_height.load(Ordering::SeqCst)
+    }
+
+    fn index_block(&self, block: &Block<N>) -> Result<()> {
+        let next_height = block.height().saturating_add(1);
+        match &self.storage {
+            IndexStorage::Memory { transactions, transitions } => {
+                let (mut transactions, mut transitions) = (transactions.write(), transitions.write());
+                for (index, confirmed) in block.transactions().iter().enumerate() {
+                    let location = TransactionLocation { block_height: block.height(), index: index as u32 };
+                    transactions.insert(confirmed.id(), location);
+                    for transition in confirmed.transaction().transitions() {
+                        transitions.insert(*transition.id(), (confirmed.id(), location));
+                    }
+                }
+            }
+            IndexStorage::Disk(db) => {
+                // Write the entries of the block and the next height atomically, so the index resumes consistently.
+                let mut batch = WriteBatch::default();
+                for (index, confirmed) in block.transactions().iter().enumerate() {
+                    let location = TransactionLocation { block_height: block.height(), index: index as u32 };
+                    batch.put(Self::key(TRANSACTION_PREFIX, &confirmed.id())?, location.to_bytes());
+                    let mut value = location.to_bytes().to_vec();
+                    confirmed.id().write_le(&mut value)?;
+                    for transition in confirmed.transaction().transitions() {
+                        batch.put(Self::key(TRANSITION_PREFIX, transition.id())?, &value);
+                    }
+                }
+                batch.put(NEXT_HEIGHT_KEY, next_height.to_le_bytes());
+                db.write(batch)?;
+            }
+        }
+        self.next_height.store(next_height, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_location_bytes() {
+        let location = TransactionLocation { block_height: 123_456, index: 7 };
+        assert_eq!(TransactionLocation::from_bytes(&location.to_bytes()).unwrap(), location);
+        assert!(TransactionLocation::from_bytes(&[0u8; 7]).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use anyhow::Context;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto::Builder,
+    service::TowerToHyperService,
+};
+use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+use tokio::net::UnixListener;
+
+/// The permissions of the Unix socket, which restrict it to the user running the node.
+const UNIX_SOCKET_MODE: u32 = 0o600;
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /mainnet/rpc (Unix socket)
+    async fn unix_rpc(State(rest): State<Self>, body: String) -> Response {
+        // Access to the socket is controlled by its file permissions, so every call is authenticated.
+        rest.handle_rpc(&body, true, None, RpcCaller::Unix).await
+    }
+
+    /// Serves the JSON-RPC endpoint on the Unix socket at the given path, until the server shuts down.
+    pub(crate) fn spawn_unix_socket_server(&self, path: &Path) -> Result<()> {
+        // Remove the socket left behind by a previous run, if any.
+        if path.exists() {
+            fs::remove_file(path).with_context(|| format!("Failed to remove the stale socket '{}'", path.display()))?;
+        }
+        let listener =
+            UnixListener::bind(path).with_context(|| format!("Failed to bind the Unix socket '{}'", path.display()))?;
+        fs::set_permissions(path, fs::Permissions::from_mode(UNIX_SOCKET_MODE))?;
+        info!("REST server is serving JSON-RPC on the Unix socket '{}'", path.display());
+
+        let router = axum::Router::new()
+            .route("/mainnet/rpc", post(Self::unix_rpc))
+            .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+            .with_state(self.clone());
+        let (path, shutdown) = (path.to_path_buf(), self.shutdown.clone());
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    result = listener.accept() => match result {
+                        Ok((stream, _)) => stream,
+                        Err(error) => {
+                            warn!("Failed to accept a Unix socket connection - {error}");
+                            continue;
+                        }
+                    },
+                    _ = shutdown.wait_for_signal() => break,
+                };
+                let service = TowerToHyperService::new(router.clone());
+                tokio::spawn(async move {
+                    if let Err(error) =
+                        Builder::new(TokioExecutor::new()).serve_connection(TokioIo::new(stream), service).await
+                    {
+                        debug!("Failed to serve a Unix socket connection - {error}");
+                    }
+                });
+            }
+            // Remove the socket, so clients fail fast instead of connecting to a stopped server.
+            let _ = fs::remove_file(&path);
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use axum::response::IntoResponse;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Executes the given JSON-RPC method, and returns the result as a plain JSON response.
+    async fn serve_v1(&self, method: &str, params: Value) -> Response {
+        match self.call_rpc_method(method, &params).await {
+            Ok(result) => ErasedJson::pretty(result).into_response(),
+            Err(error) => (error.status_code(), Json(json!({ "error": error }))).into_response(),
+        }
+    }
+
+    /// Returns the given query parameters as named JSON-RPC parameters.
+    /// Numeric and boolean values are parsed, so that they deserialize into the expected types.
+    fn v1_params(query: HashMap<String, String>) -> Value {
+        let params = query
+            .into_iter()
+            .map(|(key, value)| {
+                let value = serde_json::from_str::<Value>(&value)
+                    .ok()
+                    .filter(|value| value.is_number() || value.is_boolean())
+                    .unwrap_or(Value::String(value));
+                (key, value)
+            })
+            .collect::<Map<_, _>>();
+        Value::Object(params)
+    }
+
+    // GET /v1/status
+    pub(crate) async fn v1_status(State(rest): State<Self>) -> Response {
+        rest.serve_v1("getstatus", Value::Null).await
+    }
+
+    // GET /v1/block/{height}
+    // GET /v1/block/{blockHash}
+    pub(crate) async fn v1_block(State(rest): State<Self>, Path(height_or_hash): Path<String>) -> Response {
+        let param = match height_or_hash.parse::<u32>() {
+            Ok(height) => json!(height),
+            Err(_) => json!(height_or_hash),
+        };
+        rest.serve_v1("getblock", json!([param])).await
+    }
+
+    // GET /v1/blocks?cursor={height}&end={end_height}&limit={limit}&headers_only={bool}
+    pub(crate) async fn v1_blocks(State(rest): State<Self>, Query(query): Query<HashMap<String, String>>) -> Response {
+        rest.serve_v1("getblocks", Self::v1_params(query)).await
+    }
+
+    // GET /v1/transaction/{transactionID}
+    pub(crate) async fn v1_transaction(State(rest): State<Self>, Path(id): Path<String>) -> Response {
+        rest.serve_v1("gettransaction", json!([id])).await
+    }
+
+    // GET /v1/address/{address}/transactions?cursor={index}&limit={limit}
+    pub(crate) async fn v1_address_transactions(
+        State(rest): State<Self>,
+        Path(address): Path<String>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Response {
+        let mut params = Self::v1_params(query);
+        params["address"] = json!(address);
+        rest.serve_v1("gettransactionsbyaddress", params).await
+    }
+
+    // GET /v1/peers
+    pub(crate) async fn v1_peers(State(rest): State<Self>) -> Response {
+        rest.serve_v1("getpeerinfo", Value::Null).await
+    }
+
+    // GET /v1/network
+    pub(crate) async fn v1_network(State(rest): State<Self>) -> Response {
+        rest.serve_v1("getnetworkinfo", Value::Null).await
+    }
+
+    // GET /v1/mempool
+    pub(crate) async fn v1_mempool(State(rest): State<Self>) -> Response {
+        rest.serve_v1("getmemorypoolinfo", Value::Null).await
+    }
+
+    // GET /v1/mempool/transactions?cursor={index}&limit={limit}
+    pub(crate) async fn v1_mempool_transactions(
+        State(rest): State<Self>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Response {
+        rest.serve_v1("getmemorypooltransactions", Self::v1_params(query)).await
+    }
+
+    // GET /v1/mempool/transaction/{transactionID}
+    pub(crate) async fn v1_mempool_transaction(State(rest): State<Self>, Path(id): Path<String>) -> Response {
+        rest.serve_v1("getmemorypooltransaction", json!([id])).await
+    }
+}
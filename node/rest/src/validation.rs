@@ -0,0 +1,210 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{block::Transaction, Address, ViewKey};
+
+use anyhow::anyhow;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The outcome of a single acceptance check.
+#[derive(Debug, Serialize)]
+pub(crate) struct ValidationCheck {
+    /// The name of the check.
+    check: &'static str,
+    /// Whether the transaction passed the check.
+    passed: bool,
+    /// The reason the transaction failed the check, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl ValidationCheck {
+    /// Initializes a new check outcome from the given result.
+    fn new(check: &'static str, result: Result<()>) -> Self {
+        match result {
+            Ok(()) => Self { check, passed: true, reason: None },
+            Err(error) => Self { check, passed: false, reason: Some(error.to_string()) },
+        }
+    }
+}
+
+/// The outcome of a dry-run validation of a transaction.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct TransactionValidation<N: Network> {
+    /// The transaction ID.
+    transaction_id: N::TransactionID,
+    /// Whether the transaction passed every check, and would be accepted into the memory pool.
+    is_valid: bool,
+    /// The outcome of each check, in the order they were run.
+    checks: Vec<ValidationCheck>,
+}
+
+/// The kind of account string that was validated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AccountStringKind {
+    /// An Aleo address, prefixed by `aleo1`.
+    Address,
+    /// An Aleo view key, prefixed by `AViewKey1`.
+    ViewKey,
+}
+
+/// The outcome of validating an address or a view key.
+#[derive(Debug, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct AddressValidation<N: Network> {
+    /// Whether the string is a well-formed address or view key.
+    is_valid: bool,
+    /// The kind of account string, if it has a known prefix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<AccountStringKind>,
+    /// The name of the network that the node runs on.
+    network: &'static str,
+    /// The ID of the network that the node runs on.
+    network_id: u16,
+    /// The address, or the address derived from the view key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<Address<N>>,
+    /// The x-coordinate of the address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x_coordinate: Option<Field<N>>,
+    /// The reason the string is invalid, if it is invalid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl<N: Network> AddressValidation<N> {
+    /// Validates the given address or view key. The view key itself is never echoed back.
+    pub(crate) fn new(input: &str) -> Self {
+        let input = input.trim();
+        let (kind, result) = if input.starts_with("aleo1") {
+            (Some(AccountStringKind::Address), Address::<N>::from_str(input))
+        } else if input.starts_with("AViewKey1") {
+            (Some(AccountStringKind::ViewKey), ViewKey::<N>::from_str(input).and_then(Address::try_from))
+        } else {
+            (None, Err(anyhow!("Expected an address ('aleo1...') or a view key ('AViewKey1...')")))
+        };
+        let (address, reason) = match result {
+            Ok(address) => (Some(address), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        Self {
+            is_valid: address.is_some(),
+            kind,
+            network: N::NAME,
+            network_id: N::ID,
+            x_coordinate: address.map(|address| address.to_x_coordinate()),
+            address,
+            reason,
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /mainnet/address/validate
+    pub(crate) async fn address_validate(Json(input): Json<String>) -> ErasedJson {
+        ErasedJson::pretty(AddressValidation::<N>::new(&input))
+    }
+
+    // POST /mainnet/transaction/validate
+    pub(crate) async fn transaction_validate(
+        State(rest): State<Self>,
+        Json(transaction): Json<Transaction<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.validate_transaction(transaction).await?))
+    }
+
+    /// Runs the memory pool acceptance checks on the given transaction, without broadcasting or storing it.
+    pub(crate) async fn validate_transaction(
+        &self,
+        transaction: Transaction<N>,
+    ) -> Result<TransactionValidation<N>, RestError> {
+        let transaction_id = transaction.id();
+        let mut checks = Vec::with_capacity(4);
+
+        // Ensure the transaction is not a standalone fee transaction.
+        checks.push(ValidationCheck::new("type", match transaction.is_fee() {
+            true => Err(anyhow!("Fee transactions are not accepted into the memory pool")),
+            false => Ok(()),
+        }));
+        // Ensure the transaction is not already in the ledger.
+        checks.push(ValidationCheck::new("unique", match self.ledger.contains_transaction_id(&transaction_id)? {
+            true => Err(anyhow!("Transaction '{transaction_id}' already exists in the ledger")),
+            false => Ok(()),
+        }));
+        // Ensure the transaction is not already in the memory pool.
+        if self.consensus.is_some() {
+            let is_pending = self.memory_pool_transaction(&transaction_id).is_ok();
+            checks.push(ValidationCheck::new("memory_pool", match is_pending {
+                true => Err(anyhow!("Transaction '{transaction_id}' is already in the memory pool")),
+                false => Ok(()),
+            }));
+        }
+        // Ensure the transaction is well-formed, and that its proofs and fee are valid.
+        let ledger = self.ledger.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            ledger.check_transaction_basic(&transaction, None, &mut rand::thread_rng())
+        })
+        .await
+        .map_err(|e| RestError(format!("Failed to validate the transaction - {e}")))?;
+        checks.push(ValidationCheck::new("well_formed", result));
+
+        let is_valid = checks.iter().all(|check| check.passed);
+        Ok(TransactionValidation { transaction_id, is_valid, checks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{MainnetV0, PrivateKey};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_address_validation() {
+        let private_key = PrivateKey::<CurrentNetwork>::new(&mut rand::thread_rng()).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+
+        // A valid address.
+        let validation = AddressValidation::<CurrentNetwork>::new(&address.to_string());
+        assert!(validation.is_valid);
+        assert_eq!(validation.kind, Some(AccountStringKind::Address));
+        assert_eq!(validation.address, Some(address));
+
+        // A valid view key resolves to its address.
+        let validation = AddressValidation::<CurrentNetwork>::new(&view_key.to_string());
+        assert!(validation.is_valid);
+        assert_eq!(validation.kind, Some(AccountStringKind::ViewKey));
+        assert_eq!(validation.address, Some(address));
+
+        // A corrupted address.
+        let mut corrupted = address.to_string();
+        corrupted.pop();
+        corrupted.push(if address.to_string().ends_with('q') { 'p' } else { 'q' });
+        let validation = AddressValidation::<CurrentNetwork>::new(&corrupted);
+        assert!(!validation.is_valid);
+        assert_eq!(validation.kind, Some(AccountStringKind::Address));
+        assert!(validation.reason.is_some());
+
+        // An unknown prefix.
+        let validation = AddressValidation::<CurrentNetwork>::new("APrivateKey1abc");
+        assert!(!validation.is_valid);
+        assert_eq!(validation.kind, None);
+    }
+}
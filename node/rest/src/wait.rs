@@ -0,0 +1,93 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// The interval at which the ledger is checked while waiting, in milliseconds.
+const WAIT_POLL_INTERVAL_IN_MS: u64 = 250;
+/// The default number of seconds to wait.
+const DEFAULT_WAIT_TIMEOUT_IN_SECS: u64 = 30;
+/// The maximum number of seconds to wait.
+pub const MAX_WAIT_TIMEOUT_IN_SECS: u64 = 120;
+
+/// The `wait` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct WaitQuery {
+    /// The number of seconds to wait before returning the current tip.
+    timeout: Option<u64>,
+}
+
+/// The latest block, once the wait condition is met or the timeout expires.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct BlockTip<N: Network> {
+    /// The latest block height.
+    height: u32,
+    /// The latest block hash.
+    hash: N::BlockHash,
+    /// Whether the timeout expired before the condition was met.
+    timed_out: bool,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/wait/newBlock?timeout={seconds}
+    pub(crate) async fn get_wait_for_new_block(
+        State(rest): State<Self>,
+        Query(query): Query<WaitQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.wait_for_new_block(query.timeout).await?))
+    }
+
+    // GET /mainnet/wait/height/{height}?timeout={seconds}
+    pub(crate) async fn get_wait_for_height(
+        State(rest): State<Self>,
+        Path(height): Path<u32>,
+        Query(query): Query<WaitQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.wait_for_height(height, query.timeout).await?))
+    }
+
+    /// Waits until a block is added to the ledger, or the timeout expires.
+    pub(crate) async fn wait_for_new_block(&self, timeout_secs: Option<u64>) -> Result<BlockTip<N>, RestError> {
+        let height = self.ledger.latest_height().saturating_add(1);
+        self.wait_for_height(height, timeout_secs).await
+    }
+
+    /// Waits until the ledger reaches the given height, or the timeout expires.
+    pub(crate) async fn wait_for_height(
+        &self,
+        height: u32,
+        timeout_secs: Option<u64>,
+    ) -> Result<BlockTip<N>, RestError> {
+        let timeout_secs = timeout_secs.unwrap_or(DEFAULT_WAIT_TIMEOUT_IN_SECS);
+        if timeout_secs > MAX_WAIT_TIMEOUT_IN_SECS {
+            return Err(RestError(format!(
+                "The timeout must be at most {MAX_WAIT_TIMEOUT_IN_SECS} seconds (requested {timeout_secs})"
+            )));
+        }
+
+        let ledger = self.ledger.clone();
+        let wait = async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(WAIT_POLL_INTERVAL_IN_MS));
+            while ledger.latest_height() < height {
+                interval.tick().await;
+            }
+        };
+        let timed_out = tokio::time::timeout(Duration::from_secs(timeout_secs), wait).await.is_err();
+
+        Ok(BlockTip { height: self.ledger.latest_height(), hash: self.ledger.latest_hash(), timed_out })
+    }
+}
@@ -0,0 +1,465 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{address_index::transaction_addresses, export::BlockExportQuery};
+use snarkvm::prelude::{block::Transaction, Address, Identifier};
+
+use axum::{
+    extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::VecDeque, time::Duration};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+    Semaphore,
+};
+
+/// The interval at which the ledger is checked for new blocks, in milliseconds.
+const LEDGER_POLL_INTERVAL_IN_MS: u64 = 1_000;
+/// The maximum number of blocks loaded per poll.
+const MAX_BLOCKS_PER_POLL: u32 = 50;
+/// The maximum number of pushed blocks remembered, to detect chain reorganizations.
+const MAX_REORG_DEPTH: usize = 100;
+/// The number of ledger events buffered per subscriber, before the subscriber starts skipping events.
+const LEDGER_EVENT_CHANNEL_CAPACITY: usize = 128;
+/// The maximum number of concurrent WebSocket connections.
+const MAX_WEBSOCKET_CONNECTIONS: usize = 256;
+
+/// A request sent by a WebSocket client.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SubscriptionRequest {
+    /// The client-chosen request ID, echoed back in the response.
+    #[serde(default)]
+    id: Value,
+    /// The method name.
+    method: String,
+    /// The method parameters, such as the filters of a transaction subscription.
+    #[serde(default)]
+    params: Value,
+}
+
+/// The filters of a transaction subscription. A transaction is pushed if it matches every filter.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub(crate) struct TransactionFilter<N: Network> {
+    /// Only match transactions with a transition in the given program.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    program_id: Option<ProgramID<N>>,
+    /// Only match transactions with a transition of the given function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function: Option<Identifier<N>>,
+    /// Only match transactions that publicly reference the given address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<Address<N>>,
+}
+
+impl<N: Network> TransactionFilter<N> {
+    /// Returns `true` if the given transaction matches every filter.
+    fn matches(&self, transaction: &Transaction<N>) -> bool {
+        // The program and function filters must match the same transition.
+        let calls_function = transaction.transitions().any(|transition| {
+            self.program_id.as_ref().map_or(true, |program_id| transition.program_id() == program_id)
+                && self.function.as_ref().map_or(true, |function| transition.function_name() == function)
+        });
+        calls_function
+            && self.address.as_ref().map_or(true, |address| transaction_addresses(transaction).contains(address))
+    }
+}
+
+/// The topics that a WebSocket client may subscribe to.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct Subscriptions<N: Network> {
+    blocks: bool,
+    transactions: bool,
+    /// The filters of the transaction subscription, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_filter: Option<TransactionFilter<N>>,
+    sync_status: bool,
+}
+
+impl<N: Network> Default for Subscriptions<N> {
+    fn default() -> Self {
+        Self { blocks: false, transactions: false, transaction_filter: None, sync_status: false }
+    }
+}
+
+impl<N: Network> Subscriptions<N> {
+    /// Applies the given method to the subscriptions, returning an error if the method or its parameters are invalid.
+    fn apply(&mut self, method: &str, params: &Value) -> Result<(), String> {
+        match method {
+            "subscribe_blocks" => self.blocks = true,
+            "subscribe_transactions" => {
+                // Replace the filters of any previous transaction subscription.
+                self.transaction_filter = match params {
+                    Value::Null => None,
+                    params => Some(
+                        serde_json::from_value(params.clone())
+                            .map_err(|e| format!("Invalid transaction filter - {e}"))?,
+                    ),
+                };
+                self.transactions = true;
+            }
+            "subscribe_sync_status" => self.sync_status = true,
+            "unsubscribe_blocks" => self.blocks = false,
+            "unsubscribe_transactions" => {
+                self.transactions = false;
+                self.transaction_filter = None;
+            }
+            "unsubscribe_sync_status" => self.sync_status = false,
+            _ => return Err(format!("Unknown method '{method}'")),
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the given transaction should be pushed to the client.
+    fn wants_transaction(&self, transaction: &Transaction<N>) -> bool {
+        self.transactions && self.transaction_filter.as_ref().map_or(true, |filter| filter.matches(transaction))
+    }
+
+    /// Returns `true` if the client is subscribed to any topic derived from new blocks.
+    const fn wants_blocks(&self) -> bool {
+        self.blocks || self.transactions
+    }
+}
+
+/// A ledger event, loaded once and broadcast to every subscriber.
+#[derive(Clone)]
+enum LedgerEvent<N: Network> {
+    /// A block was added to the ledger.
+    Block(Arc<Block<N>>),
+    /// The ledger switched to another chain. This holds the `chain_reorganized` notification.
+    Reorganization(Arc<Value>),
+}
+
+/// The state shared by the WebSocket connections.
+pub(crate) struct WebSocketState<N: Network> {
+    /// The sender of the ledger events.
+    events: broadcast::Sender<LedgerEvent<N>>,
+    /// The slots of the WebSocket connections.
+    connections: Arc<Semaphore>,
+}
+
+impl<N: Network> WebSocketState<N> {
+    /// Initializes the state of the WebSocket connections.
+    pub(crate) fn new() -> Self {
+        Self {
+            events: broadcast::channel(LEDGER_EVENT_CHANNEL_CAPACITY).0,
+            connections: Arc::new(Semaphore::new(MAX_WEBSOCKET_CONNECTIONS)),
+        }
+    }
+}
+
+/// The heights and hashes of the blocks most recently broadcast, oldest first.
+struct PushedBlocks<N: Network> {
+    blocks: VecDeque<(u32, N::BlockHash)>,
+}
+
+impl<N: Network> PushedBlocks<N> {
+    /// Initializes the pushed blocks, starting after the given block.
+    fn new(height: u32, hash: N::BlockHash) -> Self {
+        Self { blocks: VecDeque::from([(height, hash)]) }
+    }
+
+    /// Returns the height of the last pushed block.
+    fn last_height(&self) -> u32 {
+        self.blocks.back().map_or(0, |(height, _)| *height)
+    }
+
+    /// Records the given block as pushed.
+    fn push(&mut self, height: u32, hash: N::BlockHash) {
+        self.blocks.push_back((height, hash));
+        if self.blocks.len() > MAX_REORG_DEPTH {
+            self.blocks.pop_front();
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /mainnet/ws
+    pub(crate) async fn websocket(State(rest): State<Self>, ws: WebSocketUpgrade) -> Response {
+        // Ensure the number of connections is bounded.
+        let Ok(permit) = rest.websocket.connections.clone().try_acquire_owned() else {
+            return (StatusCode::SERVICE_UNAVAILABLE, "Too many WebSocket connections").into_response();
+        };
+        ws.on_upgrade(move |socket| async move {
+            rest.handle_websocket(socket).await;
+            drop(permit);
+        })
+    }
+
+    /// Serves the subscriptions of a single WebSocket client, until it disconnects.
+    async fn handle_websocket(self, mut socket: WebSocket) {
+        let mut subscriptions = Subscriptions::default();
+        // Initialize the receiver of the ledger events, while the client is subscribed to blocks or transactions.
+        let mut ledger_events = None;
+        // Subscribe to the sync progress events.
+        let mut progress_receiver = self.sync.subscribe_progress();
+        // Initialize the block export in progress, and the number of blocks it has sent.
+        let mut export = None;
+        let mut num_exported = 0u32;
+
+        loop {
+            let outgoing = tokio::select! {
+                // Handle the client requests.
+                request = socket.recv() => match request {
+                    Some(Ok(WsMessage::Text(text))) => match self.process_export_request(&text) {
+                        // Start a block export, replacing any export in progress.
+                        Some((response, receiver)) => {
+                            if receiver.is_some() {
+                                (export, num_exported) = (receiver, 0);
+                            }
+                            vec![response]
+                        }
+                        None => {
+                            let response = Self::process_subscription_request(&mut subscriptions, &text);
+                            // Only push the blocks created after the subscription.
+                            if !subscriptions.wants_blocks() {
+                                ledger_events = None;
+                            } else if ledger_events.is_none() {
+                                ledger_events = Some(self.websocket.events.subscribe());
+                            }
+                            vec![response]
+                        }
+                    },
+                    Some(Ok(WsMessage::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                },
+                // Push the new blocks and transactions.
+                event = Self::next_ledger_event(&mut ledger_events), if ledger_events.is_some() => match event {
+                    Ok(event) => Self::ledger_event_notifications(&subscriptions, event),
+                    // The client is too slow to keep up with the ledger.
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        vec![json!({ "subscription": "lagged", "result": { "skipped": num_skipped } })]
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                // Push the sync status.
+                progress = progress_receiver.recv(), if subscriptions.sync_status => match progress {
+                    Ok(progress) => vec![json!({ "subscription": "sync_status", "result": progress })],
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+                // Stream the exported blocks, which are already serialized.
+                line = Self::next_exported_block(&mut export), if export.is_some() => match line {
+                    Some(Ok(line)) => {
+                        num_exported += 1;
+                        let message = format!(r#"{{"subscription":"export","result":{line}}}"#);
+                        if socket.send(WsMessage::Text(message)).await.is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                    Some(Err(error)) => {
+                        export = None;
+                        vec![json!({ "subscription": "export_complete", "error": error.to_string() })]
+                    }
+                    None => {
+                        export = None;
+                        vec![json!({ "subscription": "export_complete", "result": { "exported": num_exported } })]
+                    }
+                },
+            };
+
+            // Send the notifications to the client.
+            for message in outgoing {
+                if socket.send(WsMessage::Text(message.to_string())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Processes a subscription request, and returns the response.
+    fn process_subscription_request(subscriptions: &mut Subscriptions<N>, text: &str) -> Value {
+        match serde_json::from_str::<SubscriptionRequest>(text) {
+            Ok(request) => match subscriptions.apply(&request.method, &request.params) {
+                Ok(()) => json!({ "id": request.id, "result": subscriptions }),
+                Err(error) => json!({ "id": request.id, "error": error }),
+            },
+            Err(error) => json!({ "id": Value::Null, "error": format!("Invalid request - {error}") }),
+        }
+    }
+
+    /// Processes an `export_blocks` request, returning the response and the receiver of the exported blocks,
+    /// or `None` if the request is not an export request.
+    fn process_export_request(&self, text: &str) -> Option<(Value, Option<mpsc::Receiver<Result<String>>>)> {
+        let request = serde_json::from_str::<SubscriptionRequest>(text).ok().filter(|r| r.method == "export_blocks")?;
+        let query = match serde_json::from_value::<BlockExportQuery>(request.params) {
+            Ok(query) => query,
+            Err(error) => {
+                return Some((json!({ "id": request.id, "error": format!("Invalid export - {error}") }), None))
+            }
+        };
+        match self.export_blocks(query) {
+            Ok(receiver) => Some((
+                json!({ "id": request.id, "result": { "start": query.start, "end": query.end } }),
+                Some(receiver),
+            )),
+            Err(error) => Some((json!({ "id": request.id, "error": error.0 }), None)),
+        }
+    }
+
+    /// Returns the next block of the given export, waiting forever if there is no export.
+    async fn next_exported_block(export: &mut Option<mpsc::Receiver<Result<String>>>) -> Option<Result<String>> {
+        match export {
+            Some(receiver) => receiver.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Returns the next ledger event, waiting forever if the client is not subscribed to blocks or transactions.
+    async fn next_ledger_event(
+        receiver: &mut Option<broadcast::Receiver<LedgerEvent<N>>>,
+    ) -> Result<LedgerEvent<N>, RecvError> {
+        match receiver {
+            Some(receiver) => receiver.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Returns the notifications of the given ledger event for the given subscriptions.
+    fn ledger_event_notifications(subscriptions: &Subscriptions<N>, event: LedgerEvent<N>) -> Vec<Value> {
+        let block = match event {
+            LedgerEvent::Block(block) => block,
+            LedgerEvent::Reorganization(notification) => return vec![(*notification).clone()],
+        };
+        let mut notifications = Vec::new();
+        if subscriptions.transactions {
+            for transaction in block.transactions().iter() {
+                if !subscriptions.wants_transaction(transaction.transaction()) {
+                    continue;
+                }
+                notifications.push(json!({ "subscription": "transactions", "result": transaction }));
+            }
+        }
+        if subscriptions.blocks {
+            notifications.push(json!({ "subscription": "blocks", "result": block }));
+        }
+        notifications
+    }
+
+    /// Broadcasts the blocks added to the ledger to the WebSocket subscribers, so each block is loaded once,
+    /// however many clients are subscribed.
+    pub(crate) fn spawn_block_broadcaster(&self) {
+        let (ledger, websocket) = (self.ledger.clone(), self.websocket.clone());
+        self.handles.lock().push(tokio::spawn(async move {
+            let mut pushed = PushedBlocks::new(ledger.latest_height(), ledger.latest_hash());
+            let mut interval = tokio::time::interval(Duration::from_millis(LEDGER_POLL_INTERVAL_IN_MS));
+            loop {
+                interval.tick().await;
+                // Skip the ledger reads while no client is subscribed.
+                if websocket.events.receiver_count() == 0 {
+                    pushed = PushedBlocks::new(ledger.latest_height(), ledger.latest_hash());
+                    continue;
+                }
+                // Load the new blocks off the async runtime.
+                let ledger = ledger.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let events = collect_ledger_events(&ledger, &mut pushed);
+                    (pushed, events)
+                })
+                .await;
+                let events;
+                (pushed, events) = match result {
+                    Ok(result) => result,
+                    Err(error) => {
+                        error!("The WebSocket block broadcaster panicked - {error}");
+                        return;
+                    }
+                };
+                for event in events {
+                    // Note: Sending only fails if every client unsubscribed in the meantime.
+                    let _ = websocket.events.send(event);
+                }
+            }
+        }));
+    }
+}
+
+/// Returns the ledger events since the last pushed block: a reorganization, if the last pushed block is no longer
+/// in the ledger, followed by the new blocks.
+fn collect_ledger_events<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    pushed: &mut PushedBlocks<N>,
+) -> Vec<LedgerEvent<N>> {
+    let mut events = Vec::new();
+    // Notify the clients if the pushed blocks are no longer in the ledger, before pushing the new chain.
+    if let Some(notification) = detect_reorganization(ledger, pushed) {
+        events.push(LedgerEvent::Reorganization(Arc::new(notification)));
+    }
+
+    let latest_height = ledger.latest_height();
+    // Bound the number of blocks loaded at once, so the clients catch up over several polls.
+    let end_height = latest_height.min(pushed.last_height().saturating_add(MAX_BLOCKS_PER_POLL));
+    for height in pushed.last_height().saturating_add(1)..=end_height {
+        let block = match ledger.get_block(height) {
+            Ok(block) => block,
+            Err(error) => {
+                warn!("Failed to retrieve block {height} for the WebSocket subscribers - {error}");
+                break;
+            }
+        };
+        pushed.push(height, block.hash());
+        events.push(LedgerEvent::Block(Arc::new(block)));
+    }
+    events
+}
+
+/// Returns a `chain_reorganized` notification if the last pushed block is no longer in the ledger,
+/// and rewinds the pushed blocks to the common ancestor, so the new chain is pushed from there.
+fn detect_reorganization<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    pushed: &mut PushedBlocks<N>,
+) -> Option<Value> {
+    let is_in_ledger = |(height, hash): &(u32, N::BlockHash)| ledger.get_hash(*height).ok() == Some(*hash);
+    let old_tip = *pushed.blocks.back()?;
+    if is_in_ledger(&old_tip) {
+        return None;
+    }
+    // Roll back the pushed blocks, until the most recent one that is still in the ledger.
+    let mut rolled_back = Vec::new();
+    while let Some(block) = pushed.blocks.back() {
+        if is_in_ledger(block) {
+            break;
+        }
+        rolled_back.push(block.1);
+        pushed.blocks.pop_back();
+    }
+    // If the reorganization is deeper than the remembered blocks, the common ancestor is unknown,
+    // so resume from the block below the oldest rolled back block.
+    let common_ancestor = pushed.blocks.back().copied();
+    if common_ancestor.is_none() {
+        let resume_height = old_tip.0.saturating_sub(rolled_back.len() as u32);
+        match ledger.get_hash(resume_height) {
+            Ok(hash) => pushed.blocks.push_back((resume_height, hash)),
+            Err(_) => pushed.blocks.push_back((ledger.latest_height(), ledger.latest_hash())),
+        }
+    }
+    warn!("Detected a chain reorganization of {} block(s) below height {}", rolled_back.len(), old_tip.0);
+
+    Some(json!({
+        "subscription": "chain_reorganized",
+        "result": {
+            "old_tip": { "height": old_tip.0, "hash": old_tip.1 },
+            "new_tip": { "height": ledger.latest_height(), "hash": ledger.latest_hash() },
+            "common_ancestor": common_ancestor.map(|(height, hash)| json!({ "height": height, "hash": hash })),
+            "rolled_back": rolled_back,
+        },
+    }))
+}
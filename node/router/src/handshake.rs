@@ -176,7 +176,7 @@ impl<N: Network> Router<N> {
         send(&mut framed, peer_addr, Message::ChallengeResponse(our_response)).await?;
 
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, &peer_request, ConnectionSide::Responder), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -245,7 +245,7 @@ impl<N: Network> Router<N> {
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
         // Add the peer to the router.
-        self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
+        self.insert_connected_peer(Peer::new(peer_ip, &peer_request, ConnectionSide::Initiator), peer_addr);
 
         Ok((peer_ip, framed))
     }
@@ -268,6 +268,10 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection request from '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip.ip()) {
+            bail!("Dropping connection request from '{peer_ip}' (banned)")
+        }
         // Ensure the peer is not spamming connection attempts.
         if !peer_ip.ip().is_loopback() {
             // Add this connection attempt and retrieve the number of attempts.
@@ -107,17 +107,12 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             return;
         }
 
-        // Retrieve the trusted peers.
-        let trusted = self.router().trusted_peers();
-        // Retrieve the bootstrap peers.
-        let bootstrap = self.router().bootstrap_peers();
-
-        // Find the oldest connected peer, that is neither trusted nor a bootstrap peer.
+        // Find the oldest connected peer, that is not protected from eviction.
         let oldest_peer = self
             .router()
             .get_connected_peers()
             .iter()
-            .filter(|peer| !trusted.contains(&peer.ip()) && !bootstrap.contains(&peer.ip()))
+            .filter(|peer| !self.router().is_protected(&peer.ip()))
             .min_by_key(|peer| peer.last_seen())
             .map(|peer| peer.ip());
 
@@ -130,8 +125,8 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         }
     }
 
-    /// TODO (howardwu): If the node is a validator, keep the validator.
     /// This function keeps the number of connected peers within the allowed range.
+    /// Protected peers (trusted, bootstrap, and validator connections) are never disconnected to make room.
     fn handle_connected_peers(&self) {
         // Obtain the number of connected peers.
         let num_connected = self.router().number_of_connected_peers();
@@ -152,11 +147,6 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
                 "Exceeded maximum number of connected peers, disconnecting from ({num_surplus_provers} + {num_surplus_clients_validators}) peers"
             );
 
-            // Retrieve the trusted peers.
-            let trusted = self.router().trusted_peers();
-            // Retrieve the bootstrap peers.
-            let bootstrap = self.router().bootstrap_peers();
-
             // Initialize an RNG.
             let rng = &mut OsRng;
 
@@ -165,19 +155,18 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
                 .router()
                 .connected_provers()
                 .into_iter()
-                .filter(|peer_ip| !trusted.contains(peer_ip) && !bootstrap.contains(peer_ip))
+                .filter(|peer_ip| !self.router().is_protected(peer_ip))
                 .choose_multiple(rng, num_surplus_provers);
 
-            // TODO (howardwu): As a validator, prioritize disconnecting from clients.
-            //  Remove RNG, pick the `n` oldest nodes.
-            // Determine the clients and validators to disconnect from.
+            // TODO (howardwu): Remove RNG, pick the `n` oldest nodes.
+            // Determine the clients to disconnect from, as validators are protected.
             let peer_ips_to_disconnect = self
                 .router()
                 .get_connected_peers()
                 .into_iter()
                 .filter_map(|peer| {
                     let peer_ip = peer.ip();
-                    if !peer.is_prover() && !trusted.contains(&peer_ip) && !bootstrap.contains(&peer_ip) {
+                    if !peer.is_prover() && !self.router().is_protected(&peer_ip) {
                         Some(peer_ip)
                     } else {
                         None
@@ -187,15 +176,6 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
 
             // Proceed to send disconnect requests to these peers.
             for peer_ip in peer_ips_to_disconnect.into_iter().chain(prover_ips_to_disconnect) {
-                // TODO (howardwu): Remove this after specializing this function.
-                if self.router().node_type().is_prover() {
-                    if let Some(peer) = self.router().get_connected_peer(&peer_ip) {
-                        if peer.node_type().is_validator() {
-                            continue;
-                        }
-                    }
-                }
-
                 info!("Disconnecting from '{peer_ip}' (exceeded maximum connections)");
                 self.send(peer_ip, Message::Disconnect(DisconnectReason::TooManyPeers.into()));
                 // Disconnect from this peer.
@@ -228,7 +208,7 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         // Split the bootstrap peers into connected and candidate lists.
         let mut connected_bootstrap = Vec::new();
         let mut candidate_bootstrap = Vec::new();
-        for bootstrap_ip in self.router().bootstrap_peers() {
+        for bootstrap_ip in self.router().bootstrap_peers().iter().copied() {
             match self.router().is_connected(&bootstrap_ip) {
                 true => connected_bootstrap.push(bootstrap_ip),
                 false => candidate_bootstrap.push(bootstrap_ip),
@@ -169,6 +169,19 @@ impl<N: Network> Cache<N> {
         Self::refresh_and_insert(&self.seen_outbound_transactions, (peer_ip, transaction))
     }
 
+    /// Returns the number of outstanding block requests, puzzle requests, and peer requests to the given peer.
+    pub fn num_outbound_requests(&self, peer_ip: &SocketAddr) -> (usize, u32, u32) {
+        let block_requests = self.seen_outbound_block_requests.read().get(peer_ip).map_or(0, HashSet::len);
+        let puzzle_requests = self.seen_outbound_puzzle_requests.read().get(peer_ip).copied().unwrap_or(0);
+        let peer_requests = self.seen_outbound_peer_requests.read().get(peer_ip).copied().unwrap_or(0);
+        (block_requests, puzzle_requests, peer_requests)
+    }
+
+    /// Returns the number of recent messages received from the given peer.
+    pub fn num_inbound_messages(&self, peer_ip: &SocketAddr) -> usize {
+        self.seen_inbound_messages.read().get(peer_ip).map_or(0, VecDeque::len)
+    }
+
     /// Returns `true` if the cache contains a peer request from the given peer.
     pub fn contains_outbound_peer_request(&self, peer_ip: SocketAddr) -> bool {
         self.seen_outbound_peer_requests.read().get(&peer_ip).map(|r| *r > 0).unwrap_or(false)
@@ -15,8 +15,17 @@
 mod cache;
 pub use cache::Cache;
 
+mod network_info;
+pub use network_info::*;
+
 mod peer;
 pub use peer::*;
 
+mod peer_info;
+pub use peer_info::*;
+
 mod resolver;
 pub use resolver::*;
+
+mod sharded;
+pub use sharded::*;
@@ -0,0 +1,101 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::messages::NodeType;
+
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// A summary of the networking status of the node.
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkInfo {
+    /// The node type.
+    pub node_type: NodeType,
+    /// The message version of the node.
+    pub version: u32,
+    /// The addresses the node is listening on.
+    pub listening_addrs: Vec<SocketAddr>,
+    /// The listener port advertised to peers during the handshake.
+    pub advertised_port: u16,
+    /// The connection counts.
+    pub connections: ConnectionCounts,
+    /// The number of candidate peers.
+    pub candidate_peers: usize,
+    /// The number of restricted peers.
+    pub restricted_peers: usize,
+    /// The number of trusted peers.
+    pub trusted_peers: usize,
+    /// The relay policy of the node.
+    pub relay: RelayPolicy,
+    /// The transport settings of the node.
+    pub transport: TransportInfo,
+}
+
+/// The connection counts of the node, by direction and by node type.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ConnectionCounts {
+    /// The total number of connected peers.
+    pub total: usize,
+    /// The maximum number of connected peers.
+    pub max: usize,
+    /// The number of peers that connected to this node.
+    pub inbound: usize,
+    /// The number of peers this node connected to.
+    pub outbound: usize,
+    /// The number of connected validators.
+    pub validators: usize,
+    /// The number of connected provers.
+    pub provers: usize,
+    /// The number of connected clients.
+    pub clients: usize,
+    /// The number of peers currently handshaking.
+    pub connecting: usize,
+}
+
+/// The messages this node relays to its peers.
+#[derive(Clone, Debug, Serialize)]
+pub struct RelayPolicy {
+    /// Whether unconfirmed transactions are relayed.
+    pub transactions: bool,
+    /// Whether unconfirmed solutions are relayed.
+    pub solutions: bool,
+    /// Whether messages are only relayed to validators.
+    pub validators_only: bool,
+}
+
+impl RelayPolicy {
+    /// Returns the relay policy of the given node type.
+    pub const fn new(node_type: NodeType) -> Self {
+        match node_type {
+            NodeType::Validator => Self { transactions: true, solutions: true, validators_only: true },
+            NodeType::Client => Self { transactions: true, solutions: true, validators_only: false },
+            NodeType::Prover => Self { transactions: false, solutions: true, validators_only: false },
+        }
+    }
+}
+
+/// The transport settings of the node.
+#[derive(Clone, Debug, Serialize)]
+pub struct TransportInfo {
+    /// The transport protocol.
+    pub protocol: &'static str,
+    /// Whether the node accepts inbound connections.
+    pub inbound_enabled: bool,
+    /// The maximum number of inbound connections accepted per second (`0` if unlimited).
+    pub max_inbound_connections_per_sec: u16,
+    /// The maximum number of inbound connections accepted per second per subnet (`0` if unlimited).
+    pub max_inbound_connections_per_subnet_per_sec: u16,
+    /// The timeout for establishing a connection, in milliseconds.
+    pub connection_timeout_ms: u16,
+}
@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use crate::messages::{ChallengeRequest, NodeType};
+use snarkos_node_tcp::ConnectionSide;
 use snarkvm::prelude::{Address, Network};
 
-use std::{net::SocketAddr, time::Instant};
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
 /// The state for each connected peer.
 #[derive(Clone, Debug)]
@@ -32,11 +36,17 @@ pub struct Peer<N: Network> {
     first_seen: Instant,
     /// The timestamp of the last message received from this peer.
     last_seen: Instant,
+    /// The side of the connection the peer is on.
+    side: ConnectionSide,
+    /// The timestamp of the last unanswered ping sent to the peer.
+    ping_sent_at: Option<Instant>,
+    /// The round-trip latency of the last answered ping.
+    latency: Option<Duration>,
 }
 
 impl<N: Network> Peer<N> {
     /// Initializes a new instance of `Peer`.
-    pub fn new(listening_ip: SocketAddr, challenge_request: &ChallengeRequest<N>) -> Self {
+    pub fn new(listening_ip: SocketAddr, challenge_request: &ChallengeRequest<N>, side: ConnectionSide) -> Self {
         Self {
             peer_ip: listening_ip,
             address: challenge_request.address,
@@ -44,6 +54,9 @@ impl<N: Network> Peer<N> {
             version: challenge_request.version,
             first_seen: Instant::now(),
             last_seen: Instant::now(),
+            side,
+            ping_sent_at: None,
+            latency: None,
         }
     }
 
@@ -91,6 +104,16 @@ impl<N: Network> Peer<N> {
     pub fn last_seen(&self) -> Instant {
         self.last_seen
     }
+
+    /// Returns `true` if the peer initiated the connection.
+    pub fn is_inbound(&self) -> bool {
+        self.side == ConnectionSide::Initiator
+    }
+
+    /// Returns the round-trip latency of the last answered ping, if any.
+    pub const fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
 }
 
 impl<N: Network> Peer<N> {
@@ -108,4 +131,16 @@ impl<N: Network> Peer<N> {
     pub fn set_last_seen(&mut self, last_seen: Instant) {
         self.last_seen = last_seen;
     }
+
+    /// Records that a ping was sent to the peer.
+    pub fn set_ping_sent(&mut self, ping_sent_at: Instant) {
+        self.ping_sent_at = Some(ping_sent_at);
+    }
+
+    /// Records that a pong was received from the peer, updating the latency if a ping is outstanding.
+    pub fn set_pong_received(&mut self, pong_received_at: Instant) {
+        if let Some(ping_sent_at) = self.ping_sent_at.take() {
+            self.latency = Some(pong_received_at.saturating_duration_since(ping_sent_at));
+        }
+    }
 }
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{messages::NodeType, Peer};
+use snarkos_node_tcp::Stats;
+use snarkvm::prelude::{Address, Network};
+
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// The direction of a peer connection, relative to this node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerDirection {
+    /// The peer connected to this node.
+    Inbound,
+    /// This node connected to the peer.
+    Outbound,
+}
+
+/// A snapshot of the state of a connected peer.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "")]
+pub struct PeerInfo<N: Network> {
+    /// The listening IP address of the peer.
+    pub ip: SocketAddr,
+    /// The Aleo address of the peer.
+    pub address: Address<N>,
+    /// The node type of the peer.
+    pub node_type: NodeType,
+    /// The message version of the peer.
+    pub version: u32,
+    /// The direction of the connection.
+    pub direction: PeerDirection,
+    /// The latest block height of the peer, if known.
+    pub height: Option<u32>,
+    /// The number of seconds since the connection was established.
+    pub connected_secs: u64,
+    /// The number of seconds since the last message was received from the peer.
+    pub last_seen_secs: u64,
+    /// The number of bytes sent to the peer.
+    pub bytes_sent: u64,
+    /// The number of bytes received from the peer.
+    pub bytes_received: u64,
+    /// The round-trip latency of the last answered ping, in milliseconds.
+    pub ping_latency_ms: Option<u64>,
+}
+
+impl<N: Network> PeerInfo<N> {
+    /// Initializes a new peer snapshot from the given peer and its connection statistics.
+    pub fn new(peer: &Peer<N>, stats: Option<&Stats>) -> Self {
+        let (bytes_sent, bytes_received) = stats.map_or((0, 0), |stats| (stats.sent().1, stats.received().1));
+        Self {
+            ip: peer.ip(),
+            address: peer.address(),
+            node_type: peer.node_type(),
+            version: peer.version(),
+            direction: match peer.is_inbound() {
+                true => PeerDirection::Inbound,
+                false => PeerDirection::Outbound,
+            },
+            height: None,
+            connected_secs: peer.first_seen().elapsed().as_secs(),
+            last_seen_secs: peer.last_seen().elapsed().as_secs(),
+            bytes_sent,
+            bytes_received,
+            ping_latency_ms: peer.latency().map(|latency| latency.as_millis() as u64),
+        }
+    }
+}
+
+/// The outstanding requests and recent traffic of a connected peer.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PeerQueues {
+    /// The number of block requests sent to the peer that are awaiting a response.
+    pub block_requests: usize,
+    /// The number of puzzle requests sent to the peer that are awaiting a response.
+    pub puzzle_requests: u32,
+    /// The number of peer requests sent to the peer that are awaiting a response.
+    pub peer_requests: u32,
+    /// The number of messages recently received from the peer.
+    pub recent_messages: usize,
+}
+
+/// A detailed snapshot of a connected peer, for debugging.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "")]
+pub struct PeerDebugInfo<N: Network> {
+    /// The peer snapshot.
+    #[serde(flatten)]
+    pub info: PeerInfo<N>,
+    /// Whether the peer is trusted.
+    pub is_trusted: bool,
+    /// The outstanding requests and recent traffic of the peer.
+    pub queues: PeerQueues,
+}
+
+/// A detailed snapshot of the peers known to the router, for debugging.
+#[derive(Clone, Debug, Serialize)]
+#[serde(bound = "")]
+pub struct RouterDebugState<N: Network> {
+    /// The connected peers.
+    pub connected: Vec<PeerDebugInfo<N>>,
+    /// The peers the router is shaking hands with.
+    pub connecting: Vec<SocketAddr>,
+    /// The number of candidate peers.
+    pub num_candidates: usize,
+    /// The restricted peers.
+    pub restricted: Vec<SocketAddr>,
+    /// The number of running router tasks.
+    pub running_tasks: usize,
+    /// The number of finished router tasks.
+    pub finished_tasks: usize,
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::RwLock;
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+/// The default number of shards in a sharded map.
+pub const DEFAULT_NUM_SHARDS: usize = 16;
+
+/// A hash map that is split into independently-locked shards.
+///
+/// Each key is assigned to a single shard, so that concurrent readers and writers
+/// of different peers rarely contend on the same lock.
+pub struct ShardedMap<K, V> {
+    /// The shards of the map.
+    shards: Box<[RwLock<HashMap<K, V>>]>,
+    /// The hasher used to assign keys to shards.
+    hasher: RandomState,
+}
+
+impl<K: Eq + Hash, V> Default for ShardedMap<K, V> {
+    /// Initializes a new sharded map with the default number of shards.
+    fn default() -> Self {
+        Self::new(DEFAULT_NUM_SHARDS)
+    }
+}
+
+impl<K: Eq + Hash, V> ShardedMap<K, V> {
+    /// Initializes a new sharded map with the given number of shards.
+    pub fn new(num_shards: usize) -> Self {
+        let shards = (0..num_shards.max(1)).map(|_| RwLock::new(HashMap::new())).collect();
+        Self { shards, hasher: RandomState::new() }
+    }
+
+    /// Returns the shard for the given key.
+    fn shard(&self, key: &K) -> &RwLock<HashMap<K, V>> {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// Returns `true` if the map contains the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.shard(key).read().contains_key(key)
+    }
+
+    /// Returns the result of `f` applied to the value for the given key, if it exists.
+    pub fn get_with<T>(&self, key: &K, f: impl FnOnce(&V) -> T) -> Option<T> {
+        self.shard(key).read().get(key).map(f)
+    }
+
+    /// Returns the result of `f` applied to the mutable value for the given key, if it exists.
+    pub fn get_mut_with<T>(&self, key: &K, f: impl FnOnce(&mut V) -> T) -> Option<T> {
+        self.shard(key).write().get_mut(key).map(f)
+    }
+
+    /// Inserts the given key-value pair, returning the previous value, if it exists.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.shard(&key).write().insert(key, value)
+    }
+
+    /// Removes the given key, returning its value, if it exists.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.shard(key).write().remove(key)
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().len()).sum()
+    }
+
+    /// Returns `true` if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.read().is_empty())
+    }
+
+    /// Returns the number of entries that satisfy the given predicate.
+    pub fn count(&self, mut predicate: impl FnMut(&K, &V) -> bool) -> usize {
+        self.shards.iter().map(|shard| shard.read().iter().filter(|(k, v)| predicate(k, v)).count()).sum()
+    }
+
+    /// Returns the collected results of `f` over every entry, skipping entries for which `f` returns `None`.
+    ///
+    /// Note: Only one shard is locked at a time, so the result is not an atomic snapshot of the map.
+    pub fn filter_map<T>(&self, mut f: impl FnMut(&K, &V) -> Option<T>) -> Vec<T> {
+        let mut output = Vec::new();
+        for shard in self.shards.iter() {
+            output.extend(shard.read().iter().filter_map(|(k, v)| f(k, v)));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharded_map() {
+        let map = ShardedMap::<u32, u32>::new(4);
+        assert!(map.is_empty());
+
+        for i in 0..100 {
+            assert_eq!(map.insert(i, i * 2), None);
+        }
+        assert_eq!(map.len(), 100);
+        assert!(map.contains_key(&42));
+        assert_eq!(map.get_with(&42, |v| *v), Some(84));
+        assert_eq!(map.count(|k, _| k % 2 == 0), 50);
+
+        assert_eq!(map.get_mut_with(&42, |v| std::mem::replace(v, 0)), Some(84));
+        assert_eq!(map.get_with(&42, |v| *v), Some(0));
+
+        assert_eq!(map.remove(&42), Some(0));
+        assert!(!map.contains_key(&42));
+        assert_eq!(map.filter_map(|k, _| (*k < 10).then_some(*k)).len(), 9);
+    }
+}
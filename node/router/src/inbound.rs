@@ -69,6 +69,8 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
         }
 
         trace!("Received '{}' from '{peer_ip}'", message.name());
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::router::MESSAGES_RECEIVED);
 
         // This match statement handles the inbound message by deserializing the message,
         // checking that the message is valid, and then calling the appropriate (trait) handler.
@@ -167,10 +169,15 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                     false => bail!("Peer '{peer_ip}' sent an invalid ping"),
                 }
             }
-            Message::Pong(message) => match self.pong(peer_ip, message) {
-                true => Ok(()),
-                false => bail!("Peer '{peer_ip}' sent an invalid pong"),
-            },
+            Message::Pong(message) => {
+                // Update the ping latency of the peer.
+                self.router().update_pong_received(peer_ip);
+                // Process the pong message.
+                match self.pong(peer_ip, message) {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid pong"),
+                }
+            }
             Message::PuzzleRequest(..) => {
                 // Insert the puzzle request for the peer, and fetch the recent frequency.
                 let frequency = self.router().cache.insert_inbound_puzzle_request(peer_ip);
@@ -38,7 +38,7 @@ pub use outbound::*;
 mod routing;
 pub use routing::*;
 
-use crate::messages::NodeType;
+use crate::messages::{Message, NodeType};
 use snarkos_account::Account;
 use snarkos_node_tcp::{is_bogon_ip, is_unspecified_or_broadcast_ip, Config, Tcp};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
@@ -48,11 +48,10 @@ use parking_lot::{Mutex, RwLock};
 use std::{
     collections::{HashMap, HashSet},
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
     ops::Deref,
-    str::FromStr,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::task::JoinHandle;
 
@@ -81,7 +80,7 @@ pub struct InnerRouter<N: Network> {
     /// The set of trusted peers.
     trusted_peers: HashSet<SocketAddr>,
     /// The map of connected peer IPs to their peer handlers.
-    connected_peers: RwLock<HashMap<SocketAddr, Peer<N>>>,
+    connected_peers: ShardedMap<SocketAddr, Peer<N>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
     /// and prevents duplicate outbound connection attempts to the same IP address, it is unable to
     /// prevent simultaneous "two-way" connections between two peers (i.e. both nodes simultaneously
@@ -91,6 +90,8 @@ pub struct InnerRouter<N: Network> {
     candidate_peers: RwLock<HashSet<SocketAddr>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<HashMap<SocketAddr, Instant>>,
+    /// The map of banned IPs to the time their ban expires, or `None` if the ban is permanent.
+    banned_ips: RwLock<HashMap<IpAddr, Option<Instant>>>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -98,10 +99,19 @@ pub struct InnerRouter<N: Network> {
 }
 
 impl<N: Network> Router<N> {
+    /// The bootstrap peers.
+    const BOOTSTRAP_PEERS: &'static [SocketAddr] = &[
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(64, 23, 169, 88), 4130)),
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(146, 190, 35, 174), 4130)),
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(45, 55, 201, 67), 4130)),
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(45, 55, 201, 80), 4130)),
+    ];
     /// The maximum number of candidate peers permitted to be stored in the node.
     const MAXIMUM_CANDIDATE_PEERS: usize = 10_000;
     /// The maximum number of connection failures permitted by an inbound connecting peer.
     const MAXIMUM_CONNECTION_FAILURES: usize = 5;
+    /// The depth of the outbound message queue of a protected peer.
+    const PROTECTED_MESSAGE_QUEUE_DEPTH: usize = 8 * 1024;
     /// The duration in seconds after which a connected peer is considered inactive or
     /// disconnected if no message has been received in the meantime.
     const RADIO_SILENCE_IN_SECS: u64 = 150; // 2.5 minutes
@@ -131,6 +141,7 @@ impl<N: Network> Router<N> {
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            banned_ips: Default::default(),
             handles: Default::default(),
             is_dev,
         })))
@@ -183,6 +194,10 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip.ip()) {
+            bail!("Dropping connection attempt to '{peer_ip}' (banned)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (already shaking hands as the initiator)")
@@ -263,22 +278,40 @@ impl<N: Network> Router<N> {
 
     /// Returns `true` if the node is connected to the given peer IP.
     pub fn is_connected(&self, ip: &SocketAddr) -> bool {
-        self.connected_peers.read().contains_key(ip)
+        self.connected_peers.contains_key(ip)
     }
 
     /// Returns `true` if the given peer IP is a connected validator.
     pub fn is_connected_validator(&self, peer_ip: &SocketAddr) -> bool {
-        self.connected_peers.read().get(peer_ip).map_or(false, |peer| peer.is_validator())
+        self.connected_peers.get_with(peer_ip, |peer| peer.is_validator()).unwrap_or(false)
     }
 
     /// Returns `true` if the given peer IP is a connected prover.
     pub fn is_connected_prover(&self, peer_ip: &SocketAddr) -> bool {
-        self.connected_peers.read().get(peer_ip).map_or(false, |peer| peer.is_prover())
+        self.connected_peers.get_with(peer_ip, |peer| peer.is_prover()).unwrap_or(false)
     }
 
     /// Returns `true` if the given peer IP is a connected client.
     pub fn is_connected_client(&self, peer_ip: &SocketAddr) -> bool {
-        self.connected_peers.read().get(peer_ip).map_or(false, |peer| peer.is_client())
+        self.connected_peers.get_with(peer_ip, |peer| peer.is_client()).unwrap_or(false)
+    }
+
+    /// Returns `true` if the given peer IP is protected from eviction.
+    /// Trusted peers, bootstrap peers, and connected validators are consensus-critical,
+    /// and are never disconnected to make room for other peers.
+    pub fn is_protected(&self, peer_ip: &SocketAddr) -> bool {
+        self.trusted_peers.contains(peer_ip)
+            || self.bootstrap_peers().contains(peer_ip)
+            || self.is_connected_validator(peer_ip)
+    }
+
+    /// Returns the depth of the outbound message queue for the given peer address.
+    /// Protected peers get a deeper queue of their own, so a burst of broadcasts does not overflow it.
+    pub fn message_queue_depth(&self, peer_addr: SocketAddr, default_depth: usize) -> usize {
+        match self.resolve_to_listener(&peer_addr) {
+            Some(peer_ip) if self.is_protected(&peer_ip) => Self::PROTECTED_MESSAGE_QUEUE_DEPTH.max(default_depth),
+            _ => default_depth,
+        }
     }
 
     /// Returns `true` if the node is currently connecting to the given peer IP.
@@ -295,6 +328,15 @@ impl<N: Network> Router<N> {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the given IP is banned.
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        match self.banned_ips.read().get(ip) {
+            Some(Some(expires_at)) => Instant::now() < *expires_at,
+            Some(None) => true,
+            None => false,
+        }
+    }
+
     /// Returns the maximum number of connected peers.
     pub fn max_connected_peers(&self) -> usize {
         self.tcp.config().max_connections as usize
@@ -302,22 +344,22 @@ impl<N: Network> Router<N> {
 
     /// Returns the number of connected peers.
     pub fn number_of_connected_peers(&self) -> usize {
-        self.connected_peers.read().len()
+        self.connected_peers.len()
     }
 
     /// Returns the number of connected validators.
     pub fn number_of_connected_validators(&self) -> usize {
-        self.connected_peers.read().values().filter(|peer| peer.is_validator()).count()
+        self.connected_peers.count(|_, peer| peer.is_validator())
     }
 
     /// Returns the number of connected provers.
     pub fn number_of_connected_provers(&self) -> usize {
-        self.connected_peers.read().values().filter(|peer| peer.is_prover()).count()
+        self.connected_peers.count(|_, peer| peer.is_prover())
     }
 
     /// Returns the number of connected clients.
     pub fn number_of_connected_clients(&self) -> usize {
-        self.connected_peers.read().values().filter(|peer| peer.is_client()).count()
+        self.connected_peers.count(|_, peer| peer.is_client())
     }
 
     /// Returns the number of candidate peers.
@@ -332,32 +374,32 @@ impl<N: Network> Router<N> {
 
     /// Returns the connected peer given the peer IP, if it exists.
     pub fn get_connected_peer(&self, ip: &SocketAddr) -> Option<Peer<N>> {
-        self.connected_peers.read().get(ip).cloned()
+        self.connected_peers.get_with(ip, |peer| peer.clone())
     }
 
     /// Returns the connected peers.
     pub fn get_connected_peers(&self) -> Vec<Peer<N>> {
-        self.connected_peers.read().values().cloned().collect()
+        self.connected_peers.filter_map(|_, peer| Some(peer.clone()))
     }
 
     /// Returns the list of connected peers.
     pub fn connected_peers(&self) -> Vec<SocketAddr> {
-        self.connected_peers.read().keys().copied().collect()
+        self.connected_peers.filter_map(|ip, _| Some(*ip))
     }
 
     /// Returns the list of connected validators.
     pub fn connected_validators(&self) -> Vec<SocketAddr> {
-        self.connected_peers.read().iter().filter(|(_, peer)| peer.is_validator()).map(|(ip, _)| *ip).collect()
+        self.connected_peers.filter_map(|ip, peer| peer.is_validator().then_some(*ip))
     }
 
     /// Returns the list of connected provers.
     pub fn connected_provers(&self) -> Vec<SocketAddr> {
-        self.connected_peers.read().iter().filter(|(_, peer)| peer.is_prover()).map(|(ip, _)| *ip).collect()
+        self.connected_peers.filter_map(|ip, peer| peer.is_prover().then_some(*ip))
     }
 
     /// Returns the list of connected clients.
     pub fn connected_clients(&self) -> Vec<SocketAddr> {
-        self.connected_peers.read().iter().filter(|(_, peer)| peer.is_client()).map(|(ip, _)| *ip).collect()
+        self.connected_peers.filter_map(|ip, peer| peer.is_client().then_some(*ip))
     }
 
     /// Returns the list of candidate peers.
@@ -370,44 +412,122 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of banned IPs, with the time remaining on each ban, or `None` if the ban is permanent.
+    pub fn banned_ips(&self) -> Vec<(IpAddr, Option<Duration>)> {
+        let now = Instant::now();
+        self.banned_ips
+            .read()
+            .iter()
+            .filter(|(_, expires_at)| expires_at.map_or(true, |expires_at| now < expires_at))
+            .map(|(ip, expires_at)| (*ip, expires_at.map(|expires_at| expires_at - now)))
+            .collect()
+    }
+
     /// Returns the list of trusted peers.
     pub fn trusted_peers(&self) -> &HashSet<SocketAddr> {
         &self.trusted_peers
     }
 
     /// Returns the list of bootstrap peers.
-    pub fn bootstrap_peers(&self) -> Vec<SocketAddr> {
+    pub fn bootstrap_peers(&self) -> &'static [SocketAddr] {
         if cfg!(feature = "test") || self.is_dev {
-            vec![]
+            &[]
         } else {
-            vec![
-                SocketAddr::from_str("64.23.169.88:4130").unwrap(),
-                SocketAddr::from_str("146.190.35.174:4130").unwrap(),
-                SocketAddr::from_str("45.55.201.67:4130").unwrap(),
-                SocketAddr::from_str("45.55.201.80:4130").unwrap(),
-            ]
+            Self::BOOTSTRAP_PEERS
+        }
+    }
+
+    /// Returns a detailed snapshot of the peers known to the router, for debugging.
+    pub fn debug_state(&self) -> RouterDebugState<N> {
+        let connected = self
+            .connected_peer_info()
+            .into_iter()
+            .map(|info| {
+                let (block_requests, puzzle_requests, peer_requests) = self.cache.num_outbound_requests(&info.ip);
+                let recent_messages = self.cache.num_inbound_messages(&info.ip);
+                let queues = PeerQueues { block_requests, puzzle_requests, peer_requests, recent_messages };
+                PeerDebugInfo { is_trusted: self.trusted_peers.contains(&info.ip), queues, info }
+            })
+            .collect();
+        let (running_tasks, finished_tasks) = self.task_counts();
+        RouterDebugState {
+            connected,
+            connecting: self.connecting_peers.lock().iter().copied().collect(),
+            num_candidates: self.number_of_candidate_peers(),
+            restricted: self.restricted_peers(),
+            running_tasks,
+            finished_tasks,
         }
     }
 
+    /// Returns the number of running and finished tasks spawned by the router.
+    pub fn task_counts(&self) -> (usize, usize) {
+        let handles = self.handles.lock();
+        let finished = handles.iter().filter(|handle| handle.is_finished()).count();
+        (handles.len() - finished, finished)
+    }
+
     /// Returns the list of metrics for the connected peers.
     pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType)> {
-        self.connected_peers.read().iter().map(|(ip, peer)| (*ip, peer.node_type())).collect()
+        self.connected_peers.filter_map(|ip, peer| Some((*ip, peer.node_type())))
+    }
+
+    /// Returns a snapshot of the state of each connected peer.
+    pub fn connected_peer_info(&self) -> Vec<PeerInfo<N>> {
+        self.connected_peers.filter_map(|ip, peer| {
+            let stats = self.resolve_to_ambiguous(ip).and_then(|addr| self.tcp.known_peers().get(addr));
+            Some(PeerInfo::new(peer, stats.as_deref()))
+        })
     }
 
     #[cfg(feature = "metrics")]
     fn update_metrics(&self) {
-        metrics::gauge(metrics::router::CONNECTED, self.connected_peers.read().len() as f64);
+        metrics::gauge(metrics::router::CONNECTED, self.connected_peers.len() as f64);
         metrics::gauge(metrics::router::CANDIDATE, self.candidate_peers.read().len() as f64);
         metrics::gauge(metrics::router::RESTRICTED, self.restricted_peers.read().len() as f64);
     }
 
+    /// Returns a summary of the networking status of the node.
+    pub fn network_info(&self) -> NetworkInfo {
+        let config = self.tcp.config();
+        let inbound = self.connected_peers.count(|_, peer| peer.is_inbound());
+        let connections = ConnectionCounts {
+            total: self.number_of_connected_peers(),
+            max: self.max_connected_peers(),
+            inbound,
+            outbound: self.number_of_connected_peers().saturating_sub(inbound),
+            validators: self.number_of_connected_validators(),
+            provers: self.number_of_connected_provers(),
+            clients: self.number_of_connected_clients(),
+            connecting: self.connecting_peers.lock().len(),
+        };
+        NetworkInfo {
+            node_type: self.node_type,
+            version: Message::<N>::VERSION,
+            listening_addrs: self.tcp.listening_addr().into_iter().collect(),
+            advertised_port: self.local_ip().port(),
+            connections,
+            candidate_peers: self.number_of_candidate_peers(),
+            restricted_peers: self.number_of_restricted_peers(),
+            trusted_peers: self.trusted_peers.len(),
+            relay: RelayPolicy::new(self.node_type),
+            transport: TransportInfo {
+                protocol: "tcp",
+                inbound_enabled: config.listener_ip.is_some(),
+                max_inbound_connections_per_sec: config.max_inbound_connections_per_sec,
+                max_inbound_connections_per_subnet_per_sec: config.max_inbound_connections_per_subnet_per_sec,
+                connection_timeout_ms: config.connection_timeout_ms,
+            },
+        }
+    }
+
     /// Inserts the given peer into the connected peers.
     pub fn insert_connected_peer(&self, peer: Peer<N>, peer_addr: SocketAddr) {
         let peer_ip = peer.ip();
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr);
         // Add an entry for this `Peer` in the connected peers.
-        self.connected_peers.write().insert(peer_ip, peer);
+        self.connected_peers.insert(peer_ip, peer);
         // Remove this peer from the candidate peers, if it exists.
         self.candidate_peers.write().remove(&peer_ip);
         // Remove this peer from the restricted peers, if it exists.
@@ -427,8 +547,11 @@ impl<N: Network> Router<N> {
         let eligible_peers = peers
             .iter()
             .filter(|peer_ip| {
-                // Ensure the peer is not itself, is not already connected, and is not restricted.
-                !self.is_local_ip(peer_ip) && !self.is_connected(peer_ip) && !self.is_restricted(peer_ip)
+                // Ensure the peer is not itself, is not already connected, and is not restricted or banned.
+                !self.is_local_ip(peer_ip)
+                    && !self.is_connected(peer_ip)
+                    && !self.is_restricted(peer_ip)
+                    && !self.is_banned(&peer_ip.ip())
             })
             .take(max_candidate_peers);
 
@@ -448,6 +571,31 @@ impl<N: Network> Router<N> {
         self.update_metrics();
     }
 
+    /// Bans the given IP for the given duration, or permanently if no duration is given,
+    /// and disconnects from any connected peers with this IP.
+    pub fn ban_ip(&self, ip: IpAddr, duration: Option<Duration>) {
+        // Add the IP to the banned IPs.
+        self.banned_ips.write().insert(ip, duration.map(|duration| Instant::now() + duration));
+        // Remove the peers with this IP from the candidate peers.
+        self.candidate_peers.write().retain(|peer_ip| peer_ip.ip() != ip);
+        // Disconnect from the connected peers with this IP.
+        for peer_ip in self.connected_peers().into_iter().filter(|peer_ip| peer_ip.ip() == ip) {
+            self.disconnect(peer_ip);
+        }
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+    }
+
+    /// Removes the ban on the given IP, returning `true` if the IP was banned.
+    pub fn unban_ip(&self, ip: &IpAddr) -> bool {
+        self.banned_ips.write().remove(ip).is_some()
+    }
+
+    /// Removes every ban.
+    pub fn clear_banned_ips(&self) {
+        self.banned_ips.write().clear();
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
@@ -455,16 +603,27 @@ impl<N: Network> Router<N> {
         node_type: NodeType,
         mut write_fn: Fn,
     ) -> Result<()> {
-        // Retrieve the peer.
-        if let Some(peer) = self.connected_peers.write().get_mut(&peer_ip) {
+        // Retrieve the peer, and update it with the given function.
+        let result = self.connected_peers.get_mut_with(&peer_ip, |peer| {
             // Ensure the node type has not changed.
             if peer.node_type() != node_type {
                 bail!("Peer '{peer_ip}' has changed node types from {} to {node_type}", peer.node_type())
             }
             // Lastly, update the peer with the given function.
             write_fn(peer);
-        }
-        Ok(())
+            Ok(())
+        });
+        result.unwrap_or(Ok(()))
+    }
+
+    /// Records that a ping was sent to the given peer.
+    pub fn update_ping_sent(&self, peer_ip: SocketAddr) {
+        self.connected_peers.get_mut_with(&peer_ip, |peer| peer.set_ping_sent(Instant::now()));
+    }
+
+    /// Records that a pong was received from the given peer.
+    pub fn update_pong_received(&self, peer_ip: SocketAddr) {
+        self.connected_peers.get_mut_with(&peer_ip, |peer| peer.set_pong_received(Instant::now()));
     }
 
     /// Removes the connected peer and adds them to the candidate peers.
@@ -472,7 +631,7 @@ impl<N: Network> Router<N> {
         // Removes the bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.remove_peer(&peer_ip);
         // Remove this peer from the connected peers, if it exists.
-        self.connected_peers.write().remove(&peer_ip);
+        self.connected_peers.remove(&peer_ip);
         // Add the peer to the candidate peers.
         self.candidate_peers.write().insert(peer_ip);
         #[cfg(feature = "metrics")]
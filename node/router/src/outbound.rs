@@ -18,7 +18,10 @@ use crate::{
 };
 use snarkos_node_sync_locators::BlockLocators;
 use snarkos_node_tcp::protocols::Writing;
-use snarkvm::prelude::Network;
+use snarkvm::{
+    ledger::narwhal::Data,
+    prelude::{Network, ToBytes},
+};
 use std::io;
 
 use std::net::SocketAddr;
@@ -30,7 +33,10 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
 
     /// Sends a "Ping" message to the given peer.
     fn send_ping(&self, peer_ip: SocketAddr, block_locators: Option<BlockLocators<N>>) {
-        self.send(peer_ip, Message::Ping(Ping::new(self.router().node_type(), block_locators)));
+        if self.send(peer_ip, Message::Ping(Ping::new(self.router().node_type(), block_locators))).is_some() {
+            // Record the ping, to measure the latency once the pong arrives.
+            self.router().update_ping_sent(peer_ip);
+        }
     }
 
     /// Sends the given message to specified peer.
@@ -68,69 +74,96 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
         // Send the message to the peer.
         trace!("Sending '{name}' to '{peer_ip}'");
         let result = self.unicast(peer_addr, message);
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::router::MESSAGES_SENT);
         // If the message was unable to be sent, disconnect.
         if let Err(e) = &result {
             warn!("Failed to send '{name}' to '{peer_ip}': {e}");
-            debug!("Disconnecting from '{peer_ip}' (unable to send)");
-            self.router().disconnect(peer_ip);
+            // Note: A protected peer is kept if only its queue is full, as its writer closes a broken connection.
+            if e.kind() == io::ErrorKind::Other && self.router().is_protected(&peer_ip) {
+                debug!("Keeping the protected peer '{peer_ip}' (outbound queue is full)");
+            } else {
+                debug!("Disconnecting from '{peer_ip}' (unable to send)");
+                self.router().disconnect(peer_ip);
+            }
         }
         result.ok()
     }
 
     /// Sends the given message to every connected peer, excluding the sender and any specified peer IPs.
     fn propagate(&self, message: Message<N>, excluded_peers: &[SocketAddr]) {
-        // TODO (howardwu): Serialize large messages once only.
-        // // Perform ahead-of-time, non-blocking serialization just once for applicable objects.
-        // if let Message::UnconfirmedSolution(ref mut message) = message {
-        //     if let Ok(serialized_solution) = Data::serialize(message.solution.clone()).await {
-        //         let _ = std::mem::replace(&mut message.solution, Data::Buffer(serialized_solution));
-        //     } else {
-        //         error!("Solution serialization is bugged");
-        //     }
-        // } else if let Message::UnconfirmedTransaction(ref mut message) = message {
-        //     if let Ok(serialized_transaction) = Data::serialize(message.transaction.clone()).await {
-        //         let _ = std::mem::replace(&mut message.transaction, Data::Buffer(serialized_transaction));
-        //     } else {
-        //         error!("Transaction serialization is bugged");
-        //     }
-        // }
-
         // Prepare the peers to send to.
         let connected_peers = self.router().connected_peers();
-        let peers = connected_peers.iter().filter(|peer_ip| !excluded_peers.contains(peer_ip));
-
-        // Iterate through all peers that are not the sender and excluded peers.
-        for peer_ip in peers {
-            self.send(*peer_ip, message.clone());
-        }
+        let peers = connected_peers.into_iter().filter(|peer_ip| !excluded_peers.contains(peer_ip)).collect();
+        // Send the message to all peers that are not the sender and excluded peers.
+        self.broadcast(message, peers);
     }
 
     /// Sends the given message to every connected validator, excluding the sender and any specified IPs.
     fn propagate_to_validators(&self, message: Message<N>, excluded_peers: &[SocketAddr]) {
-        // TODO (howardwu): Serialize large messages once only.
-        // // Perform ahead-of-time, non-blocking serialization just once for applicable objects.
-        // if let Message::UnconfirmedSolution(ref mut message) = message {
-        //     if let Ok(serialized_solution) = Data::serialize(message.solution.clone()).await {
-        //         let _ = std::mem::replace(&mut message.solution, Data::Buffer(serialized_solution));
-        //     } else {
-        //         error!("Solution serialization is bugged");
-        //     }
-        // } else if let Message::UnconfirmedTransaction(ref mut message) = message {
-        //     if let Ok(serialized_transaction) = Data::serialize(message.transaction.clone()).await {
-        //         let _ = std::mem::replace(&mut message.transaction, Data::Buffer(serialized_transaction));
-        //     } else {
-        //         error!("Transaction serialization is bugged");
-        //     }
-        // }
-
         // Prepare the peers to send to.
         let connected_validators = self.router().connected_validators();
-        let peers = connected_validators.iter().filter(|peer_ip| !excluded_peers.contains(peer_ip));
+        let peers = connected_validators.into_iter().filter(|peer_ip| !excluded_peers.contains(peer_ip)).collect();
+        // Send the message to all validators that are not the sender and excluded validators.
+        self.broadcast(message, peers);
+    }
 
-        // Iterate through all validators that are not the sender and excluded validators.
-        for peer_ip in peers {
-            self.send(*peer_ip, message.clone());
+    /// Sends the given message to the given peers.
+    /// A solution or transaction is serialized once, off the async runtime, before it is sent,
+    /// so the message can be cloned for every peer without re-serializing the payload.
+    fn broadcast(&self, message: Message<N>, peers: Vec<SocketAddr>) {
+        if !Self::needs_serialization(&message) {
+            for peer_ip in peers {
+                self.send(peer_ip, message.clone());
+            }
+            return;
+        }
+        let node = self.clone();
+        tokio::spawn(async move {
+            let message = match tokio::task::spawn_blocking(move || Self::prepare_for_propagation(message)).await {
+                Ok(message) => message,
+                Err(error) => {
+                    error!("Failed to serialize the message for propagation - {error}");
+                    return;
+                }
+            };
+            for peer_ip in peers {
+                node.send(peer_ip, message.clone());
+            }
+        });
+    }
+
+    /// Returns `true` if the message holds a solution or transaction that is not serialized yet.
+    fn needs_serialization(message: &Message<N>) -> bool {
+        match message {
+            Message::UnconfirmedSolution(message) => matches!(message.solution, Data::Object(_)),
+            Message::UnconfirmedTransaction(message) => matches!(message.transaction, Data::Object(_)),
+            _ => false,
+        }
+    }
+
+    /// Returns the given message, with its solution or transaction serialized into a buffer.
+    fn prepare_for_propagation(mut message: Message<N>) -> Message<N> {
+        match &mut message {
+            Message::UnconfirmedSolution(message) => {
+                if let Data::Object(solution) = &message.solution {
+                    match solution.to_bytes_le() {
+                        Ok(bytes) => message.solution = Data::Buffer(bytes.into()),
+                        Err(error) => error!("Failed to serialize the solution for propagation - {error}"),
+                    }
+                }
+            }
+            Message::UnconfirmedTransaction(message) => {
+                if let Data::Object(transaction) = &message.transaction {
+                    match transaction.to_bytes_le() {
+                        Ok(bytes) => message.transaction = Data::Buffer(bytes.into()),
+                        Err(error) => error!("Failed to serialize the transaction for propagation - {error}"),
+                    }
+                }
+            }
+            _ => (),
         }
+        message
     }
 
     /// Returns `true` if the message can be sent.
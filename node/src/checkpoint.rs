@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{store::ConsensusStorage, Ledger, Network};
+
+use aleo_std::StorageMode;
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// The file in the ledger directory that records the latest checkpoint of the ledger.
+const CHECKPOINT_FILE: &str = "CHECKPOINT";
+/// The interval at which the checkpoint is updated.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A checkpoint of the validated state of the ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerCheckpoint<N: Network> {
+    /// The height of the latest block.
+    pub height: u32,
+    /// The hash of the latest block.
+    pub block_hash: N::BlockHash,
+    /// The state root the latest block was built on.
+    pub previous_state_root: N::StateRoot,
+    /// The cumulative weight of the latest block.
+    pub cumulative_weight: u128,
+}
+
+impl<N: Network> LedgerCheckpoint<N> {
+    /// Returns the checkpoint of the latest block of the given ledger.
+    pub fn latest<C: ConsensusStorage<N>>(ledger: &Ledger<N, C>) -> Self {
+        let block = ledger.latest_block();
+        Self {
+            height: block.height(),
+            block_hash: block.hash(),
+            previous_state_root: block.previous_state_root(),
+            cumulative_weight: block.cumulative_weight(),
+        }
+    }
+
+    /// Loads the checkpoint of the given ledger directory, if there is one.
+    pub fn load(ledger_path: &Path) -> Result<Option<Self>> {
+        let checkpoint = match fs::read(ledger_path.join(CHECKPOINT_FILE)) {
+            Ok(checkpoint) => serde_json::from_slice::<Value>(&checkpoint)?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Some(Self {
+            height: serde_json::from_value(checkpoint["height"].clone())?,
+            block_hash: serde_json::from_value(checkpoint["block_hash"].clone())?,
+            previous_state_root: serde_json::from_value(checkpoint["previous_state_root"].clone())?,
+            cumulative_weight: serde_json::from_value(checkpoint["cumulative_weight"].clone())?,
+        }))
+    }
+
+    /// Saves the checkpoint to the given ledger directory, replacing the previous one atomically.
+    pub fn save(&self, ledger_path: &Path) -> Result<()> {
+        let checkpoint = json!({
+            "height": self.height,
+            "block_hash": self.block_hash,
+            "previous_state_root": self.previous_state_root,
+            "cumulative_weight": self.cumulative_weight,
+        });
+        let path = ledger_path.join(CHECKPOINT_FILE);
+        let mut staging_path = path.clone().into_os_string();
+        staging_path.push(".tmp");
+        fs::write(&staging_path, serde_json::to_vec_pretty(&checkpoint)?)?;
+        Ok(fs::rename(staging_path, path)?)
+    }
+
+    /// Checks the given ledger against the checkpoint, which fails if the ledger contradicts it.
+    pub fn check<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<()> {
+        let latest_height = ledger.latest_height();
+        // The blocks after a checkpoint may be lost in a crash, and are synced again.
+        if latest_height < self.height {
+            warn!(
+                "The ledger is at block {latest_height}, behind its checkpoint at block {} - \
+                 the missing blocks will be synced again",
+                self.height
+            );
+            return Ok(());
+        }
+        let block_hash = ledger.get_hash(self.height)?;
+        if block_hash != self.block_hash {
+            bail!(
+                "The ledger contradicts its checkpoint: block {} is {block_hash}, but was {}",
+                self.height,
+                self.block_hash
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Checks the given ledger against its last checkpoint, if there is one.
+pub fn check_ledger_checkpoint<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    storage_mode: &StorageMode,
+) -> Result<()> {
+    let ledger_path = aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone());
+    match LedgerCheckpoint::<N>::load(&ledger_path)? {
+        Some(checkpoint) => checkpoint.check(ledger),
+        None => Ok(()),
+    }
+}
+
+/// Starts a task that checkpoints the validated state of the given ledger, whenever it advances.
+pub fn start_checkpoint_loop<N: Network, C: ConsensusStorage<N>>(
+    ledger: Ledger<N, C>,
+    storage_mode: StorageMode,
+) -> JoinHandle<()> {
+    let ledger_path: PathBuf = aleo_std::aleo_ledger_dir(N::ID, storage_mode);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+        let mut last_height = None;
+        loop {
+            interval.tick().await;
+            let checkpoint = LedgerCheckpoint::latest(&ledger);
+            if last_height == Some(checkpoint.height) {
+                continue;
+            }
+            match checkpoint.save(&ledger_path) {
+                Ok(()) => last_height = Some(checkpoint.height),
+                Err(error) => warn!("Failed to checkpoint the ledger at block {} - {error}", checkpoint.height),
+            }
+        }
+    })
+}
@@ -14,10 +14,10 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeStorage, StorageBackend};
 use snarkos_account::Account;
-use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_bft::ledger_service::{CoreLedgerService, LedgerCache};
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     messages::{Message, NodeType, UnconfirmedSolution},
     Heartbeat,
@@ -56,6 +56,8 @@ use tokio::task::JoinHandle;
 pub struct Client<N: Network, C: ConsensusStorage<N>> {
     /// The ledger of the node.
     ledger: Ledger<N, C>,
+    /// The cache of the recently read blocks.
+    ledger_cache: LedgerCache<N>,
     /// The router of the node.
     router: Router<N>,
     /// The REST server of the node.
@@ -77,13 +79,18 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub async fn new(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
-    ) -> Result<Self> {
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
+    ) -> Result<Self>
+    where
+        C: NodeStorage<N>,
+    {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
 
@@ -92,6 +99,14 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the ledger.
         let ledger = Ledger::<N, C>::load(genesis.clone(), storage_mode.clone())?;
+        // Check the ledger against its last checkpoint, if it is persisted.
+        let is_persistent = C::BACKEND == StorageBackend::RocksDb;
+        if is_persistent {
+            if let Err(error) = crate::check_ledger_checkpoint(&ledger, &storage_mode) {
+                crate::log_clean_error(&storage_mode);
+                return Err(error);
+            }
+        }
 
         // Initialize the CDN.
         if let Some(base_url) = cdn {
@@ -105,7 +120,10 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         }
 
         // Initialize the ledger service.
-        let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone(), shutdown.clone()));
+        // Initialize the cache of the recently read blocks, shared by the ledger service, router, and REST server.
+        let ledger_cache = LedgerCache::new(ledger_cache_size);
+        let ledger_service =
+            Arc::new(CoreLedgerService::<N, C>::new(ledger.clone(), ledger_cache.clone(), shutdown.clone()));
         // Initialize the sync module.
         let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone());
 
@@ -124,6 +142,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         // Initialize the node.
         let mut node = Self {
             ledger: ledger.clone(),
+            ledger_cache: ledger_cache.clone(),
             router,
             rest: None,
             sync: Arc::new(sync),
@@ -135,7 +154,11 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?);
+            let sync = (*node.sync).clone();
+            node.rest = Some(
+                Rest::start(rest_ip, rest_config, None, ledger.clone(), ledger_cache, Arc::new(node.clone()), sync)
+                    .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -143,6 +166,22 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         node.initialize_sync();
         // Initialize the notification message loop.
         node.handles.lock().push(crate::start_notification_message_loop());
+        // Initialize the checkpoint, disk space, and storage metrics loops, if the ledger is persisted.
+        if is_persistent {
+            node.handles.lock().push(crate::start_checkpoint_loop(ledger.clone(), storage_mode.clone()));
+            if min_free_disk_mb > 0 {
+                // While the disk is low on space, pause block sync.
+                let sync = node.sync.clone();
+                let ledger_path = aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone());
+                node.handles.lock().push(crate::start_disk_space_loop(
+                    ledger_path,
+                    min_free_disk_mb << 20,
+                    move |is_low| sync.set_paused(is_low),
+                ));
+            }
+            #[cfg(feature = "metrics")]
+            node.handles.lock().push(crate::start_storage_metrics_loop(N::ID, storage_mode));
+        }
         // Pass the node to the signal handler.
         let _ = signal_node.set(node.clone());
         // Return the node.
@@ -193,6 +232,12 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Client<N, C> {
     async fn shut_down(&self) {
         info!("Shutting down...");
 
+        // Shut down the REST server first, so it drains the in-flight requests.
+        if let Some(rest) = &self.rest {
+            trace!("Shutting down the REST server...");
+            rest.shut_down().await;
+        }
+
         // Shut down the node.
         trace!("Shutting down the node...");
         self.shutdown.store(true, std::sync::atomic::Ordering::Relaxed);
@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use std::{path::PathBuf, time::Duration};
+
+/// The default minimum free disk space, in MiB, below which the node pauses its ledger growth.
+pub const DEFAULT_MIN_FREE_DISK_MB: u64 = 2048;
+
+/// The interval at which the free disk space is checked.
+const DISK_SPACE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Returns the number of bytes available to the node on the file system holding the given path.
+#[cfg(target_family = "unix")]
+pub fn available_disk_space(path: &std::path::Path) -> Result<u64> {
+    let stats = nix::sys::statvfs::statvfs(path)?;
+    Ok(stats.blocks_available() as u64 * stats.fragment_size() as u64)
+}
+
+/// Returns the number of bytes available to the node on the file system holding the given path.
+#[cfg(not(target_family = "unix"))]
+pub fn available_disk_space(_path: &std::path::Path) -> Result<u64> {
+    anyhow::bail!("Checking the free disk space is only supported on Unix")
+}
+
+/// Returns `true` if the disk is low on space, given whether it was low on space at the last check.
+/// Once low, the disk must free up an extra 10% of the threshold to recover, so the node does not flap
+/// between pausing and resuming around the threshold.
+fn is_low_on_space(available: u64, min_free_bytes: u64, was_low: bool) -> bool {
+    match was_low {
+        true => available < min_free_bytes.saturating_add(min_free_bytes / 10),
+        false => available < min_free_bytes,
+    }
+}
+
+/// Starts a task that checks the free disk space of the given directory, and calls `on_change` with `true`
+/// when it falls below `min_free_bytes`, and with `false` once it recovers.
+/// The task stops if the directory is removed, or if the free disk space cannot be checked.
+pub fn start_disk_space_loop(
+    path: PathBuf,
+    min_free_bytes: u64,
+    on_change: impl Fn(bool) + Send + 'static,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DISK_SPACE_INTERVAL);
+        let mut is_low = false;
+        while path.exists() {
+            interval.tick().await;
+            let available = match available_disk_space(&path) {
+                Ok(available) => available,
+                Err(error) => {
+                    warn!("Failed to check the free disk space, disabling the low disk space guard - {error}");
+                    break;
+                }
+            };
+            // Notify the node if the disk space crossed the threshold.
+            let was_low = is_low;
+            is_low = is_low_on_space(available, min_free_bytes, was_low);
+            match (was_low, is_low) {
+                (false, true) => {
+                    error!(
+                        "The disk holding '{}' is low on space ({} MiB free) - pausing block sync and the memory pool",
+                        path.display(),
+                        available >> 20
+                    );
+                    on_change(true);
+                }
+                (true, false) => {
+                    info!("The disk holding '{}' has {} MiB free - resuming", path.display(), available >> 20);
+                    on_change(false);
+                }
+                _ => (),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_low_on_space() {
+        assert!(!is_low_on_space(1000, 1000, false));
+        assert!(is_low_on_space(999, 1000, false));
+        // Recovering requires an extra 10% of the threshold.
+        assert!(is_low_on_space(1050, 1000, true));
+        assert!(!is_low_on_space(1100, 1000, true));
+        // A zero threshold is never crossed.
+        assert!(!is_low_on_space(0, 0, false));
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_available_disk_space() {
+        assert!(available_disk_space(&std::env::temp_dir()).is_ok());
+    }
+}
@@ -30,18 +30,33 @@ pub use snarkos_node_sync as sync;
 pub use snarkos_node_tcp as tcp;
 pub use snarkvm;
 
+mod checkpoint;
+pub use checkpoint::*;
+
 mod client;
 pub use client::*;
 
+mod disk_space;
+pub use disk_space::*;
+
 mod prover;
 pub use prover::*;
 
 mod validator;
 pub use validator::*;
 
+mod mempool;
+pub use mempool::*;
+
 mod node;
 pub use node::*;
 
+mod schema;
+pub use schema::*;
+
+mod storage;
+pub use storage::*;
+
 mod traits;
 pub use traits::*;
 
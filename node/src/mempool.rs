@@ -0,0 +1,106 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Transaction, FromBytes, Network, ToBytes};
+
+use anyhow::{ensure, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The file in the ledger directory that holds the memory pool while the node is stopped.
+const MEMPOOL_FILE: &str = "MEMPOOL";
+
+/// Returns the path of the memory pool file in the given ledger directory.
+pub fn mempool_path(ledger_path: &Path) -> PathBuf {
+    ledger_path.join(MEMPOOL_FILE)
+}
+
+/// Saves the given transactions to the memory pool file, replacing the previous one atomically.
+/// Each transaction is written with its length, followed by its bytes.
+pub fn save_mempool<N: Network>(path: &Path, transactions: &[Transaction<N>]) -> Result<()> {
+    let mut bytes = Vec::new();
+    for transaction in transactions {
+        let transaction_bytes = transaction.to_bytes_le()?;
+        bytes.extend_from_slice(&u32::try_from(transaction_bytes.len())?.to_le_bytes());
+        bytes.extend_from_slice(&transaction_bytes);
+    }
+    let mut staging_path = path.to_path_buf().into_os_string();
+    staging_path.push(".tmp");
+    fs::write(&staging_path, bytes)?;
+    Ok(fs::rename(staging_path, path)?)
+}
+
+/// Loads the transactions of the memory pool file.
+/// Note: The file is kept until the transactions are restored, so they survive a restart in the meantime.
+pub fn load_mempool<N: Network>(path: &Path) -> Result<Vec<Transaction<N>>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut transactions = Vec::new();
+    let mut remaining = bytes.as_slice();
+    while !remaining.is_empty() {
+        ensure!(remaining.len() >= 4, "The memory pool file is truncated");
+        let (length, rest) = remaining.split_at(4);
+        let length = u32::from_le_bytes(length.try_into()?) as usize;
+        ensure!(rest.len() >= length, "The memory pool file is truncated");
+        let (transaction, rest) = rest.split_at(length);
+        transactions.push(Transaction::read_le(transaction)?);
+        remaining = rest;
+    }
+    Ok(transactions)
+}
+
+/// Removes the memory pool file, once its transactions are restored, if it exists.
+pub fn remove_mempool(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    #[test]
+    fn test_empty_mempool() {
+        let directory = std::env::temp_dir().join(format!("snarkos-mempool-test-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = mempool_path(&directory);
+
+        // A missing file holds no transactions.
+        assert!(load_mempool::<MainnetV0>(&path).unwrap().is_empty());
+        save_mempool::<MainnetV0>(&path, &[]).unwrap();
+        assert!(path.exists());
+
+        // The file is kept until it is removed once restored.
+        assert!(load_mempool::<MainnetV0>(&path).unwrap().is_empty());
+        assert!(path.exists());
+        remove_mempool(&path).unwrap();
+        assert!(!path.exists());
+        remove_mempool(&path).unwrap();
+
+        // A truncated file is rejected.
+        fs::write(&path, [1, 0, 0, 0]).unwrap();
+        assert!(load_mempool::<MainnetV0>(&path).is_err());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
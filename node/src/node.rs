@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{traits::NodeInterface, Client, Prover, Validator};
+use crate::{traits::NodeInterface, Client, Prover, ProverThreads, Validator};
 use snarkos_account::Account;
+use snarkos_node_consensus::MempoolPolicy;
+use snarkos_node_rest::RestConfig;
 use snarkos_node_router::messages::NodeType;
 use snarkvm::prelude::{
     block::Block,
-    store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+    store::{
+        helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+        ConsensusStorage,
+    },
     Address,
     Network,
     PrivateKey,
@@ -25,8 +30,96 @@ use snarkvm::prelude::{
 };
 
 use aleo_std::StorageMode;
-use anyhow::Result;
-use std::{net::SocketAddr, sync::Arc};
+use anyhow::{bail, Result};
+use std::{fmt, net::SocketAddr, str::FromStr, sync::Arc};
+
+/// The storage backend of the ledger.
+///
+/// Each backend is an implementation of `ConsensusStorage`, so a new backend is added
+/// by implementing that trait and `NodeStorage`, and adding a variant here.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// The ledger is persisted in RocksDB.
+    #[default]
+    RocksDb,
+    /// The ledger and the BFT storage are kept in memory, and lost when the node stops.
+    Memory,
+}
+
+impl FromStr for StorageBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(backend: &str) -> Result<Self> {
+        match backend {
+            "rocksdb" => Ok(Self::RocksDb),
+            "memory" => Ok(Self::Memory),
+            _ => bail!("Unknown storage backend '{backend}' (expected 'rocksdb' or 'memory')"),
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RocksDb => write!(f, "rocksdb"),
+            Self::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+/// The ledger storage of a node, which determines its storage backend.
+pub trait NodeStorage<N: Network>: ConsensusStorage<N> {
+    /// The storage backend of the ledger.
+    const BACKEND: StorageBackend;
+}
+
+impl<N: Network> NodeStorage<N> for ConsensusDB<N> {
+    const BACKEND: StorageBackend = StorageBackend::RocksDb;
+}
+
+impl<N: Network> NodeStorage<N> for ConsensusMemory<N> {
+    const BACKEND: StorageBackend = StorageBackend::Memory;
+}
+
+/// The data that the node keeps, beyond the ledger itself.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StorageProfile {
+    /// The node keeps the ledger, and the indexes that are enabled individually.
+    #[default]
+    Default,
+    /// The node keeps the ledger and every index, to serve historical queries.
+    Archive,
+}
+
+impl StorageProfile {
+    /// Returns `true` if the node keeps every index.
+    pub const fn is_archive(&self) -> bool {
+        matches!(self, Self::Archive)
+    }
+}
+
+impl FromStr for StorageProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(profile: &str) -> Result<Self> {
+        match profile {
+            "default" => Ok(Self::Default),
+            "archive" => Ok(Self::Archive),
+            // Note: The ledger store cannot discard block data yet, so every node keeps the full history.
+            "pruned" => bail!("The 'pruned' storage profile is not supported yet"),
+            _ => bail!("Unknown storage profile '{profile}' (expected 'default' or 'archive')"),
+        }
+    }
+}
+
+impl fmt::Display for StorageProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Archive => write!(f, "archive"),
+        }
+    }
+}
 
 pub enum Node<N: Network> {
     /// A validator is a full node, capable of validating blocks.
@@ -35,6 +128,10 @@ pub enum Node<N: Network> {
     Prover(Arc<Prover<N, ConsensusMemory<N>>>),
     /// A client node is a full node, capable of querying with the network.
     Client(Arc<Client<N, ConsensusDB<N>>>),
+    /// A validator with an in-memory ledger.
+    ValidatorInMemory(Arc<Validator<N, ConsensusMemory<N>>>),
+    /// A client node with an in-memory ledger.
+    ClientInMemory(Arc<Client<N, ConsensusMemory<N>>>),
 }
 
 impl<N: Network> Node<N> {
@@ -43,29 +140,93 @@ impl<N: Network> Node<N> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        storage_backend: StorageBackend,
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
+        mempool_policy: MempoolPolicy,
     ) -> Result<Self> {
-        Ok(Self::Validator(Arc::new(
-            Validator::new(
+        match storage_backend {
+            StorageBackend::RocksDb => Self::validator(
                 node_ip,
                 bft_ip,
                 rest_ip,
-                rest_rps,
+                rest_config,
                 account,
                 trusted_peers,
                 trusted_validators,
                 genesis,
                 cdn,
                 storage_mode,
+                ledger_cache_size,
+                min_free_disk_mb,
+                mempool_policy,
             )
-            .await?,
-        )))
+            .await
+            .map(Self::Validator),
+            StorageBackend::Memory => Self::validator(
+                node_ip,
+                bft_ip,
+                rest_ip,
+                rest_config,
+                account,
+                trusted_peers,
+                trusted_validators,
+                genesis,
+                cdn,
+                storage_mode,
+                ledger_cache_size,
+                min_free_disk_mb,
+                mempool_policy,
+            )
+            .await
+            .map(Self::ValidatorInMemory),
+        }
+    }
+
+    /// Initializes a new validator node, with the ledger storage `C`.
+    async fn validator<C: NodeStorage<N>>(
+        node_ip: SocketAddr,
+        bft_ip: Option<SocketAddr>,
+        rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
+        account: Account<N>,
+        trusted_peers: &[SocketAddr],
+        trusted_validators: &[SocketAddr],
+        genesis: Block<N>,
+        cdn: Option<String>,
+        storage_mode: StorageMode,
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
+        mempool_policy: MempoolPolicy,
+    ) -> Result<Arc<Validator<N, C>>> {
+        // Upgrade the storage schema of the ledger, if needed.
+        if C::BACKEND == StorageBackend::RocksDb {
+            crate::migrate_storage(N::ID, &storage_mode)?;
+        }
+        let validator = Validator::new(
+            node_ip,
+            bft_ip,
+            rest_ip,
+            rest_config,
+            account,
+            trusted_peers,
+            trusted_validators,
+            genesis,
+            cdn,
+            storage_mode,
+            ledger_cache_size,
+            min_free_disk_mb,
+            mempool_policy,
+        )
+        .await?;
+        Ok(Arc::new(validator))
     }
 
     /// Initializes a new prover node.
@@ -75,24 +236,89 @@ impl<N: Network> Node<N> {
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         storage_mode: StorageMode,
+        prover_threads: ProverThreads,
     ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, storage_mode).await?)))
+        let prover = Prover::new(node_ip, account, trusted_peers, genesis, storage_mode, prover_threads).await?;
+        Ok(Self::Prover(Arc::new(prover)))
     }
 
     /// Initializes a new client node.
     pub async fn new_client(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        storage_backend: StorageBackend,
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
     ) -> Result<Self> {
-        Ok(Self::Client(Arc::new(
-            Client::new(node_ip, rest_ip, rest_rps, account, trusted_peers, genesis, cdn, storage_mode).await?,
-        )))
+        match storage_backend {
+            StorageBackend::RocksDb => Self::client(
+                node_ip,
+                rest_ip,
+                rest_config,
+                account,
+                trusted_peers,
+                genesis,
+                cdn,
+                storage_mode,
+                ledger_cache_size,
+                min_free_disk_mb,
+            )
+            .await
+            .map(Self::Client),
+            StorageBackend::Memory => Self::client(
+                node_ip,
+                rest_ip,
+                rest_config,
+                account,
+                trusted_peers,
+                genesis,
+                cdn,
+                storage_mode,
+                ledger_cache_size,
+                min_free_disk_mb,
+            )
+            .await
+            .map(Self::ClientInMemory),
+        }
+    }
+
+    /// Initializes a new client node, with the ledger storage `C`.
+    async fn client<C: NodeStorage<N>>(
+        node_ip: SocketAddr,
+        rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
+        account: Account<N>,
+        trusted_peers: &[SocketAddr],
+        genesis: Block<N>,
+        cdn: Option<String>,
+        storage_mode: StorageMode,
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
+    ) -> Result<Arc<Client<N, C>>> {
+        // Upgrade the storage schema of the ledger, if needed.
+        if C::BACKEND == StorageBackend::RocksDb {
+            crate::migrate_storage(N::ID, &storage_mode)?;
+        }
+        let client = Client::new(
+            node_ip,
+            rest_ip,
+            rest_config,
+            account,
+            trusted_peers,
+            genesis,
+            cdn,
+            storage_mode,
+            ledger_cache_size,
+            min_free_disk_mb,
+        )
+        .await?;
+        Ok(Arc::new(client))
     }
 
     /// Returns the node type.
@@ -101,6 +327,8 @@ impl<N: Network> Node<N> {
             Self::Validator(validator) => validator.node_type(),
             Self::Prover(prover) => prover.node_type(),
             Self::Client(client) => client.node_type(),
+            Self::ValidatorInMemory(validator) => validator.node_type(),
+            Self::ClientInMemory(client) => client.node_type(),
         }
     }
 
@@ -110,6 +338,8 @@ impl<N: Network> Node<N> {
             Self::Validator(node) => node.private_key(),
             Self::Prover(node) => node.private_key(),
             Self::Client(node) => node.private_key(),
+            Self::ValidatorInMemory(node) => node.private_key(),
+            Self::ClientInMemory(node) => node.private_key(),
         }
     }
 
@@ -119,6 +349,8 @@ impl<N: Network> Node<N> {
             Self::Validator(node) => node.view_key(),
             Self::Prover(node) => node.view_key(),
             Self::Client(node) => node.view_key(),
+            Self::ValidatorInMemory(node) => node.view_key(),
+            Self::ClientInMemory(node) => node.view_key(),
         }
     }
 
@@ -128,6 +360,8 @@ impl<N: Network> Node<N> {
             Self::Validator(node) => node.address(),
             Self::Prover(node) => node.address(),
             Self::Client(node) => node.address(),
+            Self::ValidatorInMemory(node) => node.address(),
+            Self::ClientInMemory(node) => node.address(),
         }
     }
 
@@ -137,6 +371,8 @@ impl<N: Network> Node<N> {
             Self::Validator(node) => node.is_dev(),
             Self::Prover(node) => node.is_dev(),
             Self::Client(node) => node.is_dev(),
+            Self::ValidatorInMemory(node) => node.is_dev(),
+            Self::ClientInMemory(node) => node.is_dev(),
         }
     }
 }
@@ -12,8 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod puzzle_sources;
+use puzzle_sources::PuzzleSources;
+
 mod router;
 
+mod workers;
+pub use workers::*;
+
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::ProverLedgerService;
@@ -49,12 +55,16 @@ use rand::{rngs::OsRng, CryptoRng, Rng};
 use std::{
     net::SocketAddr,
     sync::{
-        atomic::{AtomicBool, AtomicU8, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
         Arc,
     },
+    time::Instant,
 };
 use tokio::task::JoinHandle;
 
+/// The interval at which the prover reports its proof rate.
+const PROOF_RATE_INTERVAL_IN_SECS: u64 = 60;
+
 /// A prover is a light node, capable of producing proofs for consensus.
 #[derive(Clone)]
 pub struct Prover<N: Network, C: ConsensusStorage<N>> {
@@ -74,6 +84,13 @@ pub struct Prover<N: Network, C: ConsensusStorage<N>> {
     puzzle_instances: Arc<AtomicU8>,
     /// The maximum number of puzzle instances.
     max_puzzle_instances: u8,
+    /// The peers to request the coinbase puzzle from.
+    puzzle_sources: Arc<Mutex<PuzzleSources>>,
+    /// The number of proofs computed, and of solutions found, since the node started.
+    num_proofs: Arc<(AtomicU64, AtomicU64)>,
+    /// The dedicated pool of proving threads, if the CPU usage of the prover is bounded.
+    #[cfg(feature = "parallel")]
+    proving_pool: Option<Arc<rayon::ThreadPool>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -90,6 +107,7 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         storage_mode: StorageMode,
+        prover_threads: ProverThreads,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -115,7 +133,12 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         // Load the coinbase puzzle.
         let coinbase_puzzle = CoinbasePuzzle::<N>::load()?;
         // Compute the maximum number of puzzle instances.
-        let max_puzzle_instances = num_cpus::get().saturating_sub(2).clamp(1, 6);
+        let max_puzzle_instances = prover_threads.max_puzzle_instances();
+        match prover_threads.cores.is_empty() {
+            true if prover_threads.is_bounded() => info!("Proving with {} threads", prover_threads.num_threads()),
+            true => (),
+            false => info!("Proving with {} threads on cores {:?}", prover_threads.num_threads(), prover_threads.cores),
+        }
         // Initialize the node.
         let node = Self {
             router,
@@ -126,6 +149,10 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             latest_block_header: Default::default(),
             puzzle_instances: Default::default(),
             max_puzzle_instances: u8::try_from(max_puzzle_instances)?,
+            puzzle_sources: Arc::new(Mutex::new(PuzzleSources::new(trusted_peers.to_vec()))),
+            num_proofs: Default::default(),
+            #[cfg(feature = "parallel")]
+            proving_pool: prover_threads.build_pool()?.map(Arc::new),
             handles: Default::default(),
             shutdown,
             _phantom: Default::default(),
@@ -173,6 +200,30 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
                 prover.coinbase_puzzle_loop().await;
             }));
         }
+        // Report the proof rate periodically.
+        let prover = self.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            prover.proof_rate_loop().await;
+        }));
+    }
+
+    /// Logs the proof rate of the prover, and reports it to the metrics.
+    async fn proof_rate_loop(&self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(PROOF_RATE_INTERVAL_IN_SECS));
+        let mut previous = (Instant::now(), 0);
+        loop {
+            interval.tick().await;
+            let (now, num_proofs) = (Instant::now(), self.num_proofs.0.load(Ordering::Relaxed));
+            let proof_rate = (num_proofs - previous.1) as f64 / now.duration_since(previous.0).as_secs_f64().max(1e-3);
+            previous = (now, num_proofs);
+
+            #[cfg(feature = "metrics")]
+            metrics::gauge(metrics::prover::PROOF_RATE, proof_rate);
+            if proof_rate > 0.0 {
+                let num_solutions = self.num_proofs.1.load(Ordering::Relaxed);
+                info!("Proving at {proof_rate:.2} proofs/s ({num_proofs} proofs, {num_solutions} solutions found)");
+            }
+        }
     }
 
     /// Executes an instance of the coinbase puzzle.
@@ -213,6 +264,9 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
                 // If the prover found a solution, then broadcast it.
                 if let Ok(Some((solution_target, solution))) = result {
                     info!("Found a Solution '{}' (Proof Target {solution_target})", solution.commitment());
+                    self.num_proofs.1.fetch_add(1, Ordering::Relaxed);
+                    #[cfg(feature = "metrics")]
+                    metrics::increment_counter(metrics::prover::SOLUTIONS_FOUND);
                     // Broadcast the prover solution.
                     self.broadcast_prover_solution(solution);
                 }
@@ -250,11 +304,26 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         );
 
         // Compute the prover solution.
-        let result = self
-            .coinbase_puzzle
-            .prove(epoch_challenge, self.address(), rng.gen(), Some(proof_target))
-            .ok()
-            .and_then(|solution| solution.to_target().ok().map(|solution_target| (solution_target, solution)));
+        let (coinbase_puzzle, address, nonce) = (&self.coinbase_puzzle, self.address(), rng.gen());
+        let prove = move || {
+            coinbase_puzzle
+                .prove(epoch_challenge, address, nonce, Some(proof_target))
+                .ok()
+                .and_then(|solution| solution.to_target().ok().map(|solution_target| (solution_target, solution)))
+        };
+        // Run the proof on the dedicated proving threads, if there are any.
+        #[cfg(feature = "parallel")]
+        let result = match &self.proving_pool {
+            Some(pool) => pool.install(prove),
+            None => prove(),
+        };
+        #[cfg(not(feature = "parallel"))]
+        let result = prove();
+
+        // Count the proof.
+        self.num_proofs.0.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::prover::PUZZLE_ITERATIONS);
 
         // Decrement the puzzle instances.
         self.decrement_puzzle_instances();
@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indexmap::IndexMap;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of blocks a puzzle source may be behind the other peers, and still be used.
+const MAX_PUZZLE_SOURCE_LAG: u32 = 10;
+/// The time after which an unanswered puzzle request marks the peer as unhealthy.
+const PUZZLE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(20);
+/// The time after which an unhealthy peer is tried again.
+const PUZZLE_RETRY_INTERVAL: Duration = Duration::from_secs(120);
+
+/// The peers a prover requests the coinbase puzzle from.
+/// The prover fails over along the trusted peers, in the order they were given, and then to the other peers,
+/// and fails back to a preferred peer as soon as it is healthy again.
+pub(crate) struct PuzzleSources {
+    /// The preferred peers, in order.
+    preferred: Vec<SocketAddr>,
+    /// The time of the oldest unanswered puzzle request, for each peer.
+    pending: HashMap<SocketAddr, Instant>,
+    /// The peer the puzzle was last requested from.
+    current: Option<SocketAddr>,
+}
+
+impl PuzzleSources {
+    /// Initializes the puzzle sources with the given preferred peers, in order.
+    pub(crate) fn new(preferred: Vec<SocketAddr>) -> Self {
+        Self { preferred, pending: Default::default(), current: None }
+    }
+
+    /// Returns the peer to request the puzzle from, given the connected peers with their latest heights.
+    pub(crate) fn select(&mut self, peers: &IndexMap<SocketAddr, u32>, now: Instant) -> Option<SocketAddr> {
+        // Forget the unanswered requests of the disconnected peers, and retry the peers that have waited long enough.
+        self.pending.retain(|peer_ip, since| {
+            peers.contains_key(peer_ip) && now.saturating_duration_since(*since) < PUZZLE_RETRY_INTERVAL
+        });
+
+        let max_height = peers.values().copied().max()?;
+        let is_healthy = |peer_ip: &SocketAddr, height: u32| {
+            let is_responsive = self
+                .pending
+                .get(peer_ip)
+                .map_or(true, |since| now.saturating_duration_since(*since) < PUZZLE_RESPONSE_TIMEOUT);
+            is_responsive && height.saturating_add(MAX_PUZZLE_SOURCE_LAG) >= max_height
+        };
+        // Select the first healthy preferred peer, or else the healthy peer with the highest block height.
+        let selected = self
+            .preferred
+            .iter()
+            .find(|peer_ip| peers.get(*peer_ip).map_or(false, |height| is_healthy(peer_ip, *height)))
+            .copied()
+            .or_else(|| {
+                peers
+                    .iter()
+                    .filter(|(peer_ip, height)| is_healthy(peer_ip, **height))
+                    .max_by_key(|(_, height)| **height)
+                    .map(|(peer_ip, _)| *peer_ip)
+            });
+
+        if selected != self.current {
+            match (self.current, selected) {
+                (Some(previous), Some(next)) => info!("Switching the puzzle source from '{previous}' to '{next}'"),
+                (None, Some(next)) => debug!("Requesting the coinbase puzzle from '{next}'"),
+                (Some(previous), None) => warn!("Lost the puzzle source '{previous}', no other peer is healthy"),
+                (None, None) => (),
+            }
+            self.current = selected;
+        }
+        selected
+    }
+
+    /// Records a puzzle request to the given peer.
+    pub(crate) fn on_request(&mut self, peer_ip: SocketAddr, now: Instant) {
+        self.pending.entry(peer_ip).or_insert(now);
+    }
+
+    /// Records a puzzle response from the given peer.
+    pub(crate) fn on_response(&mut self, peer_ip: SocketAddr) {
+        self.pending.remove(&peer_ip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::indexmap;
+
+    #[test]
+    fn test_failover_and_failback() {
+        let (a, b, c) =
+            ("1.1.1.1:4130".parse().unwrap(), "2.2.2.2:4130".parse().unwrap(), "3.3.3.3:4130".parse().unwrap());
+        let mut sources = PuzzleSources::new(vec![a, b]);
+        let now = Instant::now();
+
+        // The first preferred peer is selected, even if another peer is slightly ahead.
+        let peers = indexmap! { a => 100, b => 100, c => 105 };
+        assert_eq!(sources.select(&peers, now), Some(a));
+        // A preferred peer that is far behind is skipped.
+        assert_eq!(sources.select(&indexmap! { a => 80, b => 100, c => 100 }, now), Some(b));
+
+        // An unresponsive peer is skipped, until it is retried.
+        sources.on_request(a, now);
+        assert_eq!(sources.select(&peers, now + Duration::from_secs(30)), Some(b));
+        assert_eq!(sources.select(&peers, now + Duration::from_secs(150)), Some(a));
+        sources.on_request(a, now + Duration::from_secs(150));
+        sources.on_response(a);
+        assert_eq!(sources.select(&peers, now + Duration::from_secs(200)), Some(a));
+
+        // Without a preferred peer, the highest peer is selected.
+        assert_eq!(sources.select(&indexmap! { c => 105 }, now), Some(c));
+        assert_eq!(sources.select(&IndexMap::new(), now), None);
+    }
+}
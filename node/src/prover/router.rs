@@ -27,7 +27,7 @@ use snarkos_node_router::messages::{
 use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
 use snarkvm::prelude::{block::Transaction, Network};
 
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, time::Instant};
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Prover<N, C> {
     /// Returns a reference to the TCP instance.
@@ -85,6 +85,11 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Prover<N, C> {
     fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
         Default::default()
     }
+
+    /// Returns the depth of the outbound message queue for the given peer address.
+    fn message_queue_depth(&self, peer_addr: SocketAddr) -> usize {
+        self.router().message_queue_depth(peer_addr, Self::MESSAGE_QUEUE_DEPTH)
+    }
 }
 
 #[async_trait]
@@ -121,8 +126,17 @@ impl<N: Network, C: ConsensusStorage<N>> Heartbeat<N> for Prover<N, C> {
     fn handle_puzzle_request(&self) {
         // Find the sync peers.
         if let Some((sync_peers, _)) = self.sync.find_sync_peers() {
-            // Choose the peer with the highest block height.
-            if let Some((peer_ip, _)) = sync_peers.into_iter().max_by_key(|(_, height)| *height) {
+            // Choose the first healthy trusted peer, or else the healthy peer with the highest block height.
+            let now = Instant::now();
+            let peer_ip = {
+                let mut puzzle_sources = self.puzzle_sources.lock();
+                let peer_ip = puzzle_sources.select(&sync_peers, now);
+                if let Some(peer_ip) = peer_ip {
+                    puzzle_sources.on_request(peer_ip, now);
+                }
+                peer_ip
+            };
+            if let Some(peer_ip) = peer_ip {
                 // Request the coinbase puzzle from the peer.
                 Outbound::send(self, peer_ip, Message::PuzzleRequest(PuzzleRequest));
             }
@@ -205,6 +219,8 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Prover<N, C> {
             header.proof_target()
         );
 
+        // Mark the peer as responsive.
+        self.puzzle_sources.lock().on_response(peer_ip);
         // Save the latest epoch challenge in the node.
         self.latest_epoch_challenge.write().replace(Arc::new(epoch_challenge));
         // Save the latest block header in the node.
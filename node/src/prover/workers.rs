@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+
+/// The CPU budget of the proving workers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverThreads {
+    /// The number of proving threads, or `None` to use every core.
+    pub num_threads: Option<usize>,
+    /// The cores the proving threads are pinned to, or empty to leave them to the OS scheduler.
+    pub cores: Vec<usize>,
+}
+
+impl ProverThreads {
+    /// Returns the number of proving threads.
+    pub fn num_threads(&self) -> usize {
+        match (self.num_threads, self.cores.len()) {
+            (Some(num_threads), _) => num_threads,
+            (None, 0) => num_cpus::get(),
+            // Default to one thread per pinned core.
+            (None, num_cores) => num_cores,
+        }
+    }
+
+    /// Returns `true` if the proving threads run in a dedicated pool, rather than in the global one.
+    pub fn is_bounded(&self) -> bool {
+        self.num_threads.is_some() || !self.cores.is_empty()
+    }
+
+    /// Returns the maximum number of concurrent puzzle instances.
+    pub fn max_puzzle_instances(&self) -> usize {
+        self.num_threads().min(num_cpus::get()).saturating_sub(2).clamp(1, 6)
+    }
+
+    /// Initializes the dedicated pool of proving threads, or `None` to use the global pool.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn build_pool(&self) -> Result<Option<rayon::ThreadPool>> {
+        if !self.is_bounded() {
+            return Ok(None);
+        }
+        let cores = self.cores.clone();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads())
+            .thread_name(|index| format!("prover-{index}"))
+            .start_handler(move |index| {
+                // Pin each thread to a core, cycling through the cores if there are more threads.
+                if !cores.is_empty() {
+                    if let Err(error) = pin_to_core(cores[index % cores.len()]) {
+                        warn!("Failed to pin the proving thread {index} to a core - {error}");
+                    }
+                }
+            })
+            .build()?;
+        Ok(Some(pool))
+    }
+}
+
+/// Parses a list of cores, such as `0-3,6`, into their indices.
+pub fn parse_core_list(list: &str) -> Result<Vec<usize>> {
+    let mut cores = Vec::new();
+    for range in list.split(',').map(str::trim).filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.trim().parse::<usize>()?, end.trim().parse::<usize>()?);
+                ensure!(start <= end, "The core range '{range}' is reversed");
+                cores.extend(start..=end);
+            }
+            None => cores.push(range.parse()?),
+        }
+    }
+    cores.sort_unstable();
+    cores.dedup();
+    ensure!(!cores.is_empty(), "The core list '{list}' is empty");
+    Ok(cores)
+}
+
+/// Pins the current thread to the given core.
+#[cfg(all(feature = "parallel", target_os = "linux"))]
+fn pin_to_core(core: usize) -> Result<()> {
+    use nix::{sched::CpuSet, unistd::Pid};
+
+    let mut cpu_set = CpuSet::new();
+    cpu_set.set(core)?;
+    Ok(nix::sched::sched_setaffinity(Pid::from_raw(0), &cpu_set)?)
+}
+
+/// Pins the current thread to the given core.
+#[cfg(all(feature = "parallel", not(target_os = "linux")))]
+fn pin_to_core(_core: usize) -> Result<()> {
+    anyhow::bail!("Pinning threads to cores is only supported on Linux")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core_list() {
+        assert_eq!(parse_core_list("0-3,6").unwrap(), vec![0, 1, 2, 3, 6]);
+        assert_eq!(parse_core_list("5, 1,1").unwrap(), vec![1, 5]);
+        assert!(parse_core_list("3-1").is_err());
+        assert!(parse_core_list("a").is_err());
+        assert!(parse_core_list(",").is_err());
+    }
+
+    #[test]
+    fn test_num_threads() {
+        let threads = ProverThreads { num_threads: None, cores: vec![2, 3] };
+        assert_eq!(threads.num_threads(), 2);
+        assert!(threads.is_bounded());
+        assert_eq!(threads.max_puzzle_instances(), 1);
+        assert!(!ProverThreads::default().is_bounded());
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use aleo_std::StorageMode;
+use anyhow::{anyhow, bail, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The current version of the storage schema.
+pub const STORAGE_SCHEMA_VERSION: u32 = 1;
+/// The file in the ledger directory that records the version of the storage schema.
+/// A ledger without it predates the schema versions, and has version `1`.
+const SCHEMA_VERSION_FILE: &str = "SCHEMA_VERSION";
+
+/// A migration of the storage from one schema version to the next.
+pub struct Migration {
+    /// The schema version the migration upgrades from.
+    pub from: u32,
+    /// The description of the migration.
+    pub description: &'static str,
+    /// Upgrades the given ledger directory in place. The table files are hard-linked into the backup,
+    /// so a migration must replace files rather than modify them.
+    pub migrate: fn(&Path) -> Result<()>,
+}
+
+/// The storage migrations, in order. A change to the storage format increments
+/// `STORAGE_SCHEMA_VERSION`, and adds the migration from the previous version here.
+const MIGRATIONS: &[Migration] = &[];
+
+/// Upgrades the ledger of the given network to the current storage schema, if needed.
+pub fn migrate_storage(network: u16, storage_mode: &StorageMode) -> Result<()> {
+    let ledger_path = aleo_std::aleo_ledger_dir(network, storage_mode.clone());
+    migrate_storage_with(&ledger_path, STORAGE_SCHEMA_VERSION, MIGRATIONS)
+}
+
+/// Upgrades the given ledger directory to the given schema version, using the given migrations.
+/// The ledger is backed up first, and restored from the backup if a migration fails.
+fn migrate_storage_with(ledger_path: &Path, current_version: u32, migrations: &[Migration]) -> Result<()> {
+    // A new ledger is created with the current schema.
+    if !ledger_path.exists() {
+        return Ok(());
+    }
+    let version = read_schema_version(ledger_path)?;
+    if version > current_version {
+        bail!(
+            "The ledger in '{}' uses storage schema version {version}, but this version of snarkOS only supports \
+             up to version {current_version} - upgrade snarkOS to use this ledger",
+            ledger_path.display()
+        );
+    }
+    if version == current_version {
+        return write_schema_version(ledger_path, version);
+    }
+
+    // Back up the ledger, so a failed migration can be rolled back.
+    let mut backup_path = ledger_path.to_path_buf().into_os_string();
+    backup_path.push(format!(".backup-v{version}"));
+    let backup_path = PathBuf::from(backup_path);
+    if backup_path.exists() {
+        fs::remove_dir_all(&backup_path)?;
+    }
+    info!("Backing up the ledger to '{}' before upgrading its storage schema", backup_path.display());
+    backup_dir(ledger_path, &backup_path)?;
+
+    // Apply the migrations in order, recording the version after each one.
+    let result = (version..current_version).try_for_each(|from| {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.from == from)
+            .ok_or_else(|| anyhow!("Missing the storage migration from schema version {from}"))?;
+        info!("Upgrading the storage schema to version {} - {}", from + 1, migration.description);
+        (migration.migrate)(ledger_path)?;
+        write_schema_version(ledger_path, from + 1)
+    });
+
+    match result {
+        Ok(()) => {
+            fs::remove_dir_all(&backup_path)?;
+            info!("Upgraded the storage schema from version {version} to {current_version}");
+            Ok(())
+        }
+        Err(error) => {
+            // Roll back to the backup.
+            fs::remove_dir_all(ledger_path)?;
+            fs::rename(&backup_path, ledger_path)?;
+            Err(error.context(format!("Failed to upgrade the storage schema from version {version}")))
+        }
+    }
+}
+
+/// Returns the storage schema version of the given ledger directory.
+fn read_schema_version(ledger_path: &Path) -> Result<u32> {
+    match fs::read_to_string(ledger_path.join(SCHEMA_VERSION_FILE)) {
+        Ok(version) => version.trim().parse().context("Invalid storage schema version"),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(1),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Records the storage schema version of the given ledger directory.
+fn write_schema_version(ledger_path: &Path, version: u32) -> Result<()> {
+    Ok(fs::write(ledger_path.join(SCHEMA_VERSION_FILE), version.to_string())?)
+}
+
+/// Copies the given directory, hard-linking the table files, which RocksDB never modifies.
+fn backup_dir(source: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            backup_dir(&entry.path(), &target)?;
+        } else if entry.path().extension().map_or(true, |extension| extension != "sst")
+            || fs::hard_link(entry.path(), &target).is_err()
+        {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_storage() {
+        let directory = std::env::temp_dir().join(format!("snarkos-schema-test-{}", std::process::id()));
+        let ledger_path = directory.join("ledger");
+        fs::create_dir_all(&ledger_path).unwrap();
+        fs::write(ledger_path.join("000001.sst"), b"abc").unwrap();
+
+        // A ledger without a version has the first version.
+        migrate_storage_with(&ledger_path, 1, &[]).unwrap();
+        assert_eq!(read_schema_version(&ledger_path).unwrap(), 1);
+
+        // The migrations are applied in order.
+        let migrations = [
+            Migration {
+                from: 1,
+                description: "rename",
+                migrate: |path| Ok(fs::rename(path.join("000001.sst"), path.join("a"))?),
+            },
+            Migration { from: 2, description: "write", migrate: |path| Ok(fs::write(path.join("b"), b"")?) },
+        ];
+        migrate_storage_with(&ledger_path, 3, &migrations).unwrap();
+        assert_eq!(read_schema_version(&ledger_path).unwrap(), 3);
+        assert!(ledger_path.join("a").exists() && ledger_path.join("b").exists());
+
+        // A failed migration rolls back to the backup.
+        let migrations = [
+            Migration { from: 3, description: "write", migrate: |path| Ok(fs::write(path.join("c"), b"")?) },
+            Migration { from: 4, description: "fail", migrate: |_| bail!("Failed") },
+        ];
+        assert!(migrate_storage_with(&ledger_path, 5, &migrations).is_err());
+        assert_eq!(read_schema_version(&ledger_path).unwrap(), 3);
+        assert!(!ledger_path.join("c").exists());
+
+        // A ledger from a newer schema is rejected.
+        assert!(migrate_storage_with(&ledger_path, 2, &[]).is_err());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use std::{fs, path::Path};
+
+#[cfg(feature = "metrics")]
+use aleo_std::StorageMode;
+#[cfg(feature = "metrics")]
+use std::time::Duration;
+
+/// The interval at which the storage metrics are updated.
+#[cfg(feature = "metrics")]
+const STORAGE_METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The on-disk statistics of the ledger storage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    /// The total size of the storage files, in bytes.
+    pub size: u64,
+    /// The number of storage files.
+    pub files: u64,
+    /// The total size of the table (SST) files, in bytes.
+    pub table_size: u64,
+    /// The number of table (SST) files.
+    pub table_files: u64,
+    /// The total size of the write-ahead log files, in bytes.
+    pub wal_size: u64,
+}
+
+impl StorageStats {
+    /// Computes the statistics of the storage in the given directory.
+    pub fn compute(path: &Path) -> Result<Self> {
+        let mut stats = Self::default();
+        let mut directories = vec![path.to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(&directory)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_dir() {
+                    directories.push(entry.path());
+                    continue;
+                }
+                stats.size += metadata.len();
+                stats.files += 1;
+                match entry.path().extension().and_then(|extension| extension.to_str()) {
+                    Some("sst") => {
+                        stats.table_size += metadata.len();
+                        stats.table_files += 1;
+                    }
+                    Some("log") => stats.wal_size += metadata.len(),
+                    _ => (),
+                }
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// Starts a task that reports the storage statistics of the ledger to the metrics.
+/// The task stops if the ledger is not stored on disk.
+#[cfg(feature = "metrics")]
+pub fn start_storage_metrics_loop(network: u16, storage_mode: StorageMode) -> tokio::task::JoinHandle<()> {
+    let ledger_path = aleo_std::aleo_ledger_dir(network, storage_mode);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STORAGE_METRICS_INTERVAL);
+        while ledger_path.exists() {
+            interval.tick().await;
+            // Walk the ledger directory in a blocking task, as it may hold many files.
+            let path = ledger_path.clone();
+            match tokio::task::spawn_blocking(move || StorageStats::compute(&path)).await {
+                Ok(Ok(stats)) => {
+                    metrics::gauge(metrics::storage::SIZE, stats.size as f64);
+                    metrics::gauge(metrics::storage::FILES, stats.files as f64);
+                    metrics::gauge(metrics::storage::TABLE_SIZE, stats.table_size as f64);
+                    metrics::gauge(metrics::storage::TABLE_FILES, stats.table_files as f64);
+                    metrics::gauge(metrics::storage::WAL_SIZE, stats.wal_size as f64);
+                }
+                Ok(Err(error)) => warn!("Failed to compute the storage metrics - {error}"),
+                Err(error) => warn!("Failed to compute the storage metrics - {error}"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_stats() {
+        let directory = std::env::temp_dir().join(format!("snarkos-storage-test-{}", std::process::id()));
+        fs::create_dir_all(directory.join("nested")).unwrap();
+        fs::write(directory.join("000001.sst"), b"abc").unwrap();
+        fs::write(directory.join("nested").join("000002.sst"), b"de").unwrap();
+        fs::write(directory.join("000003.log"), b"f").unwrap();
+        fs::write(directory.join("CURRENT"), b"MANIFEST-000004").unwrap();
+
+        let stats = StorageStats::compute(&directory).unwrap();
+        assert_eq!(stats, StorageStats { size: 21, files: 4, table_size: 5, table_files: 2, wal_size: 1 });
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -14,11 +14,15 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeStorage, StorageBackend};
 use snarkos_account::Account;
-use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService, spawn_blocking};
-use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_bft::{
+    helpers::init_primary_channels,
+    ledger_service::{CoreLedgerService, LedgerCache},
+    spawn_blocking,
+};
+use snarkos_node_consensus::{Consensus, MempoolPolicy};
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
@@ -33,7 +37,7 @@ use snarkos_node_tcp::{
     P2P,
 };
 use snarkvm::prelude::{
-    block::{Block, Header},
+    block::{Block, Header, Transaction},
     coinbase::ProverSolution,
     store::ConsensusStorage,
     Ledger,
@@ -46,6 +50,7 @@ use core::future::Future;
 use parking_lot::Mutex;
 use std::{
     net::SocketAddr,
+    path::{Path, PathBuf},
     sync::{atomic::AtomicBool, Arc},
     time::Duration,
 };
@@ -56,6 +61,8 @@ use tokio::task::JoinHandle;
 pub struct Validator<N: Network, C: ConsensusStorage<N>> {
     /// The ledger of the node.
     ledger: Ledger<N, C>,
+    /// The cache of the recently read blocks.
+    ledger_cache: LedgerCache<N>,
     /// The consensus module of the node.
     consensus: Consensus<N>,
     /// The router of the node.
@@ -64,6 +71,10 @@ pub struct Validator<N: Network, C: ConsensusStorage<N>> {
     rest: Option<Rest<N, C, Self>>,
     /// The sync module.
     sync: BlockSync<N>,
+    /// The path of the memory pool file, if the ledger is persisted.
+    mempool_path: Option<PathBuf>,
+    /// The transactions of the memory pool file that are not restored yet, in reverse order.
+    pending_mempool: Arc<Mutex<Vec<Transaction<N>>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -76,14 +87,20 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
-    ) -> Result<Self> {
+        ledger_cache_size: usize,
+        min_free_disk_mb: u64,
+        mempool_policy: MempoolPolicy,
+    ) -> Result<Self>
+    where
+        C: NodeStorage<N>,
+    {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
 
@@ -92,6 +109,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the ledger.
         let ledger = Ledger::load(genesis, storage_mode.clone())?;
+        // Check the ledger against its last checkpoint, if it is persisted.
+        let is_persistent = C::BACKEND == StorageBackend::RocksDb;
+        if is_persistent {
+            if let Err(error) = crate::check_ledger_checkpoint(&ledger, &storage_mode) {
+                crate::log_clean_error(&storage_mode);
+                return Err(error);
+            }
+        }
 
         // Initialize the CDN.
         if let Some(base_url) = cdn {
@@ -105,13 +130,22 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         }
 
         // Initialize the ledger service.
-        let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone(), shutdown.clone()));
+        // Initialize the cache of the recently read blocks, shared by the ledger service, router, and REST server.
+        let ledger_cache = LedgerCache::new(ledger_cache_size);
+        let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone(), ledger_cache.clone(), shutdown.clone()));
         // Initialize the sync module.
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
-        // Initialize the consensus.
-        let mut consensus =
-            Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, storage_mode.clone())?;
+        // Initialize the consensus, keeping its storage in memory if the ledger is.
+        let mut consensus = Consensus::new(
+            account.clone(),
+            ledger_service,
+            bft_ip,
+            trusted_validators,
+            storage_mode.clone(),
+            !is_persistent,
+            mempool_policy,
+        )?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -131,25 +165,64 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         // Initialize the node.
         let mut node = Self {
             ledger: ledger.clone(),
+            ledger_cache: ledger_cache.clone(),
             consensus: consensus.clone(),
             router,
             rest: None,
             sync,
+            mempool_path: is_persistent
+                .then(|| crate::mempool_path(&aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone()))),
+            pending_mempool: Default::default(),
             handles: Default::default(),
             shutdown,
         };
         // Initialize the transaction pool.
-        node.initialize_transaction_pool(storage_mode)?;
+        node.initialize_transaction_pool(storage_mode.clone())?;
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest =
-                Some(Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?);
+            // Retrieve the block sync module of the BFT, which advances the ledger of the validator.
+            let sync = consensus.bft().primary().sync().block_sync().clone();
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rest_config,
+                    Some(consensus),
+                    ledger.clone(),
+                    ledger_cache,
+                    Arc::new(node.clone()),
+                    sync,
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
+        // Restore the memory pool saved at the last shutdown, if there is one.
+        if let Some(path) = &node.mempool_path {
+            node.restore_mempool(path);
+        }
         // Initialize the notification message loop.
         node.handles.lock().push(crate::start_notification_message_loop());
+        // Initialize the checkpoint, disk space, and storage metrics loops, if the ledger is persisted.
+        if is_persistent {
+            node.handles.lock().push(crate::start_checkpoint_loop(ledger.clone(), storage_mode.clone()));
+            if min_free_disk_mb > 0 {
+                // While the disk is low on space, pause the block sync of the BFT and the memory pool.
+                let consensus = node.consensus.clone();
+                let ledger_path = aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone());
+                node.handles.lock().push(crate::start_disk_space_loop(
+                    ledger_path,
+                    min_free_disk_mb << 20,
+                    move |is_low| {
+                        consensus.bft().primary().sync().block_sync().set_paused(is_low);
+                        consensus.set_mempool_paused(is_low);
+                    },
+                ));
+            }
+            #[cfg(feature = "metrics")]
+            node.handles.lock().push(crate::start_storage_metrics_loop(N::ID, storage_mode));
+        }
         // Pass the node to the signal handler.
         let _ = signal_node.set(node.clone());
         // Return the node.
@@ -418,6 +491,66 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         Ok(())
     }
 
+    /// Adds the transactions of the memory pool file to the memory pool, once the node is synced.
+    /// The transactions are verified again as they enter the memory pool, and those in the ledger are dropped.
+    /// The file is removed once every transaction is restored, and the pending transactions are saved on shutdown.
+    fn restore_mempool(&self, path: &Path) {
+        let mut transactions = match crate::load_mempool::<N>(path) {
+            Ok(transactions) => transactions,
+            Err(error) => {
+                warn!("Failed to restore the memory pool - {error}");
+                return;
+            }
+        };
+        if transactions.is_empty() {
+            return;
+        }
+        info!("Restoring {} transactions to the memory pool", transactions.len());
+        transactions.reverse();
+        *self.pending_mempool.lock() = transactions;
+
+        let node = self.clone();
+        let path = path.to_path_buf();
+        self.spawn(async move {
+            // Wait for the node to sync, as the memory pool rejects transactions until then.
+            while !node.consensus.bft().is_synced() {
+                if node.shutdown.load(std::sync::atomic::Ordering::Relaxed) {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            // Keep each transaction pending until it is added, so it is saved if the node shuts down meanwhile.
+            loop {
+                let Some(transaction) = node.pending_mempool.lock().last().cloned() else { break };
+                let transaction_id = transaction.id();
+                if let Err(error) = node.consensus.add_unconfirmed_transaction(transaction, None).await {
+                    debug!("Dropped the restored transaction '{transaction_id}' - {error}");
+                }
+                node.pending_mempool.lock().pop();
+            }
+            if let Err(error) = crate::remove_mempool(&path) {
+                warn!("Failed to remove the restored memory pool file - {error}");
+            }
+        });
+    }
+
+    /// Saves the transactions of the memory pool, and those waiting to enter it, to the memory pool file.
+    fn save_mempool(&self, path: &Path) {
+        // Start with the transactions of the previous memory pool file that are not restored yet.
+        let mut transactions = self.pending_mempool.lock().iter().rev().cloned().collect::<Vec<_>>();
+        transactions.extend(self.consensus.queued_transactions());
+        for (transaction_id, transaction) in self.consensus.unconfirmed_transactions() {
+            match transaction.deserialize_blocking() {
+                Ok(transaction) => transactions.push(transaction),
+                Err(error) => warn!("Failed to save the transaction '{transaction_id}' of the memory pool - {error}"),
+            }
+        }
+        match crate::save_mempool(path, &transactions) {
+            Ok(()) => info!("Saved {} transactions of the memory pool", transactions.len()),
+            Err(error) => warn!("Failed to save the memory pool - {error}"),
+        }
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     pub fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
         self.handles.lock().push(tokio::spawn(future));
@@ -430,6 +563,12 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
     async fn shut_down(&self) {
         info!("Shutting down...");
 
+        // Shut down the REST server first, so it drains the in-flight requests.
+        if let Some(rest) = &self.rest {
+            trace!("Shutting down the REST server...");
+            rest.shut_down().await;
+        }
+
         // Shut down the node.
         trace!("Shutting down the node...");
         self.shutdown.store(true, std::sync::atomic::Ordering::Relaxed);
@@ -441,6 +580,11 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
         // Shut down the router.
         self.router.shut_down().await;
 
+        // Save the memory pool, so its transactions are restored when the node restarts.
+        if let Some(path) = &self.mempool_path {
+            self.save_mempool(path);
+        }
+
         // Shut down consensus.
         trace!("Shutting down consensus...");
         self.consensus.shut_down().await;
@@ -452,6 +596,7 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use snarkos_node_bft::ledger_service::DEFAULT_LEDGER_CACHE_SIZE;
     use snarkvm::prelude::{
         store::{helpers::memory::ConsensusMemory, ConsensusStore},
         MainnetV0,
@@ -489,13 +634,16 @@ mod tests {
             node,
             None,
             Some(rest),
-            10,
+            RestConfig::default(),
             account,
             &[],
             &[],
             genesis,
             None,
             storage_mode,
+            DEFAULT_LEDGER_CACHE_SIZE,
+            crate::DEFAULT_MIN_FREE_DISK_MB,
+            MempoolPolicy::new::<CurrentNetwork>(),
         )
         .await
         .unwrap();
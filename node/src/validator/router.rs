@@ -96,6 +96,11 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Validator<N, C> {
     fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
         Default::default()
     }
+
+    /// Returns the depth of the outbound message queue for the given peer address.
+    fn message_queue_depth(&self, peer_addr: SocketAddr) -> usize {
+        self.router().message_queue_depth(peer_addr, Self::MESSAGE_QUEUE_DEPTH)
+    }
 }
 
 #[async_trait]
@@ -146,7 +151,7 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         let BlockRequest { start_height, end_height } = &message;
 
         // Retrieve the blocks within the requested range.
-        let blocks = match self.ledger.get_blocks(*start_height..*end_height) {
+        let blocks = match self.ledger_cache.get_blocks(&self.ledger, *start_height..*end_height) {
             Ok(blocks) => Data::Object(DataBlocks(blocks)),
             Err(error) => {
                 error!("Failed to retrieve blocks {start_height} to {end_height} from the ledger - {error}");
@@ -257,8 +262,13 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         serialized: UnconfirmedTransaction<N>,
         transaction: Transaction<N>,
     ) -> bool {
+        // Use the size of the received bytes, so the transaction is not serialized again.
+        let size = match &serialized.transaction {
+            Data::Buffer(bytes) => Some(bytes.len()),
+            Data::Object(_) => None,
+        };
         // Add the unconfirmed transaction to the memory pool.
-        if let Err(error) = self.consensus.add_unconfirmed_transaction(transaction).await {
+        if let Err(error) = self.consensus.add_unconfirmed_transaction(transaction, size).await {
             trace!("[UnconfirmedTransaction] {error}");
             return true; // Maintain the connection.
         }
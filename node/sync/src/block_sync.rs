@@ -13,7 +13,14 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{PeerPair, SyncRequest},
+    helpers::{
+        OutstandingRequest,
+        PeerPair,
+        SyncDebugState,
+        SyncProgress,
+        SyncRequest,
+        SYNC_PROGRESS_CHANNEL_CAPACITY,
+    },
     locators::BlockLocators,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
@@ -33,8 +40,9 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
+use tokio::sync::broadcast;
 
 #[cfg(not(test))]
 pub const REDUNDANCY_FACTOR: usize = 1;
@@ -46,6 +54,7 @@ const NUM_SYNC_CANDIDATE_PEERS: usize = REDUNDANCY_FACTOR * 5;
 const BLOCK_REQUEST_TIMEOUT_IN_SECS: u64 = 600; // 600 seconds
 const MAX_BLOCK_REQUESTS: usize = 50; // 50 requests
 const MAX_BLOCK_REQUEST_TIMEOUTS: usize = 5; // 5 timeouts
+const MAX_VALIDATION_TIMES: usize = 1_000; // 1,000 blocks
 
 /// The maximum number of blocks tolerated before the primary is considered behind its peers.
 pub const MAX_BLOCKS_BEHIND: u32 = 1; // blocks
@@ -109,8 +118,18 @@ pub struct BlockSync<N: Network> {
     request_timeouts: Arc<RwLock<IndexMap<SocketAddr, Vec<Instant>>>>,
     /// The boolean indicator of whether the node is synced up to the latest block (within the given tolerance).
     is_block_synced: Arc<AtomicBool>,
+    /// The boolean indicator of whether block sync is paused, such as while the node is low on disk space.
+    is_paused: Arc<AtomicBool>,
     /// The lock to guarantee advance_with_sync_blocks() is called only once at a time.
     advance_with_sync_blocks_lock: Arc<Mutex<()>>,
+    /// The start height and time of the current sync session, if the node is syncing.
+    sync_start: Arc<Mutex<Option<(u32, Instant)>>>,
+    /// The latest sync progress snapshot.
+    latest_progress: Arc<RwLock<Option<SyncProgress>>>,
+    /// The sender for sync progress events.
+    progress_sender: broadcast::Sender<SyncProgress>,
+    /// The map of block height to the time it took to check and add the block, for the most recently synced blocks.
+    validation_times: Arc<RwLock<BTreeMap<u32, Duration>>>,
 }
 
 impl<N: Network> BlockSync<N> {
@@ -126,7 +145,12 @@ impl<N: Network> BlockSync<N> {
             request_timestamps: Default::default(),
             request_timeouts: Default::default(),
             is_block_synced: Default::default(),
+            is_paused: Default::default(),
             advance_with_sync_blocks_lock: Default::default(),
+            sync_start: Default::default(),
+            latest_progress: Default::default(),
+            progress_sender: broadcast::channel(SYNC_PROGRESS_CHANNEL_CAPACITY).0,
+            validation_times: Default::default(),
         }
     }
 
@@ -141,15 +165,79 @@ impl<N: Network> BlockSync<N> {
     pub fn is_block_synced(&self) -> bool {
         self.is_block_synced.load(Ordering::SeqCst)
     }
+
+    /// Returns `true` if block sync is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses or resumes block sync. While paused, no blocks are requested or added to the ledger.
+    pub fn set_paused(&self, is_paused: bool) {
+        self.is_paused.store(is_paused, Ordering::SeqCst);
+    }
+
+    /// Returns the latest sync progress snapshot, if one has been recorded.
+    pub fn sync_progress(&self) -> Option<SyncProgress> {
+        self.latest_progress.read().clone()
+    }
+
+    /// Returns the time it took to check and add the block at the given height,
+    /// if the block is among the most recent blocks added through block sync.
+    pub fn validation_time(&self, height: u32) -> Option<Duration> {
+        self.validation_times.read().get(&height).copied()
+    }
+
+    /// Returns a detailed snapshot of the block sync state, for debugging.
+    pub fn debug_state(&self) -> SyncDebugState {
+        // Note: Each map is read separately, so no two locks are held at once.
+        let requests = self
+            .requests
+            .read()
+            .iter()
+            .map(|(height, (_, _, sync_ips))| (*height, sync_ips.iter().copied().collect()))
+            .collect::<Vec<_>>();
+        let requests = requests
+            .into_iter()
+            .map(|(height, sync_ips)| OutstandingRequest {
+                height,
+                sync_ips,
+                has_response: self.responses.read().contains_key(&height),
+                pending_secs: self
+                    .request_timestamps
+                    .read()
+                    .get(&height)
+                    .map(|timestamp| timestamp.elapsed().as_secs()),
+            })
+            .collect();
+        SyncDebugState {
+            is_block_synced: self.is_block_synced(),
+            canon_height: self.canon.latest_block_height(),
+            peer_heights: self
+                .locators
+                .read()
+                .iter()
+                .map(|(peer_ip, locators)| (*peer_ip, locators.latest_locator_height()))
+                .collect(),
+            requests,
+            request_timeouts: self
+                .request_timeouts
+                .read()
+                .iter()
+                .map(|(peer_ip, timeouts)| (*peer_ip, timeouts.len()))
+                .collect(),
+        }
+    }
+
+    /// Returns a receiver for sync progress events.
+    /// Note: Slow receivers skip the oldest events, as the channel is bounded.
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<SyncProgress> {
+        self.progress_sender.subscribe()
+    }
 }
 
 #[allow(dead_code)]
 impl<N: Network> BlockSync<N> {
-    /// Returns the latest block height of the given peer IP.
-    fn get_peer_height(&self, peer_ip: &SocketAddr) -> Option<u32> {
-        self.locators.read().get(peer_ip).map(|locators| locators.latest_locator_height())
-    }
-
     // /// Returns a map of peer height to peer IPs.
     // /// e.g. `{{ 127 => \[peer1, peer2\], 128 => \[peer3\], 135 => \[peer4, peer5\] }}`
     // fn get_peer_heights(&self) -> BTreeMap<u32, Vec<SocketAddr>> {
@@ -216,6 +304,12 @@ impl<N: Network> BlockSync<N> {
     /// Performs one iteration of the block sync.
     #[inline]
     pub async fn try_block_sync<C: CommunicationService>(&self, communication: &C) {
+        // If block sync is paused, neither request nor add blocks.
+        if self.is_paused() {
+            trace!("Block sync is paused");
+            return;
+        }
+
         // Prepare the block requests, if any.
         // In the process, we update the state of `is_block_synced` for the sync module.
         let block_requests = self.prepare_block_requests();
@@ -280,6 +374,10 @@ impl<N: Network> BlockSync<N> {
     /// Returns the next block to process, if one is ready.
     #[inline]
     pub fn process_next_block(&self, next_height: u32) -> Option<Block<N>> {
+        // If block sync is paused, hold the block in the sync pool.
+        if self.is_paused() {
+            return None;
+        }
         // Try to advance the ledger with a block from the sync pool.
         self.remove_block_response(next_height)
     }
@@ -297,6 +395,11 @@ impl<N: Network> BlockSync<N> {
             return Ok(());
         };
 
+        // If block sync is paused, hold the blocks in the sync pool.
+        if self.is_paused() {
+            return Ok(());
+        }
+
         // Retrieve the latest block height.
         let current_height = self.canon.latest_block_height();
         // Try to advance the ledger with the sync pool.
@@ -312,6 +415,7 @@ impl<N: Network> BlockSync<N> {
                 warn!("Block height mismatch: expected {}, found {}", current_height + 1, block.height());
                 break;
             }
+            let timer = Instant::now();
             // Check the next block.
             if let Err(error) = self.canon.check_next_block(&block) {
                 warn!("The next block ({}) is invalid - {error}", block.height());
@@ -322,6 +426,13 @@ impl<N: Network> BlockSync<N> {
                 warn!("{error}");
                 break;
             }
+            // Record the validation time, evicting the oldest entry if the map is full.
+            let mut validation_times = self.validation_times.write();
+            validation_times.insert(block.height(), timer.elapsed());
+            if validation_times.len() > MAX_VALIDATION_TIMES {
+                validation_times.pop_first();
+            }
+            drop(validation_times);
             // Update the latest height.
             current_height = self.canon.latest_block_height();
         }
@@ -344,6 +455,11 @@ impl<N: Network> BlockSync<N> {
         }
     }
 
+    /// Returns the latest block height of the given peer, if its block locators are known.
+    pub fn get_peer_height(&self, peer_ip: &SocketAddr) -> Option<u32> {
+        self.locators.read().get(peer_ip).map(|locators| locators.latest_locator_height())
+    }
+
     /// Updates the block locators and common ancestors for the given peer IP.
     /// This function checks that the given block locators are well-formed, however it does **not** check
     /// that the block locators are consistent the peer's previous block locators or other peers' block locators.
@@ -418,11 +534,15 @@ impl<N: Network> BlockSync<N> {
             let greatest_peer_height = sync_peers.values().map(|l| l.latest_locator_height()).max().unwrap_or(0);
             // Update the state of `is_block_synced` for the sync module.
             self.update_is_block_synced(greatest_peer_height, MAX_BLOCKS_BEHIND);
+            // Update the sync progress.
+            self.update_sync_progress(greatest_peer_height, sync_peers.keys().copied().collect());
             // Return the list of block requests.
             self.construct_requests(sync_peers, min_common_ancestor, &mut rand::thread_rng())
         } else {
             // Update the state of `is_block_synced` for the sync module.
             self.update_is_block_synced(0, MAX_BLOCKS_BEHIND);
+            // Update the sync progress.
+            self.update_sync_progress(0, vec![]);
             // Return an empty list of block requests.
             Vec::new()
         }
@@ -443,6 +563,43 @@ impl<N: Network> BlockSync<N> {
         self.is_block_synced.store(is_synced, Ordering::SeqCst);
     }
 
+    /// Updates the sync progress snapshot, and notifies any subscribers.
+    fn update_sync_progress(&self, greatest_peer_height: u32, sync_peers: Vec<SocketAddr>) {
+        // Retrieve the latest block height.
+        let current_height = self.canon.latest_block_height();
+        // Determine the target height, which is never below the current height.
+        let target_height = greatest_peer_height.max(current_height);
+        // Determine if the node is synced.
+        let is_synced = self.is_block_synced();
+
+        // Retrieve the start of the sync session, opening or closing it as needed.
+        let session = {
+            let mut sync_start = self.sync_start.lock();
+            match (current_height < target_height, *sync_start) {
+                // The node is syncing, so retrieve (or start) the session.
+                (true, _) => *sync_start.get_or_insert((current_height, Instant::now())),
+                // The node caught up, so close the session.
+                (false, Some(session)) => {
+                    *sync_start = None;
+                    session
+                }
+                // The node is not syncing, and was not syncing.
+                (false, None) => (current_height, Instant::now()),
+            }
+        };
+
+        // Construct the progress snapshot.
+        let progress = SyncProgress::new(session, current_height, target_height, sync_peers, is_synced);
+        // If the progress is unchanged, return early.
+        if self.latest_progress.read().as_ref() == Some(&progress) {
+            return;
+        }
+        // Update the latest progress.
+        *self.latest_progress.write() = Some(progress.clone());
+        // Notify the subscribers. Note: This only fails if there are no subscribers.
+        let _ = self.progress_sender.send(progress);
+    }
+
     /// Inserts a block request for the given height.
     fn insert_block_request(&self, height: u32, (hash, previous_hash, sync_ips): SyncRequest<N>) -> Result<()> {
         // Ensure the block request does not already exist.
@@ -974,6 +1131,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_paused() {
+        let sync = sample_sync_at_height(0);
+        assert!(!sync.is_paused());
+        sync.set_paused(true);
+        assert!(sync.is_paused());
+        // A clone shares the paused state.
+        assert!(sync.clone().is_paused());
+        sync.set_paused(false);
+        assert!(!sync.is_paused());
+    }
+
     #[test]
     fn test_get_block_height() {
         for height in 0..100_002u32 {
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Serialize;
+use std::{collections::BTreeMap, net::SocketAddr};
+
+/// An outstanding block request.
+#[derive(Clone, Debug, Serialize)]
+pub struct OutstandingRequest {
+    /// The requested block height.
+    pub height: u32,
+    /// The peers the block was requested from, which have not responded yet.
+    pub sync_ips: Vec<SocketAddr>,
+    /// Whether the block has been received, and is waiting to be advanced to.
+    pub has_response: bool,
+    /// The number of seconds since the block was requested.
+    pub pending_secs: Option<u64>,
+}
+
+/// A detailed snapshot of the block sync state, for debugging.
+#[derive(Clone, Debug, Serialize)]
+pub struct SyncDebugState {
+    /// Whether the node is synced up to the latest block.
+    pub is_block_synced: bool,
+    /// The latest canonical block height.
+    pub canon_height: u32,
+    /// The latest block height advertised by each peer.
+    pub peer_heights: BTreeMap<SocketAddr, u32>,
+    /// The outstanding block requests, by height.
+    pub requests: Vec<OutstandingRequest>,
+    /// The number of request timeouts recorded for each peer.
+    pub request_timeouts: BTreeMap<SocketAddr, usize>,
+}
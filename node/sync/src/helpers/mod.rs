@@ -12,6 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod debug_state;
+pub use debug_state::*;
+
+mod progress;
+pub use progress::*;
+
 use snarkvm::prelude::Network;
 
 use core::hash::Hash;
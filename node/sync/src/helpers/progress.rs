@@ -0,0 +1,86 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, time::Instant};
+
+/// The capacity of the sync progress channel.
+pub const SYNC_PROGRESS_CHANNEL_CAPACITY: usize = 64;
+
+/// A structured snapshot of the block sync progress.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncProgress {
+    /// The block height at which the current sync session started.
+    pub start_height: u32,
+    /// The current block height of the ledger.
+    pub current_height: u32,
+    /// The greatest block height advertised by the sync peers.
+    pub target_height: u32,
+    /// The average number of blocks advanced per second, since the sync session started.
+    pub blocks_per_sec: f64,
+    /// The estimated number of seconds remaining, if it can be computed.
+    pub eta_secs: Option<u64>,
+    /// The peers currently being synced from.
+    pub sync_peers: Vec<SocketAddr>,
+    /// Whether the node is synced up to the target height.
+    pub is_synced: bool,
+}
+
+impl SyncProgress {
+    /// Initializes a new sync progress snapshot, given the start of the sync session.
+    pub(crate) fn new(
+        (start_height, start_time): (u32, Instant),
+        current_height: u32,
+        target_height: u32,
+        sync_peers: Vec<SocketAddr>,
+        is_synced: bool,
+    ) -> Self {
+        // Compute the average number of blocks per second.
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let num_advanced = current_height.saturating_sub(start_height);
+        let blocks_per_sec = if elapsed > 0.0 { num_advanced as f64 / elapsed } else { 0.0 };
+        // Estimate the time remaining.
+        let num_remaining = target_height.saturating_sub(current_height);
+        let eta_secs = match num_remaining {
+            0 => Some(0),
+            _ if blocks_per_sec > 0.0 => Some((num_remaining as f64 / blocks_per_sec).ceil() as u64),
+            _ => None,
+        };
+
+        Self { start_height, current_height, target_height, blocks_per_sec, eta_secs, sync_peers, is_synced }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sync_progress_eta() {
+        // A session that advanced 100 blocks in (at least) 10 seconds.
+        let start = Instant::now() - Duration::from_secs(10);
+        let progress = SyncProgress::new((0, start), 100, 200, vec![], false);
+        assert!(progress.blocks_per_sec > 0.0 && progress.blocks_per_sec <= 10.0);
+        assert!(progress.eta_secs.unwrap() >= 10);
+
+        // A session that has not advanced has no estimate.
+        let progress = SyncProgress::new((100, Instant::now()), 100, 200, vec![], false);
+        assert_eq!(progress.eta_secs, None);
+
+        // A session that reached the target has no time remaining.
+        let progress = SyncProgress::new((0, start), 200, 200, vec![], true);
+        assert_eq!(progress.eta_secs, Some(0));
+    }
+}
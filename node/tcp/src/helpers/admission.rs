@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Instant,
+};
+
+use parking_lot::Mutex;
+
+/// The number of subnet buckets above which fully-refilled buckets are pruned.
+const MAX_TRACKED_SUBNETS: usize = 4096;
+
+/// A token bucket, refilled continuously at a fixed rate up to its capacity.
+#[derive(Debug)]
+pub struct TokenBucket {
+    /// The maximum number of tokens.
+    capacity: f64,
+    /// The number of tokens added per second.
+    refill_per_sec: f64,
+    /// The current number of tokens.
+    tokens: f64,
+    /// The last time the bucket was refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Initializes a new, full token bucket with the given capacity and refill rate.
+    pub fn new(capacity: u32, refill_per_sec: u32) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_per_sec: refill_per_sec as f64,
+            tokens: capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Refills the bucket based on the time elapsed since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Returns `true` if the bucket has at least one token available.
+    fn has_token(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= 1.0
+    }
+
+    /// Attempts to take a token from the bucket, returning `true` on success.
+    pub fn try_take(&mut self, now: Instant) -> bool {
+        match self.has_token(now) {
+            true => {
+                self.tokens -= 1.0;
+                true
+            }
+            false => false,
+        }
+    }
+
+    /// Returns `true` if the bucket is at capacity.
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+}
+
+/// Rate-limits the acceptance of inbound connections, globally and per source subnet.
+///
+/// Admission is checked when the connection is accepted, before any handshake work is performed.
+#[derive(Debug)]
+pub struct InboundAdmission {
+    /// The global token bucket, if enabled.
+    global: Option<Mutex<TokenBucket>>,
+    /// The maximum number of inbound connections per second from a single subnet (`0` disables the limit).
+    per_subnet_rate: u16,
+    /// The token buckets for each source subnet.
+    subnets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl InboundAdmission {
+    /// Initializes a new admission controller with the given global and per-subnet rates (per second).
+    /// A rate of `0` disables the corresponding limit.
+    pub fn new(global_rate: u16, per_subnet_rate: u16) -> Self {
+        let global = (global_rate > 0).then(|| Mutex::new(TokenBucket::new(global_rate as u32, global_rate as u32)));
+        Self { global, per_subnet_rate, subnets: Default::default() }
+    }
+
+    /// Returns `true` if an inbound connection from the given IP should be accepted,
+    /// consuming a token from both the global and the subnet bucket.
+    ///
+    /// Loopback addresses are exempt from the per-subnet limit, as local development networks connect from it.
+    pub fn try_admit(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+
+        // Acquire the global bucket first, so that a rejected subnet does not consume a global token.
+        let mut global = self.global.as_ref().map(|global| global.lock());
+        if let Some(global) = global.as_mut() {
+            if !global.has_token(now) {
+                return false;
+            }
+        }
+
+        // Check the per-subnet bucket.
+        if self.per_subnet_rate > 0 && !ip.is_loopback() {
+            let mut subnets = self.subnets.lock();
+            // Bound the number of tracked subnets, by pruning buckets which have fully refilled.
+            if subnets.len() >= MAX_TRACKED_SUBNETS {
+                subnets.retain(|_, bucket| !bucket.is_full(now));
+            }
+            let rate = self.per_subnet_rate as u32;
+            let bucket = subnets.entry(subnet_of(ip)).or_insert_with(|| TokenBucket::new(rate, rate));
+            if !bucket.try_take(now) {
+                return false;
+            }
+        }
+
+        // Consume the global token.
+        if let Some(global) = global.as_mut() {
+            global.try_take(now);
+        }
+        true
+    }
+}
+
+/// Returns the subnet of the given IP, as a /24 for IPv4 and a /48 for IPv6.
+fn subnet_of(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(ipv4) => {
+            let [a, b, c, _] = ipv4.octets();
+            IpAddr::V4(Ipv4Addr::new(a, b, c, 0))
+        }
+        IpAddr::V6(ipv6) => {
+            let [a, b, c, ..] = ipv6.segments();
+            IpAddr::V6(Ipv6Addr::new(a, b, c, 0, 0, 0, 0, 0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_subnet_admission() {
+        let admission = InboundAdmission::new(0, 2);
+
+        let ip_a: IpAddr = "1.2.3.4".parse().unwrap();
+        let ip_b: IpAddr = "1.2.3.5".parse().unwrap();
+        let ip_c: IpAddr = "5.6.7.8".parse().unwrap();
+
+        // The same /24 shares a bucket.
+        assert!(admission.try_admit(ip_a));
+        assert!(admission.try_admit(ip_b));
+        assert!(!admission.try_admit(ip_a));
+        // A different subnet has its own bucket.
+        assert!(admission.try_admit(ip_c));
+        // Loopback is exempt from the per-subnet limit.
+        for _ in 0..10 {
+            assert!(admission.try_admit(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        }
+    }
+
+    #[test]
+    fn test_global_admission() {
+        let admission = InboundAdmission::new(3, 0);
+        for i in 0..3 {
+            assert!(admission.try_admit(IpAddr::V4(Ipv4Addr::new(10, i, 0, 1))));
+        }
+        assert!(!admission.try_admit(IpAddr::V4(Ipv4Addr::new(10, 9, 0, 1))));
+    }
+}
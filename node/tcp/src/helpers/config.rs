@@ -54,6 +54,14 @@ pub struct Config {
     pub max_connections: u16,
     /// The maximum time (in milliseconds) allowed to establish a raw (before the [`Handshake`] protocol) TCP connection.
     pub connection_timeout_ms: u16,
+    /// The maximum number of inbound connections accepted per second, across all sources.
+    ///
+    /// note: Inbound connections above this rate are dropped before the [`Handshake`] protocol. Set to `0` to disable.
+    pub max_inbound_connections_per_sec: u16,
+    /// The maximum number of inbound connections accepted per second from a single subnet (/24 for IPv4, /48 for IPv6).
+    ///
+    /// note: Loopback addresses are exempt from this limit. Set to `0` to disable.
+    pub max_inbound_connections_per_subnet_per_sec: u16,
 }
 
 impl Config {
@@ -90,6 +98,8 @@ impl Default for Config {
             fatal_io_errors: vec![ConnectionReset, ConnectionAborted, BrokenPipe, InvalidData, UnexpectedEof],
             max_connections: 100,
             connection_timeout_ms: 1_000,
+            max_inbound_connections_per_sec: 100,
+            max_inbound_connections_per_subnet_per_sec: 5,
         }
     }
 }
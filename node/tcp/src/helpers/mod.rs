@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod admission;
+pub use admission::{InboundAdmission, TokenBucket};
+
 mod config;
 pub use config::Config;
 
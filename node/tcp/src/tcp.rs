@@ -40,6 +40,7 @@ use crate::{
     connections::{Connection, ConnectionSide, Connections},
     protocols::{Protocol, Protocols},
     Config,
+    InboundAdmission,
     KnownPeers,
     Stats,
 };
@@ -71,6 +72,8 @@ pub struct InnerTcp {
     pub(crate) protocols: Protocols,
     /// A set of connections that have not been finalized yet.
     connecting: Mutex<HashSet<SocketAddr>>,
+    /// The rate limiter for inbound connections.
+    admission: InboundAdmission,
     /// Contains objects related to the node's active connections.
     connections: Connections,
     /// Collects statistics related to the node's peers.
@@ -92,6 +95,12 @@ impl Tcp {
         // Create a tracing span containing the node's name.
         let span = crate::helpers::create_span(config.name.as_deref().unwrap());
 
+        // Initialize the inbound connection rate limiter.
+        let admission = InboundAdmission::new(
+            config.max_inbound_connections_per_sec,
+            config.max_inbound_connections_per_subnet_per_sec,
+        );
+
         // Initialize the Tcp stack.
         let tcp = Tcp(Arc::new(InnerTcp {
             span,
@@ -99,6 +108,7 @@ impl Tcp {
             listening_addr: Default::default(),
             protocols: Default::default(),
             connecting: Default::default(),
+            admission,
             connections: Default::default(),
             known_peers: Default::default(),
             stats: Default::default(),
@@ -370,6 +380,11 @@ impl Tcp {
             return;
         }
 
+        if !self.admission.try_admit(addr.ip()) {
+            debug!(parent: self.span(), "Rejecting the connection from {addr} (inbound rate limit reached)");
+            return;
+        }
+
         self.connecting.lock().insert(addr);
 
         let tcp = self.clone();
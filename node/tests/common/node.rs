@@ -14,7 +14,16 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{
+    bft::ledger_service::DEFAULT_LEDGER_CACHE_SIZE,
+    consensus::MempoolPolicy,
+    rest::RestConfig,
+    Client,
+    Prover,
+    ProverThreads,
+    Validator,
+    DEFAULT_MIN_FREE_DISK_MB,
+};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, MainnetV0 as CurrentNetwork};
 
 use aleo_std::StorageMode;
@@ -24,12 +33,14 @@ pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
     Client::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
-        10,
+        RestConfig::default(),
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
         None, // No CDN.
         StorageMode::Production,
+        DEFAULT_LEDGER_CACHE_SIZE,
+        DEFAULT_MIN_FREE_DISK_MB,
     )
     .await
     .expect("couldn't create client instance")
@@ -42,6 +53,7 @@ pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
         &[],
         sample_genesis_block(),
         StorageMode::Production,
+        ProverThreads::default(),
     )
     .await
     .expect("couldn't create prover instance")
@@ -52,13 +64,16 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
         "127.0.0.1:0".parse().unwrap(),
         None,
         None,
-        10,
+        RestConfig::default(),
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         &[],
         sample_genesis_block(), // Should load the current network's genesis block.
         None,                   // No CDN.
         StorageMode::Production,
+        DEFAULT_LEDGER_CACHE_SIZE,
+        DEFAULT_MIN_FREE_DISK_MB,
+        MempoolPolicy::new::<CurrentNetwork>(),
     )
     .await
     .expect("couldn't create validator instance")